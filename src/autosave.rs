@@ -0,0 +1,175 @@
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+    path::{Path, PathBuf},
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{StorageError, instrument::report_unhandled, save, try_load};
+
+/// A value that is saved to its JSON file when it goes out of scope.
+///
+/// `Autosave` owns a `T` and dereferences to it, so it can be read and modified like the value
+/// itself; when it is dropped, the current value is written with [`save`](crate::save). Call
+/// [`save_now`](Autosave::save_now) to persist intermediate states or to find out whether a
+/// write succeeded.
+///
+/// A failed save during drop cannot be returned, so it is logged instead: as an `ERROR` event
+/// with the `tracing` feature, otherwise on standard error. The drop-time save is a best effort
+/// in other ways too. While a panic unwinds, the value may be in whatever half-updated state
+/// the panicking code left it in, and that state is what gets written; nothing is saved at all
+/// if the process aborts or exits without unwinding, as with `panic = "abort"` or
+/// [`std::process::exit`].
+///
+/// # Example
+///
+/// ```
+/// use serde::{Serialize, Deserialize};
+/// use storage_service::{Autosave, load};
+/// use tempfile::tempdir;
+///
+/// #[derive(Serialize, Deserialize, Default)]
+/// struct Session {
+///     pages_viewed: u32,
+/// }
+///
+/// let dir = tempdir().unwrap();
+/// let path = dir.path().join("session.json");
+///
+/// {
+///     let mut session: Autosave<Session> = Autosave::open(&path).unwrap();
+///     session.pages_viewed += 3;
+/// }
+///
+/// let saved: Session = load(&path).unwrap();
+/// assert_eq!(saved.pages_viewed, 3);
+/// ```
+pub struct Autosave<T: Serialize> {
+    path: PathBuf,
+    value: T,
+}
+
+impl<T: Serialize> Autosave<T> {
+    /// Wraps `value`, to be saved to `path`; nothing is written until a save.
+    pub fn new<P: AsRef<Path>>(path: P, value: T) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            value,
+        }
+    }
+
+    /// Loads the value stored at `path`, or starts from `T::default()` if the file is missing.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Autosave<T>)` - The wrapped value
+    /// * `Err(StorageError)` - If the file exists but cannot be read or deserialized
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, StorageError>
+    where
+        T: DeserializeOwned + Default,
+    {
+        let value = try_load(path.as_ref())?.unwrap_or_default();
+
+        Ok(Self::new(path, value))
+    }
+
+    /// Saves the current value immediately; it is still saved again on drop.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+    /// * `Err(StorageError)` - If file creation or writing fails, or if serialization fails
+    pub fn save_now(&self) -> Result<usize, StorageError> {
+        save(&self.path, &self.value)
+    }
+
+    /// Returns the path the value is saved to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl<T: Serialize> Deref for Autosave<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: Serialize> DerefMut for Autosave<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T: Serialize> Drop for Autosave<T> {
+    fn drop(&mut self) {
+        if let Err(err) = self.save_now() {
+            report_unhandled("autosave", &self.path, &err);
+        }
+    }
+}
+
+impl<T: Serialize + fmt::Debug> fmt::Debug for Autosave<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Autosave")
+            .field("path", &self.path)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::load;
+
+    #[test]
+    fn autosave_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("tags.json");
+
+        let mut tags = Autosave::new(&path, vec!["a".to_string()]);
+        tags.push("b".to_string());
+        assert!(!path.exists());
+
+        tags.save_now().unwrap();
+        assert_eq!(load::<_, Vec<String>>(&path).unwrap(), ["a", "b"]);
+
+        tags.push("c".to_string());
+        drop(tags);
+        assert_eq!(load::<_, Vec<String>>(&path).unwrap(), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn autosave_open_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("count.json");
+
+        let mut count: Autosave<u32> = Autosave::open(&path).unwrap();
+        assert_eq!(*count, 0);
+        *count += 5;
+        drop(count);
+
+        let count: Autosave<u32> = Autosave::open(&path).unwrap();
+        assert_eq!(*count, 5);
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(Autosave::<u32>::open(&path).is_err());
+    }
+
+    #[test]
+    fn autosave_drop_failure_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("missing").join("value.json");
+
+        let value = Autosave::new(&path, 1);
+        assert!(value.save_now().is_err());
+        drop(value);
+        assert!(!path.exists());
+    }
+}
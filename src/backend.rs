@@ -0,0 +1,391 @@
+use std::{
+    collections::HashMap,
+    fmt, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{Clock, Format, Json, StorageError, SystemClock, sanitize_filename, write_atomic};
+
+/// A key-value store for serialized data.
+///
+/// This abstracts over where bytes are kept, so code built on top of it can run against the
+/// filesystem in production via [`FileBackend`] and against memory in tests via
+/// [`MemoryBackend`]. The provided [`save`](StorageBackend::save) and
+/// [`load`](StorageBackend::load) methods add JSON serialization on top of the raw byte
+/// operations.
+///
+/// A missing key is reported as [`StorageError::Io`] with [`io::ErrorKind::NotFound`] by every
+/// backend, so callers can handle it the same way they would for a missing file.
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{MemoryBackend, StorageBackend};
+///
+/// let backend = MemoryBackend::new();
+/// backend.save("answer", 42).unwrap();
+///
+/// let answer: u32 = backend.load("answer").unwrap();
+/// assert_eq!(answer, 42);
+/// ```
+pub trait StorageBackend {
+    /// Returns the bytes stored under `key`.
+    fn get(&self, key: &str) -> Result<Vec<u8>, StorageError>;
+
+    /// Stores `bytes` under `key`, replacing any previous value.
+    fn put(&self, key: &str, bytes: &[u8]) -> Result<(), StorageError>;
+
+    /// Removes the value stored under `key`.
+    fn delete(&self, key: &str) -> Result<(), StorageError>;
+
+    /// Returns every stored key, in no particular order.
+    fn keys(&self) -> Result<Vec<String>, StorageError>;
+
+    /// Returns when the value stored under `key` was last written.
+    fn modified(&self, key: &str) -> Result<SystemTime, StorageError>;
+
+    /// Serializes `data` as JSON and stores it under `key`.
+    fn save<T>(&self, key: &str, data: T) -> Result<(), StorageError>
+    where
+        Self: Sized,
+        T: Serialize,
+    {
+        self.put(key, &Json::serialize(&data)?)
+    }
+
+    /// Loads and deserializes the JSON value stored under `key`.
+    fn load<T>(&self, key: &str) -> Result<T, StorageError>
+    where
+        Self: Sized,
+        T: DeserializeOwned,
+    {
+        Json::deserialize(&self.get(key)?)
+    }
+
+    /// Deletes every value last written more than `age` before the time of `clock`, returning
+    /// the number of values deleted.
+    ///
+    /// Keys that disappear while the purge runs, for example removed by a concurrent purge, are
+    /// skipped. Any other error stops the purge and is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::{sync::Arc, time::{Duration, UNIX_EPOCH}};
+    /// use storage_service::{ManualClock, MemoryBackend, StorageBackend};
+    ///
+    /// let clock = Arc::new(ManualClock::new(UNIX_EPOCH));
+    /// let backend = MemoryBackend::with_clock(clock.clone());
+    /// backend.save("session", "stale").unwrap();
+    ///
+    /// clock.advance(Duration::from_secs(2 * 60 * 60));
+    /// let purged = backend.purge_older_than(Duration::from_secs(60 * 60), clock.as_ref());
+    /// assert_eq!(purged.unwrap(), 1);
+    /// ```
+    fn purge_older_than<C>(&self, age: Duration, clock: &C) -> Result<usize, StorageError>
+    where
+        Self: Sized,
+        C: Clock + ?Sized,
+    {
+        let cutoff = clock
+            .now()
+            .checked_sub(age)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+
+        let mut purged = 0;
+        for key in self.keys()? {
+            match self.modified(&key) {
+                Ok(modified) if modified < cutoff => {}
+                Ok(_) => continue,
+                Err(err) if is_not_found(&err) => continue,
+                Err(err) => return Err(err),
+            }
+
+            match self.delete(&key) {
+                Ok(()) => purged += 1,
+                Err(err) if is_not_found(&err) => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(purged)
+    }
+}
+
+/// A [`StorageBackend`] that keeps each key in its own file under a root directory.
+///
+/// Writes go through the same temporary-file-and-rename path as [`save`](crate::save). Keys
+/// are relative file names resolved with [`sanitize_filename`], so a key containing `..` or an
+/// absolute path is rejected instead of reaching outside the root.
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{FileBackend, StorageBackend};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let backend = FileBackend::new(dir.path());
+///
+/// backend.put("greeting.json", b"\"hello\"").unwrap();
+/// assert!(dir.path().join("greeting.json").exists());
+/// ```
+#[derive(Debug, Clone)]
+pub struct FileBackend {
+    root: PathBuf,
+}
+
+impl FileBackend {
+    /// Creates a backend storing files under `root`.
+    pub fn new<P: AsRef<Path>>(root: P) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Returns the root directory of the backend.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, StorageError> {
+        sanitize_filename(&self.root, key)
+    }
+}
+
+impl StorageBackend for FileBackend {
+    fn get(&self, key: &str) -> Result<Vec<u8>, StorageError> {
+        Ok(std::fs::read(self.path(key)?)?)
+    }
+
+    fn put(&self, key: &str, bytes: &[u8]) -> Result<(), StorageError> {
+        write_atomic(&self.path(key)?, |writer| Ok(writer.write_all(bytes)?))
+    }
+
+    fn delete(&self, key: &str) -> Result<(), StorageError> {
+        crate::delete(self.path(key)?)
+    }
+
+    /// Returns the names of the regular files directly under the root; names that are not
+    /// valid UTF-8 cannot be keys and are left out.
+    fn keys(&self) -> Result<Vec<String>, StorageError> {
+        let mut keys = Vec::new();
+        for entry in std::fs::read_dir(&self.root)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_file() {
+                continue;
+            }
+            if let Ok(key) = entry.file_name().into_string() {
+                keys.push(key);
+            }
+        }
+
+        Ok(keys)
+    }
+
+    fn modified(&self, key: &str) -> Result<SystemTime, StorageError> {
+        Ok(std::fs::metadata(self.path(key)?)?.modified()?)
+    }
+}
+
+/// A [`StorageBackend`] that keeps everything in memory.
+///
+/// Intended for tests of storage-dependent logic that should not touch the disk. Writes are
+/// timestamped with the backend's [`Clock`], so time-dependent logic such as
+/// [`purge_older_than`](StorageBackend::purge_older_than) can be tested with a
+/// [`ManualClock`](crate::ManualClock).
+pub struct MemoryBackend {
+    entries: Mutex<HashMap<String, Entry>>,
+    clock: Arc<dyn Clock>,
+}
+
+/// A value stored in a [`MemoryBackend`], with the time it was written.
+struct Entry {
+    bytes: Vec<u8>,
+    modified: SystemTime,
+}
+
+impl MemoryBackend {
+    /// Creates an empty in-memory backend that timestamps writes with the [`SystemClock`].
+    pub fn new() -> Self {
+        Self::with_clock(Arc::new(SystemClock))
+    }
+
+    /// Creates an empty in-memory backend that timestamps writes with `clock`.
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            entries: Mutex::default(),
+            clock,
+        }
+    }
+}
+
+impl Default for MemoryBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for MemoryBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        f.debug_struct("MemoryBackend")
+            .field("keys", &entries.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl StorageBackend for MemoryBackend {
+    fn get(&self, key: &str) -> Result<Vec<u8>, StorageError> {
+        let entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        entries
+            .get(key)
+            .map(|entry| entry.bytes.clone())
+            .ok_or_else(|| missing_key(key))
+    }
+
+    fn put(&self, key: &str, bytes: &[u8]) -> Result<(), StorageError> {
+        let entry = Entry {
+            bytes: bytes.to_vec(),
+            modified: self.clock.now(),
+        };
+        let mut entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        entries.insert(key.to_string(), entry);
+
+        Ok(())
+    }
+
+    fn delete(&self, key: &str) -> Result<(), StorageError> {
+        let mut entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        entries
+            .remove(key)
+            .map(drop)
+            .ok_or_else(|| missing_key(key))
+    }
+
+    fn keys(&self) -> Result<Vec<String>, StorageError> {
+        let entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        Ok(entries.keys().cloned().collect())
+    }
+
+    fn modified(&self, key: &str) -> Result<SystemTime, StorageError> {
+        let entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        entries
+            .get(key)
+            .map(|entry| entry.modified)
+            .ok_or_else(|| missing_key(key))
+    }
+}
+
+fn is_not_found(err: &StorageError) -> bool {
+    matches!(err, StorageError::Io(err) if err.kind() == io::ErrorKind::NotFound)
+}
+
+fn missing_key(key: &str) -> StorageError {
+    StorageError::Io(io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("key not found: {key}"),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::UNIX_EPOCH;
+
+    use super::*;
+    use crate::ManualClock;
+    use serde::Deserialize;
+    use tempfile::tempdir;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct TestData {
+        name: String,
+        value: i32,
+    }
+
+    fn exercise<B: StorageBackend>(backend: &B) {
+        let data = TestData {
+            name: "backend".to_string(),
+            value: 3,
+        };
+        backend.save("data.json", &data).unwrap();
+
+        let loaded: TestData = backend.load("data.json").unwrap();
+        assert_eq!(loaded, data);
+        assert_eq!(backend.keys().unwrap(), vec!["data.json".to_string()]);
+        assert!(backend.modified("data.json").is_ok());
+
+        backend.delete("data.json").unwrap();
+        assert!(matches!(
+            backend.get("data.json"),
+            Err(StorageError::Io(err)) if err.kind() == io::ErrorKind::NotFound
+        ));
+        assert!(backend.delete("data.json").is_err());
+        assert!(backend.keys().unwrap().is_empty());
+    }
+
+    #[test]
+    fn file_backend_test() {
+        let dir = tempdir().unwrap();
+        let backend = FileBackend::new(dir.path());
+
+        exercise(&backend);
+
+        backend.put("raw.json", b"[1]").unwrap();
+        let loaded: Vec<i32> = crate::load(dir.path().join("raw.json")).unwrap();
+        assert_eq!(loaded, vec![1]);
+
+        let escaped = backend.put("../escaped.json", b"[]");
+        assert!(matches!(
+            escaped,
+            Err(StorageError::Io(err)) if err.kind() == io::ErrorKind::InvalidInput
+        ));
+        assert!(!dir.path().parent().unwrap().join("escaped.json").exists());
+    }
+
+    #[test]
+    fn memory_backend_test() {
+        let backend = MemoryBackend::new();
+
+        exercise(&backend);
+
+        backend.put("raw", b"bytes").unwrap();
+        assert_eq!(backend.get("raw").unwrap(), b"bytes");
+    }
+
+    #[test]
+    fn purge_older_than_test() {
+        let hour = Duration::from_secs(60 * 60);
+        let clock = Arc::new(ManualClock::new(UNIX_EPOCH));
+        let backend = MemoryBackend::with_clock(clock.clone());
+
+        backend.put("old", b"1").unwrap();
+        clock.advance(3 * hour);
+        backend.put("new", b"2").unwrap();
+        assert_eq!(backend.modified("new").unwrap(), UNIX_EPOCH + 3 * hour);
+
+        clock.advance(hour);
+        assert_eq!(
+            backend.purge_older_than(2 * hour, clock.as_ref()).unwrap(),
+            1
+        );
+        assert_eq!(backend.keys().unwrap(), vec!["new".to_string()]);
+        assert_eq!(
+            backend.purge_older_than(2 * hour, clock.as_ref()).unwrap(),
+            0
+        );
+
+        clock.advance(hour);
+        assert_eq!(
+            backend
+                .purge_older_than(Duration::ZERO, clock.as_ref())
+                .unwrap(),
+            1
+        );
+        assert!(backend.keys().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,349 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+
+use crate::{SaveOptions, StorageError, parent_dir, with_suffix, write_atomic, write_json};
+
+/// Synchronously saves data to a JSON file, keeping the previous version as `<file>.bak`.
+///
+/// The data is serialized first, so a value that cannot be serialized fails without touching
+/// either file. If `path` already exists, its current contents are then copied to
+/// `<path>.bak`, replacing any older backup. The existing file is copied rather than renamed so `path` never
+/// disappears, even briefly; since the new data is then written with the usual
+/// temporary-file-and-rename strategy, a failed write leaves `path` with its previous contents
+/// and nothing needs to be rolled back. Use [`restore_backup`] to go back to the backup.
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If creating the backup, file creation or writing fails, or if
+///   serialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save, save_with_backup, restore_backup, load};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let path = dir.path().join("config.json");
+///
+/// save(&path, "good").unwrap();
+/// save_with_backup(&path, "bad").unwrap();
+///
+/// restore_backup(&path).unwrap();
+/// let restored: String = load(&path).unwrap();
+/// assert_eq!(restored, "good");
+/// ```
+pub fn save_with_backup<P, T>(path: P, data: T) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    let path = path.as_ref();
+
+    let mut json_data = Vec::new();
+    write_json(&mut json_data, &data, &SaveOptions::default())?;
+
+    match std::fs::File::open(path) {
+        Ok(mut current) => {
+            write_atomic(&backup_path(path), |writer| {
+                io::copy(&mut current, writer)?;
+                Ok(())
+            })?;
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(StorageError::Io(err)),
+    }
+
+    write_atomic(path, |writer| {
+        writer.write_all(&json_data)?;
+        Ok(json_data.len())
+    })
+}
+
+/// Synchronously replaces a file with its `<file>.bak` backup.
+///
+/// The backup is renamed over `path` atomically, so it is consumed by the restore.
+///
+/// # Arguments
+///
+/// * `path` - The file path whose backup should be restored
+///
+/// # Returns
+///
+/// * `Ok(())` - If the backup was restored
+/// * `Err(StorageError)` - With an [`io::ErrorKind::NotFound`] error naming the backup if there
+///   is none, or any other I/O error raised while renaming it
+///
+/// # Example
+///
+/// ```
+/// use storage_service::restore_backup;
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// assert!(restore_backup(dir.path().join("config.json")).is_err());
+/// ```
+pub fn restore_backup<P>(path: P) -> Result<(), StorageError>
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    let backup = backup_path(path);
+
+    std::fs::rename(&backup, path).map_err(|err| match err.kind() {
+        io::ErrorKind::NotFound => StorageError::Io(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("backup not found: {}", backup.display()),
+        )),
+        _ => StorageError::Io(err),
+    })
+}
+
+/// Synchronously saves data to a JSON file, keeping up to `keep` rotating backups.
+///
+/// Backups are named `<file>.1.bak` (the most recent) through `<file>.<keep>.bak` (the oldest).
+/// On each call the existing backups are shifted up by one, anything that would end up beyond
+/// `keep` is deleted, the current file is copied to `<file>.1.bak` and then the new data is
+/// written. Gaps in the numbering (for example a `<file>.2.bak` without a `<file>.1.bak`) are
+/// tolerated and simply shifted along.
+///
+/// The data is serialized before any backup is touched, and the current file is copied rather
+/// than moved, so it is never removed before the new version replaces it atomically.
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+/// * `keep` - The maximum number of backups to keep; `0` removes all backups
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If rotating the backups, file creation or writing fails, or if
+///   serialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save_rotating, load};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let path = dir.path().join("state.json");
+///
+/// for version in 1..=4 {
+///     save_rotating(&path, version, 2).unwrap();
+/// }
+///
+/// let latest_backup: u32 = load(dir.path().join("state.json.1.bak")).unwrap();
+/// let oldest_backup: u32 = load(dir.path().join("state.json.2.bak")).unwrap();
+/// assert_eq!((latest_backup, oldest_backup), (3, 2));
+/// assert!(!dir.path().join("state.json.3.bak").exists());
+/// ```
+pub fn save_rotating<P, T>(path: P, data: T, keep: usize) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    let path = path.as_ref();
+
+    let mut json_data = Vec::new();
+    write_json(&mut json_data, &data, &SaveOptions::default())?;
+
+    let mut existing = rotating_backups(path)?;
+    existing.sort_unstable_by(|a, b| b.cmp(a));
+    for number in existing {
+        let backup = rotating_backup_path(path, number);
+        if number >= keep {
+            std::fs::remove_file(backup)?;
+        } else {
+            std::fs::rename(backup, rotating_backup_path(path, number + 1))?;
+        }
+    }
+
+    if keep > 0 {
+        match std::fs::File::open(path) {
+            Ok(mut current) => {
+                write_atomic(&rotating_backup_path(path, 1), |writer| {
+                    io::copy(&mut current, writer)?;
+                    Ok(())
+                })?;
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(StorageError::Io(err)),
+        }
+    }
+
+    write_atomic(path, |writer| {
+        writer.write_all(&json_data)?;
+        Ok(json_data.len())
+    })
+}
+
+/// Returns the numbers of the rotating backups that currently exist for `path`.
+fn rotating_backups(path: &Path) -> Result<Vec<usize>, StorageError> {
+    let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+        return Ok(Vec::new());
+    };
+    let dir = parent_dir(path);
+
+    let mut numbers = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let number = name
+            .to_str()
+            .and_then(|name| name.strip_prefix(file_name))
+            .and_then(|rest| rest.strip_prefix('.'))
+            .and_then(|rest| rest.strip_suffix(".bak"))
+            .and_then(|digits| {
+                // Only the names this module writes, so `01` or `+1` are left alone.
+                let number = digits.parse::<usize>().ok()?;
+                (number.to_string() == digits).then_some(number)
+            });
+
+        if let Some(number) = number.filter(|&number| number > 0) {
+            numbers.push(number);
+        }
+    }
+
+    Ok(numbers)
+}
+
+/// Returns the path of the rotating backup with the given number.
+fn rotating_backup_path(path: &Path, number: usize) -> PathBuf {
+    with_suffix(path, format!(".{number}.bak"))
+}
+
+/// Returns the backup path used for `path`.
+fn backup_path(path: &Path) -> PathBuf {
+    with_suffix(path, ".bak")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{load, save};
+    use tempfile::tempdir;
+
+    #[test]
+    fn save_with_backup_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+
+        save_with_backup(&path, 1).unwrap();
+        assert!(!backup_path(&path).exists());
+
+        save_with_backup(&path, 2).unwrap();
+        save_with_backup(&path, 3).unwrap();
+
+        let current: u32 = load(&path).unwrap();
+        let backup: u32 = load(backup_path(&path)).unwrap();
+        assert_eq!(current, 3);
+        assert_eq!(backup, 2);
+
+        let unserializable = HashMap::from([((1, 2), 3)]);
+        assert!(matches!(
+            save_with_backup(&path, unserializable),
+            Err(StorageError::Serialize(_))
+        ));
+        let current: u32 = load(&path).unwrap();
+        let backup: u32 = load(backup_path(&path)).unwrap();
+        assert_eq!((current, backup), (3, 2));
+    }
+
+    #[test]
+    fn restore_backup_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+
+        save(&path, "first").unwrap();
+        save_with_backup(&path, "second").unwrap();
+
+        restore_backup(&path).unwrap();
+        let restored: String = load(&path).unwrap();
+        assert_eq!(restored, "first");
+        assert!(!backup_path(&path).exists());
+
+        let result = restore_backup(&path);
+        assert!(matches!(
+            result,
+            Err(StorageError::Io(err)) if err.kind() == io::ErrorKind::NotFound
+        ));
+    }
+
+    #[test]
+    fn save_rotating_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("state.json");
+
+        for version in 1..=5 {
+            save_rotating(&path, version, 3).unwrap();
+        }
+
+        let current: u32 = load(&path).unwrap();
+        assert_eq!(current, 5);
+        for (number, expected) in [(1, 4), (2, 3), (3, 2)] {
+            let backup: u32 = load(rotating_backup_path(&path, number)).unwrap();
+            assert_eq!(backup, expected);
+        }
+        assert!(!rotating_backup_path(&path, 4).exists());
+    }
+
+    #[test]
+    fn save_rotating_with_gaps_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("state.json");
+
+        save(&path, 10).unwrap();
+        save(rotating_backup_path(&path, 2), 8).unwrap();
+        save(rotating_backup_path(&path, 7), 1).unwrap();
+
+        save_rotating(&path, 11, 3).unwrap();
+
+        let current: u32 = load(&path).unwrap();
+        let first: u32 = load(rotating_backup_path(&path, 1)).unwrap();
+        let third: u32 = load(rotating_backup_path(&path, 3)).unwrap();
+        assert_eq!((current, first, third), (11, 10, 8));
+        assert!(!rotating_backup_path(&path, 2).exists());
+        assert!(!rotating_backup_path(&path, 7).exists());
+        assert!(!rotating_backup_path(&path, 8).exists());
+
+        save_rotating(&path, 12, 0).unwrap();
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn save_rotating_stray_files_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("state.json");
+
+        let strays = ["state.json.01.bak", "state.json.+1.bak", "state.json.0.bak"];
+        for stray in strays {
+            std::fs::write(dir.path().join(stray), "stray").unwrap();
+        }
+
+        for version in 1..=3 {
+            save_rotating(&path, version, 2).unwrap();
+        }
+
+        let first: u32 = load(rotating_backup_path(&path, 1)).unwrap();
+        let second: u32 = load(rotating_backup_path(&path, 2)).unwrap();
+        assert_eq!((first, second), (2, 1));
+        for stray in strays {
+            assert!(dir.path().join(stray).exists());
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    sync::{
+        Arc, Mutex, MutexGuard,
+        mpsc::{self, RecvTimeoutError, Sender},
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use serde::Serialize;
+
+use crate::{StorageError, instrument::report_unhandled, save};
+
+/// Coalesces frequent saves of one JSON file into fewer disk writes.
+///
+/// [`set`](BatchedStore::set) only replaces the in-memory copy of the value; the file is written,
+/// atomically as with [`save`](crate::save), when [`flush`](BatchedStore::flush) is called, on
+/// every tick of the optional background interval, and when the store is dropped. Only the latest
+/// value is ever written, so a burst of updates between two flushes costs a single write.
+///
+/// Errors from background and drop-time flushes cannot be returned, so they are logged instead:
+/// as an `ERROR` event with the `tracing` feature, otherwise on standard error. The pending
+/// value is kept after a failed background flush so the next one retries it. Call `flush`
+/// before dropping the store to observe the final write's result.
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{BatchedStore, load};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let path = dir.path().join("position.json");
+///
+/// let store = BatchedStore::new(&path);
+/// for x in 0..100 {
+///     store.set(&x);
+/// }
+/// assert!(!path.exists());
+///
+/// store.flush().unwrap();
+/// assert_eq!(load::<_, i32>(&path).unwrap(), 99);
+/// ```
+pub struct BatchedStore<T: Serialize> {
+    shared: Arc<Shared<T>>,
+    flusher: Option<Flusher>,
+}
+
+struct Shared<T> {
+    path: PathBuf,
+    pending: Mutex<Option<T>>,
+    /// Held for the whole of a flush, so two flushes cannot write their values out of order.
+    flushing: Mutex<()>,
+}
+
+/// The background thread of a store created with [`BatchedStore::with_interval`].
+struct Flusher {
+    /// Dropping this stops the thread.
+    stop: Sender<()>,
+    handle: JoinHandle<()>,
+}
+
+impl<T: Serialize + Clone> BatchedStore<T> {
+    /// Creates a store that writes to `path` only on [`flush`](BatchedStore::flush) and on drop.
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                path: path.as_ref().to_path_buf(),
+                pending: Mutex::new(None),
+                flushing: Mutex::new(()),
+            }),
+            flusher: None,
+        }
+    }
+
+    /// Creates a store that also flushes pending changes every `interval` on a background
+    /// thread.
+    pub fn with_interval<P: AsRef<Path>>(path: P, interval: Duration) -> Self
+    where
+        T: Send + 'static,
+    {
+        let mut store = Self::new(path);
+        let shared = Arc::clone(&store.shared);
+        let (stop, stopped) = mpsc::channel::<()>();
+
+        let handle = std::thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                if let Err(err) = shared.flush() {
+                    report_unhandled("batched_store", &shared.path, &err);
+                }
+            }
+        });
+        store.flusher = Some(Flusher { stop, handle });
+
+        store
+    }
+
+    /// Replaces the value to be written by the next flush; the file is not touched.
+    pub fn set(&self, data: &T) {
+        *self.shared.lock_pending() = Some(data.clone());
+    }
+
+    /// Writes the latest value set since the last flush, if any.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - Whether there was a pending value to write
+    /// * `Err(StorageError)` - If writing fails; the value stays pending unless a newer one was
+    ///   set in the meantime
+    pub fn flush(&self) -> Result<bool, StorageError> {
+        self.shared.flush()
+    }
+
+    /// Returns whether a value has been set that no flush has started writing yet.
+    pub fn is_dirty(&self) -> bool {
+        self.shared.lock_pending().is_some()
+    }
+
+    /// Returns the path of the file.
+    pub fn path(&self) -> &Path {
+        &self.shared.path
+    }
+}
+
+impl<T: Serialize> Shared<T> {
+    fn flush(&self) -> Result<bool, StorageError> {
+        let _flushing = self.flushing.lock().unwrap_or_else(|err| err.into_inner());
+
+        let Some(data) = self.lock_pending().take() else {
+            return Ok(false);
+        };
+
+        save(&self.path, &data).map(|_| true).inspect_err(|_| {
+            self.lock_pending().get_or_insert(data);
+        })
+    }
+
+    fn lock_pending(&self) -> MutexGuard<'_, Option<T>> {
+        self.pending.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl<T: Serialize> Drop for BatchedStore<T> {
+    fn drop(&mut self) {
+        if let Some(Flusher { stop, handle }) = self.flusher.take() {
+            drop(stop);
+            let _ = handle.join();
+        }
+
+        if let Err(err) = self.shared.flush() {
+            report_unhandled("batched_store", &self.shared.path, &err);
+        }
+    }
+}
+
+impl<T: Serialize> fmt::Debug for BatchedStore<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BatchedStore")
+            .field("path", &self.shared.path)
+            .field("dirty", &self.shared.lock_pending().is_some())
+            .field("background", &self.flusher.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::load;
+
+    #[test]
+    fn batched_store_flush_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("counter.json");
+
+        let store = BatchedStore::new(&path);
+        assert!(!store.flush().unwrap());
+
+        store.set(&1);
+        store.set(&2);
+        assert!(store.is_dirty());
+        assert!(!path.exists());
+
+        assert!(store.flush().unwrap());
+        assert!(!store.is_dirty());
+        assert_eq!(load::<_, i32>(&path).unwrap(), 2);
+
+        store.set(&3);
+        drop(store);
+        assert_eq!(load::<_, i32>(&path).unwrap(), 3);
+    }
+
+    #[test]
+    fn batched_store_flush_failure_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("missing").join("counter.json");
+
+        let store = BatchedStore::new(&path);
+        store.set(&"kept");
+        assert!(store.flush().is_err());
+        assert!(store.is_dirty());
+
+        std::fs::create_dir(dir.path().join("missing")).unwrap();
+        assert!(store.flush().unwrap());
+        assert_eq!(load::<_, String>(&path).unwrap(), "kept");
+    }
+
+    #[test]
+    fn batched_store_with_interval_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("counter.json");
+
+        let store = BatchedStore::with_interval(&path, Duration::from_millis(20));
+        store.set(&vec![1, 2, 3]);
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !path.exists() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(load::<_, Vec<i32>>(&path).unwrap(), vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,177 @@
+use std::{io::Read, path::Path};
+
+use serde::Deserialize;
+
+use crate::{StorageError, parse_json};
+
+/// The raw contents of a file, kept around so values can borrow from them.
+///
+/// Deserializing into types with `&str` or `&[u8]` fields avoids allocating a copy of every
+/// string, but the parsed value can then not outlive the bytes it borrows from. `LoadedBytes`
+/// owns those bytes: read the file once with [`LoadedBytes::read`], then call
+/// [`parse`](LoadedBytes::parse) to get values tied to its lifetime.
+///
+/// Strings containing escape sequences cannot be borrowed; use `Cow<'a, str>` with
+/// `#[serde(borrow)]` for fields that may contain them.
+///
+/// # Example
+///
+/// ```
+/// use serde::Deserialize;
+/// use serde_json::json;
+/// use storage_service::{LoadedBytes, save};
+/// use tempfile::NamedTempFile;
+///
+/// #[derive(Deserialize)]
+/// struct Entry<'a> {
+///     key: &'a str,
+///     value: &'a str,
+/// }
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save(temp_file.path(), json!({ "key": "region", "value": "eu-west-1" })).unwrap();
+///
+/// let bytes = LoadedBytes::read(temp_file.path()).unwrap();
+/// let entry: Entry = bytes.parse().unwrap();
+/// assert_eq!((entry.key, entry.value), ("region", "eu-west-1"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoadedBytes {
+    bytes: Vec<u8>,
+}
+
+impl LoadedBytes {
+    /// Reads the whole file at `path`.
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self, StorageError> {
+        let mut bytes = Vec::new();
+        read_into(path.as_ref(), &mut bytes)?;
+
+        Ok(Self { bytes })
+    }
+
+    /// Deserializes a value from the JSON contents, borrowing from them where possible.
+    pub fn parse<'a, T: Deserialize<'a>>(&'a self) -> Result<T, StorageError> {
+        parse_json(&self.bytes)
+    }
+
+    /// Returns the raw file contents.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns the raw file contents, consuming the wrapper.
+    pub fn into_inner(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// Synchronously loads data from a JSON file into a value that borrows from `buffer`.
+///
+/// The file is read into `buffer`, replacing its previous contents, and deserialized from it
+/// without copying strings where possible. Reusing one buffer across calls also reuses its
+/// allocation. See [`LoadedBytes`] for a variant that owns the buffer.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+/// * `buffer` - The buffer to read the file into, which the result borrows from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - If file reading fails or if deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save, load_borrowed};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save(temp_file.path(), vec!["alpha", "beta"]).unwrap();
+///
+/// let mut buffer = Vec::new();
+/// let names: Vec<&str> = load_borrowed(temp_file.path(), &mut buffer).unwrap();
+/// assert_eq!(names, ["alpha", "beta"]);
+/// ```
+pub fn load_borrowed<'a, P, T>(path: P, buffer: &'a mut Vec<u8>) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: Deserialize<'a>,
+{
+    buffer.clear();
+    read_into(path.as_ref(), buffer)?;
+
+    parse_json(buffer)
+}
+
+fn read_into(path: &Path, buffer: &mut Vec<u8>) -> Result<(), StorageError> {
+    std::fs::File::open(path)?.read_to_end(buffer)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use serde::Serialize;
+    use tempfile::NamedTempFile;
+
+    use super::*;
+    use crate::save;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Record<'a> {
+        id: u32,
+        name: &'a str,
+        #[serde(borrow)]
+        note: Cow<'a, str>,
+    }
+
+    #[test]
+    fn loaded_bytes_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        let record = Record {
+            id: 1,
+            name: "plain",
+            note: Cow::Borrowed("line\nbreak"),
+        };
+        save(path, &record).unwrap();
+
+        let bytes = LoadedBytes::read(path).unwrap();
+        let loaded: Record = bytes.parse().unwrap();
+        assert_eq!(loaded, record);
+        // The escaped note has to be allocated, the plain name is borrowed from the buffer.
+        assert!(matches!(loaded.note, Cow::Owned(_)));
+        assert!(
+            bytes
+                .as_bytes()
+                .as_ptr_range()
+                .contains(&loaded.name.as_ptr())
+        );
+
+        assert_eq!(bytes.clone().into_inner(), std::fs::read(path).unwrap());
+        assert!(matches!(
+            LoadedBytes::read(path.with_extension("missing")),
+            Err(StorageError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn load_borrowed_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+        let mut buffer = b"stale contents".to_vec();
+
+        save(path, ["first", "second"]).unwrap();
+        let names: Vec<&str> = load_borrowed(path, &mut buffer).unwrap();
+        assert_eq!(names, ["first", "second"]);
+
+        save(path, "not a list").unwrap();
+        let result = load_borrowed::<_, Vec<&str>>(path, &mut buffer);
+        assert!(matches!(result, Err(StorageError::Deserialize(_))));
+    }
+}
@@ -0,0 +1,231 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+    time::SystemTime,
+};
+
+use serde::de::DeserializeOwned;
+
+use crate::{StorageError, load};
+
+/// A cache of loaded JSON files that reloads a file only after it changes on disk.
+///
+/// Every [`get`](CachedStore::get) checks the file's metadata, which is one `stat` call, and
+/// serves the previously parsed value if the modification time and size are unchanged.
+/// Otherwise the file is loaded again and the new value replaces the cached one. At most
+/// `capacity` files are kept; when a new file is added to a full cache, the least recently used
+/// one is dropped.
+///
+/// A change that keeps both the size and modification time identical, which is possible on
+/// filesystems with coarse timestamps, is not detected until the file changes again; call
+/// [`invalidate`](CachedStore::invalidate) after writing through another channel if that
+/// matters.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use storage_service::{CachedStore, save};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save(temp_file.path(), vec!["a", "b"]).unwrap();
+///
+/// let cache = CachedStore::new(16);
+/// let first: Arc<Vec<String>> = cache.get(temp_file.path()).unwrap();
+/// let second: Arc<Vec<String>> = cache.get(temp_file.path()).unwrap();
+/// assert!(Arc::ptr_eq(&first, &second));
+/// ```
+#[derive(Debug)]
+pub struct CachedStore {
+    capacity: usize,
+    inner: Mutex<Cache>,
+}
+
+#[derive(Debug, Default)]
+struct Cache {
+    entries: HashMap<PathBuf, Entry>,
+    clock: u64,
+}
+
+#[derive(Debug)]
+struct Entry {
+    version: Version,
+    value: Arc<dyn Any + Send + Sync>,
+    last_used: u64,
+}
+
+/// What identifies a particular revision of a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Version {
+    modified: SystemTime,
+    len: u64,
+}
+
+impl CachedStore {
+    /// Creates an empty cache holding at most `capacity` files; a capacity of zero is treated
+    /// as one.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            inner: Mutex::default(),
+        }
+    }
+
+    /// Returns the value stored in the JSON file at `path`, loading it if it is not cached or
+    /// has changed since it was cached.
+    ///
+    /// The same file may be requested as different types; asking for a type other than the
+    /// cached one reloads the file and caches it as the new type.
+    pub fn get<T, P>(&self, path: P) -> Result<Arc<T>, StorageError>
+    where
+        T: DeserializeOwned + Send + Sync + 'static,
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let version = version(path)?;
+
+        {
+            let mut cache = self.lock();
+            cache.clock += 1;
+            let clock = cache.clock;
+            if let Some(entry) = cache.entries.get_mut(path)
+                && entry.version == version
+                && let Ok(value) = Arc::clone(&entry.value).downcast::<T>()
+            {
+                entry.last_used = clock;
+                return Ok(value);
+            }
+        }
+
+        // Load without holding the lock, so a slow file does not block hits on other files.
+        let value = Arc::new(load::<_, T>(path)?);
+
+        let mut cache = self.lock();
+        if !cache.entries.contains_key(path) && cache.entries.len() >= self.capacity {
+            cache.evict_least_recently_used();
+        }
+        cache.clock += 1;
+        let entry = Entry {
+            version,
+            value: Arc::clone(&value) as Arc<dyn Any + Send + Sync>,
+            last_used: cache.clock,
+        };
+        cache.entries.insert(path.to_path_buf(), entry);
+
+        Ok(value)
+    }
+
+    /// Drops the cached value for `path`, so the next [`get`](CachedStore::get) reloads it.
+    pub fn invalidate<P: AsRef<Path>>(&self, path: P) {
+        self.lock().entries.remove(path.as_ref());
+    }
+
+    /// Drops every cached value.
+    pub fn clear(&self) {
+        self.lock().entries.clear();
+    }
+
+    /// Returns the number of files currently cached.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Returns whether nothing is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Cache> {
+        self.inner.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl Cache {
+    fn evict_least_recently_used(&mut self) {
+        let oldest = self
+            .entries
+            .iter()
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(path, _)| path.clone());
+
+        if let Some(path) = oldest {
+            self.entries.remove(&path);
+        }
+    }
+}
+
+fn version(path: &Path) -> Result<Version, StorageError> {
+    let metadata = std::fs::metadata(path)?;
+
+    Ok(Version {
+        modified: metadata.modified()?,
+        len: metadata.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::save;
+
+    #[test]
+    fn cached_store_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        save(&path, vec![1, 2]).unwrap();
+
+        let cache = CachedStore::new(4);
+        let first: Arc<Vec<i32>> = cache.get(&path).unwrap();
+        let cached: Arc<Vec<i32>> = cache.get(&path).unwrap();
+        assert!(Arc::ptr_eq(&first, &cached));
+
+        save(&path, vec![1, 2, 3]).unwrap();
+        let reloaded: Arc<Vec<i32>> = cache.get(&path).unwrap();
+        assert_eq!(*reloaded, vec![1, 2, 3]);
+        assert_eq!(*first, vec![1, 2]);
+
+        let as_values: Arc<Vec<serde_json::Value>> = cache.get(&path).unwrap();
+        assert_eq!(as_values.len(), 3);
+        assert_eq!(cache.len(), 1);
+
+        cache.invalidate(&path);
+        assert!(cache.is_empty());
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            cache.get::<Vec<i32>, _>(&path),
+            Err(StorageError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn cached_store_eviction_test() {
+        let dir = tempdir().unwrap();
+        let paths: Vec<_> = (0..3)
+            .map(|i| {
+                let path = dir.path().join(format!("{i}.json"));
+                save(&path, i).unwrap();
+                path
+            })
+            .collect();
+
+        let cache = CachedStore::new(2);
+        let zero: Arc<u32> = cache.get(&paths[0]).unwrap();
+        cache.get::<u32, _>(&paths[1]).unwrap();
+        // Touch the first file so the second becomes the least recently used.
+        cache.get::<u32, _>(&paths[0]).unwrap();
+        cache.get::<u32, _>(&paths[2]).unwrap();
+
+        assert_eq!(cache.len(), 2);
+        assert!(Arc::ptr_eq(&zero, &cache.get(&paths[0]).unwrap()));
+        assert!(!cache.lock().entries.contains_key(&paths[1]));
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,186 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{HashAlgo, SaveOptions, StorageError, parse_file, write_atomic_with};
+
+/// Synchronously saves data into a content-addressed directory, returning its hash.
+///
+/// The data is serialized to compact JSON and stored as `dir/<hash>.json`, where `<hash>` is
+/// the lowercase hex SHA-256 digest of the serialized bytes. Equal data always serializes to
+/// the same bytes and therefore the same file, so storing content that is already present is a
+/// cheap no-op that does not rewrite the file. New files are written atomically, and `dir` is
+/// created if needed. Read the data back with [`get_cas`].
+///
+/// Maps are serialized in their iteration order, so data held in a `HashMap` should be
+/// converted to a `BTreeMap` first for its hash to be stable.
+///
+/// # Arguments
+///
+/// * `dir` - The directory holding the content-addressed files
+/// * `data` - The data to serialize and store (must implement `Serialize`)
+///
+/// # Returns
+///
+/// * `Ok(String)` - The hash under which the data is stored
+/// * `Err(StorageError)` - If serialization or writing the file fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{get_cas, put_cas};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let hash = put_cas(dir.path(), &vec!["a", "b"]).unwrap();
+/// assert_eq!(put_cas(dir.path(), &vec!["a", "b"]).unwrap(), hash);
+///
+/// let loaded: Vec<String> = get_cas(dir.path(), &hash).unwrap();
+/// assert_eq!(loaded, vec!["a", "b"]);
+/// ```
+pub fn put_cas<P, T>(dir: P, data: &T) -> Result<String, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize + ?Sized,
+{
+    let bytes = serde_json::to_vec(data).map_err(StorageError::from_serialize)?;
+    let hash = HashAlgo::Sha256.digest(&bytes);
+
+    let path = object_path(dir.as_ref(), &hash);
+    if !path.is_file() {
+        write_atomic_with(&path, &SaveOptions::new().create_dirs(true), |writer| {
+            Ok(writer.write_all(&bytes)?)
+        })?;
+    }
+
+    Ok(hash)
+}
+
+/// Synchronously loads data stored by [`put_cas`] under `hash`.
+///
+/// The file's contents are hashed again before they are deserialized, so a stored object that
+/// has been modified or corrupted is reported as a [`StorageError::ChecksumMismatch`] rather
+/// than returned as if it were the original.
+///
+/// # Arguments
+///
+/// * `dir` - The directory holding the content-addressed files
+/// * `hash` - The hash returned by [`put_cas`]
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - With an [`io::ErrorKind::InvalidInput`] error if `hash` is not a
+///   SHA-256 hex digest, [`StorageError::ChecksumMismatch`] if the file no longer matches its
+///   hash, or if reading the file or deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{get_cas, put_cas};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let hash = put_cas(dir.path(), "immutable").unwrap();
+///
+/// let loaded: String = get_cas(dir.path(), &hash).unwrap();
+/// assert_eq!(loaded, "immutable");
+/// assert!(get_cas::<_, String>(dir.path(), "../escape").is_err());
+/// ```
+pub fn get_cas<P, T>(dir: P, hash: &str) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    let is_digest = hash.len() == 64
+        && hash
+            .bytes()
+            .all(|byte| byte.is_ascii_digit() || (b'a'..=b'f').contains(&byte));
+    if !is_digest {
+        return Err(StorageError::Io(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{hash:?} is not a SHA-256 hex digest"),
+        )));
+    }
+
+    let path = object_path(dir.as_ref(), hash);
+    let bytes = std::fs::read(&path)?;
+
+    let actual = HashAlgo::Sha256.digest(&bytes);
+    if actual != hash {
+        return Err(StorageError::ChecksumMismatch {
+            expected: hash.to_string(),
+            actual,
+        });
+    }
+
+    parse_file(&path, &bytes)
+}
+
+/// Returns the path of the object stored under `hash` in `dir`.
+fn object_path(dir: &Path, hash: &str) -> PathBuf {
+    dir.join(format!("{hash}.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn put_cas_test() {
+        let dir = tempdir().unwrap();
+        let objects = dir.path().join("objects");
+        let data = BTreeMap::from([("name", "Ada"), ("role", "admin")]);
+
+        let hash = put_cas(&objects, &data).unwrap();
+        assert_eq!(
+            hash,
+            HashAlgo::Sha256.digest(&serde_json::to_vec(&data).unwrap())
+        );
+        let path = objects.join(format!("{hash}.json"));
+        let modified = std::fs::metadata(&path).unwrap().modified().unwrap();
+
+        assert_eq!(put_cas(&objects, &data).unwrap(), hash);
+        assert_eq!(
+            std::fs::metadata(&path).unwrap().modified().unwrap(),
+            modified
+        );
+        assert_ne!(put_cas(&objects, "other").unwrap(), hash);
+        assert_eq!(std::fs::read_dir(&objects).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn get_cas_test() {
+        let dir = tempdir().unwrap();
+        let hash = put_cas(dir.path(), &[1, 2, 3]).unwrap();
+        assert_eq!(get_cas::<_, Vec<u8>>(dir.path(), &hash).unwrap(), [1, 2, 3]);
+
+        std::fs::write(dir.path().join(format!("{hash}.json")), "[1,2,4]").unwrap();
+        let tampered = get_cas::<_, Vec<u8>>(dir.path(), &hash);
+        assert!(matches!(
+            tampered,
+            Err(StorageError::ChecksumMismatch { .. })
+        ));
+
+        let missing = HashAlgo::Sha256.digest(b"missing");
+        let result = get_cas::<_, Vec<u8>>(dir.path(), &missing);
+        assert!(
+            matches!(result, Err(StorageError::Io(err)) if err.kind() == io::ErrorKind::NotFound)
+        );
+
+        for invalid in ["", "../outside", &hash.to_uppercase(), &hash[1..]] {
+            let result = get_cas::<_, Vec<u8>>(dir.path(), invalid);
+            assert!(
+                matches!(result, Err(StorageError::Io(err)) if err.kind() == io::ErrorKind::InvalidInput),
+                "{invalid}"
+            );
+        }
+    }
+}
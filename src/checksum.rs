@@ -0,0 +1,327 @@
+use std::{
+    fs::File,
+    io::{self, Read},
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::value::RawValue;
+use sha2::{Digest, Sha256};
+
+use crate::{StorageError, parse_file, write_atomic};
+
+/// A hashing algorithm used to checksum stored data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgo {
+    /// SHA-256, a cryptographic hash that also detects deliberate tampering.
+    #[default]
+    Sha256,
+    /// CRC32, a fast checksum that is enough to detect accidental corruption.
+    Crc32,
+}
+
+impl HashAlgo {
+    /// Returns an incremental hasher for this algorithm.
+    pub(crate) fn hasher(self) -> Hasher {
+        match self {
+            HashAlgo::Sha256 => Hasher::Sha256(Sha256::new()),
+            HashAlgo::Crc32 => Hasher::Crc32(crc32fast::Hasher::new()),
+        }
+    }
+
+    /// Returns the lowercase hex digest of `bytes`.
+    pub(crate) fn digest(self, bytes: &[u8]) -> String {
+        let mut hasher = self.hasher();
+        hasher.update(bytes);
+        hasher.finish()
+    }
+}
+
+/// An in-progress hash computation for one of the [`HashAlgo`]s.
+pub(crate) enum Hasher {
+    Sha256(Sha256),
+    Crc32(crc32fast::Hasher),
+}
+
+impl Hasher {
+    /// Feeds more bytes into the hash.
+    pub(crate) fn update(&mut self, bytes: &[u8]) {
+        match self {
+            Hasher::Sha256(hasher) => hasher.update(bytes),
+            Hasher::Crc32(hasher) => hasher.update(bytes),
+        }
+    }
+
+    /// Returns the lowercase hex digest of everything fed so far.
+    pub(crate) fn finish(self) -> String {
+        match self {
+            Hasher::Sha256(hasher) => hasher
+                .finalize()
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect(),
+            Hasher::Crc32(hasher) => format!("{:08x}", hasher.finalize()),
+        }
+    }
+}
+
+/// The on-disk layout of a checksummed file.
+///
+/// `data` is kept as raw JSON so the checksum is verified against the exact bytes that were
+/// written, independent of how `T` would re-serialize.
+#[derive(Serialize, Deserialize)]
+struct Envelope<'a> {
+    algorithm: HashAlgo,
+    checksum: String,
+    #[serde(borrow)]
+    data: &'a RawValue,
+}
+
+/// Synchronously saves data to a JSON file together with a checksum of the data.
+///
+/// The file contains an envelope of the form
+/// `{"algorithm": "sha256", "checksum": "...", "data": ...}`, where the checksum covers the
+/// serialized `data`. Use [`load_checksummed`] to read it back with verification.
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+/// * `algorithm` - The hashing algorithm used for the checksum
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, envelope included, if the operation succeeds
+/// * `Err(StorageError)` - If file creation or writing fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{HashAlgo, save_checksummed, load_checksummed};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save_checksummed(temp_file.path(), vec![1, 2, 3], HashAlgo::Sha256).unwrap();
+///
+/// let loaded: Vec<u8> = load_checksummed(temp_file.path()).unwrap();
+/// assert_eq!(loaded, vec![1, 2, 3]);
+/// ```
+pub fn save_checksummed<P, T>(path: P, data: T, algorithm: HashAlgo) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    let raw = serde_json::value::to_raw_value(&data).map_err(StorageError::from_serialize)?;
+    let envelope = Envelope {
+        algorithm,
+        checksum: algorithm.digest(raw.get().as_bytes()),
+        data: &raw,
+    };
+    let bytes = serde_json::to_vec(&envelope).map_err(StorageError::from_serialize)?;
+
+    write_atomic(path.as_ref(), |writer| {
+        writer.write_all(&bytes)?;
+
+        Ok(bytes.len())
+    })
+}
+
+/// Synchronously loads data saved by [`save_checksummed`], verifying its checksum.
+///
+/// The checksum is recomputed with the algorithm recorded in the file and compared before the
+/// data is deserialized, so corruption is reported as a clear
+/// [`StorageError::ChecksumMismatch`] instead of a confusing parse error.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if the checksum matches
+/// * `Err(StorageError)` - [`StorageError::ChecksumMismatch`] if the data was corrupted,
+///   [`StorageError::Empty`] if the file is empty, or if file reading or deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{HashAlgo, StorageError, save_checksummed, load_checksummed};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save_checksummed(temp_file.path(), "original", HashAlgo::Crc32).unwrap();
+///
+/// let content = std::fs::read_to_string(temp_file.path()).unwrap();
+/// std::fs::write(temp_file.path(), content.replace("original", "tampered")).unwrap();
+///
+/// let result: Result<String, _> = load_checksummed(temp_file.path());
+/// assert!(matches!(result, Err(StorageError::ChecksumMismatch { .. })));
+/// ```
+pub fn load_checksummed<P, T>(path: P) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    let path = path.as_ref();
+    let bytes = std::fs::read(path)?;
+    let envelope: Envelope = parse_file(path, &bytes)?;
+
+    let actual = envelope.algorithm.digest(envelope.data.get().as_bytes());
+    if actual != envelope.checksum {
+        return Err(StorageError::ChecksumMismatch {
+            expected: envelope.checksum,
+            actual,
+        });
+    }
+
+    serde_json::from_str(envelope.data.get())
+        .map_err(|err| StorageError::from_deserialize(err).in_file(path))
+}
+
+/// Computes the hex digest of a file's bytes.
+///
+/// The file is streamed through the hasher in fixed-size chunks, so files of any size can be
+/// hashed without loading them into memory. The digest covers the raw bytes on disk, whatever
+/// function wrote them, which makes it suitable for deduplication or for comparing a stored file
+/// against a remote copy. Digests use the same lowercase hex encoding as
+/// [`save_checksummed`].
+///
+/// # Arguments
+///
+/// * `path` - The file to hash
+/// * `algo` - The hashing algorithm to use
+///
+/// # Returns
+///
+/// * `Ok(String)` - The lowercase hex digest of the file's contents
+/// * `Err(io::Error)` - If the file cannot be opened or read
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{HashAlgo, checksum};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// std::fs::write(temp_file.path(), "123456789").unwrap();
+///
+/// assert_eq!(checksum(temp_file.path(), HashAlgo::Crc32).unwrap(), "cbf43926");
+/// ```
+pub fn checksum<P>(path: P, algo: HashAlgo) -> Result<String, io::Error>
+where
+    P: AsRef<Path>,
+{
+    let mut file = File::open(path)?;
+    let mut hasher = algo.hasher();
+    let mut buffer = vec![0; 64 * 1024];
+
+    loop {
+        match file.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => hasher.update(&buffer[..read]),
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok(hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct TestData {
+        name: String,
+        value: i32,
+    }
+
+    #[test]
+    fn digest_test() {
+        assert_eq!(
+            HashAlgo::Sha256.digest(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(HashAlgo::Crc32.digest(b"123456789"), "cbf43926");
+    }
+
+    #[test]
+    fn save_checksummed_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        let data = TestData {
+            name: "checked".to_string(),
+            value: 9,
+        };
+        let written = save_checksummed(path, &data, HashAlgo::Crc32).unwrap();
+
+        let content = std::fs::read_to_string(path).unwrap();
+        assert_eq!(written, content.len());
+        let expected_checksum = HashAlgo::Crc32.digest(br#"{"name":"checked","value":9}"#);
+        assert_eq!(
+            content,
+            format!(
+                r#"{{"algorithm":"crc32","checksum":"{expected_checksum}","data":{{"name":"checked","value":9}}}}"#
+            )
+        );
+    }
+
+    #[test]
+    fn load_checksummed_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        let data = TestData {
+            name: "verified".to_string(),
+            value: 12,
+        };
+        save_checksummed(path, &data, HashAlgo::Sha256).unwrap();
+
+        let loaded: TestData = load_checksummed(path).unwrap();
+        assert_eq!(loaded, data);
+
+        let corrupted = std::fs::read_to_string(path).unwrap().replace("12", "13");
+        std::fs::write(path, corrupted).unwrap();
+
+        let result: Result<TestData, _> = load_checksummed(path);
+        assert!(matches!(result, Err(StorageError::ChecksumMismatch { .. })));
+
+        save_checksummed(path, &data, HashAlgo::Sha256).unwrap();
+        let mut with_bom = b"\xEF\xBB\xBF".to_vec();
+        with_bom.extend(std::fs::read(path).unwrap());
+        std::fs::write(path, with_bom).unwrap();
+        assert_eq!(load_checksummed::<_, TestData>(path).unwrap(), data);
+
+        std::fs::write(path, b"").unwrap();
+        let result: Result<TestData, _> = load_checksummed(path);
+        assert!(matches!(result, Err(StorageError::Empty(empty)) if empty == path));
+
+        std::fs::write(path, r#"{"algorithm":"#).unwrap();
+        let result: Result<TestData, _> = load_checksummed(path);
+        assert!(matches!(result, Err(StorageError::Parse { path: failed, .. }) if failed == path));
+    }
+
+    #[test]
+    fn checksum_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        let bytes: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        std::fs::write(path, &bytes).unwrap();
+        assert_eq!(
+            checksum(path, HashAlgo::Sha256).unwrap(),
+            HashAlgo::Sha256.digest(&bytes)
+        );
+        assert_eq!(
+            checksum(path, HashAlgo::Crc32).unwrap(),
+            HashAlgo::Crc32.digest(&bytes)
+        );
+
+        let missing = checksum(path.with_extension("missing"), HashAlgo::Sha256);
+        assert_eq!(missing.unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+}
@@ -0,0 +1,112 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, SystemTime},
+};
+
+/// A source of the current time.
+///
+/// Time-dependent functions such as [`purge_older_than_with_clock`] and
+/// [`Metadata::with_clock`] read the time through this trait, so tests can drive them with a
+/// [`ManualClock`] instead of sleeping or backdating files. The functions without a clock
+/// parameter use [`SystemClock`].
+///
+/// Those functions still work on the real filesystem. To keep the disk out of a test as well,
+/// write the storage logic against [`StorageBackend`] and test it with a [`MemoryBackend`],
+/// which timestamps writes with its own clock; [`StorageBackend::purge_older_than`] is the
+/// backend counterpart of [`purge_older_than_with_clock`].
+///
+/// [`purge_older_than_with_clock`]: crate::purge_older_than_with_clock
+/// [`Metadata::with_clock`]: crate::Metadata::with_clock
+/// [`StorageBackend`]: crate::StorageBackend
+/// [`StorageBackend::purge_older_than`]: crate::StorageBackend::purge_older_than
+/// [`MemoryBackend`]: crate::MemoryBackend
+pub trait Clock: Send + Sync {
+    /// Returns the current time.
+    fn now(&self) -> SystemTime;
+}
+
+/// The real system clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A clock that only moves when told to, for deterministic tests.
+///
+/// # Example
+///
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use storage_service::{Clock, ManualClock};
+///
+/// let clock = ManualClock::new(UNIX_EPOCH);
+/// clock.advance(Duration::from_secs(60));
+/// assert_eq!(clock.now(), UNIX_EPOCH + Duration::from_secs(60));
+/// ```
+#[derive(Debug)]
+pub struct ManualClock {
+    now: Mutex<SystemTime>,
+}
+
+impl ManualClock {
+    /// Creates a clock stopped at `now`.
+    pub fn new(now: SystemTime) -> Self {
+        Self {
+            now: Mutex::new(now),
+        }
+    }
+
+    /// Moves the clock to `now`, which may be earlier than its current time.
+    pub fn set(&self, now: SystemTime) {
+        *self.now.lock().unwrap_or_else(|err| err.into_inner()) = now;
+    }
+
+    /// Moves the clock forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap_or_else(|err| err.into_inner()) += duration;
+    }
+}
+
+impl Default for ManualClock {
+    /// Creates a clock stopped at the current system time.
+    fn default() -> Self {
+        Self::new(SystemTime::now())
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> SystemTime {
+        *self.now.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::UNIX_EPOCH;
+
+    use super::*;
+
+    #[test]
+    fn manual_clock_test() {
+        let clock = ManualClock::new(UNIX_EPOCH);
+        assert_eq!(clock.now(), UNIX_EPOCH);
+
+        clock.advance(Duration::from_secs(5));
+        clock.advance(Duration::from_secs(5));
+        assert_eq!(clock.now(), UNIX_EPOCH + Duration::from_secs(10));
+
+        clock.set(UNIX_EPOCH + Duration::from_secs(1));
+        assert_eq!(clock.now(), UNIX_EPOCH + Duration::from_secs(1));
+    }
+
+    #[test]
+    fn system_clock_test() {
+        let before = SystemTime::now();
+        let now = SystemClock.now();
+        assert!(before <= now && now <= SystemTime::now());
+    }
+}
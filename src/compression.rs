@@ -0,0 +1,491 @@
+use std::{
+    io::{self, BufRead, Read},
+    path::Path,
+};
+
+use flate2::{Compression, bufread::GzDecoder, write::GzEncoder};
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{CountingWriter, SaveOptions, StorageError, parse_file, write_atomic, write_json};
+
+/// The two bytes every gzip stream starts with.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// The four bytes every zstd frame starts with.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Synchronously saves data to a gzip-compressed JSON file.
+///
+/// The data is serialized to JSON and streamed through a gzip encoder into the file, using the
+/// same temporary-file-and-rename strategy as [`save`](crate::save).
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved, conventionally ending in `.json.gz`
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written to the file, if the operation succeeds
+/// * `Err(StorageError)` - If file creation, compression or writing fails, or if serialization
+///   fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save_compressed, load_compressed};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let path = dir.path().join("numbers.json.gz");
+///
+/// save_compressed(&path, vec![1, 2, 3]).unwrap();
+/// let loaded: Vec<i32> = load_compressed(&path).unwrap();
+/// assert_eq!(loaded, vec![1, 2, 3]);
+/// ```
+pub fn save_compressed<P, T>(path: P, data: T) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    write_atomic(path.as_ref(), |writer| {
+        let mut writer = CountingWriter::new(writer);
+        let mut encoder = GzEncoder::new(&mut writer, Compression::default());
+        write_json(&mut encoder, &data, &SaveOptions::default())?;
+        encoder.finish()?;
+
+        Ok(writer.count)
+    })
+}
+
+/// Synchronously loads data from a gzip-compressed or plain JSON file.
+///
+/// The file is checked for the gzip magic bytes (`0x1f 0x8b`): compressed files are decoded on
+/// the fly, while anything else is parsed as plain JSON, so the same call works for both.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - [`StorageError::Empty`] if the file or its decompressed contents are
+///   empty, or if file reading, decompression or deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save, load_compressed};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save(temp_file.path(), "plain").unwrap();
+///
+/// let loaded: String = load_compressed(temp_file.path()).unwrap();
+/// assert_eq!(loaded, "plain");
+/// ```
+pub fn load_compressed<P, T>(path: P) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    let path = path.as_ref();
+    let mut reader = io::BufReader::new(std::fs::File::open(path)?);
+
+    let mut json_data = Vec::new();
+    if reader.fill_buf()?.starts_with(&GZIP_MAGIC) {
+        GzDecoder::new(reader).read_to_end(&mut json_data)?;
+    } else {
+        reader.read_to_end(&mut json_data)?;
+    }
+
+    parse_file(path, &json_data)
+}
+
+/// Synchronously saves data to a zstd-compressed JSON file.
+///
+/// The data is serialized to JSON and streamed through a zstd encoder into the file, using the
+/// same temporary-file-and-rename strategy as [`save`](crate::save). Higher levels compress
+/// better but more slowly; decompression speed is largely unaffected by the level.
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved, conventionally ending in `.json.zst`
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+/// * `level` - The compression level, from 1 (fastest) to 22 (smallest)
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written to the file, if the operation succeeds
+/// * `Err(StorageError)` - With an [`io::ErrorKind::InvalidInput`] error if `level` is out of
+///   range, if file creation, compression or writing fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save_zstd, load_zstd};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let path = dir.path().join("numbers.json.zst");
+///
+/// save_zstd(&path, vec![1, 2, 3], 3).unwrap();
+/// let loaded: Vec<i32> = load_zstd(&path).unwrap();
+/// assert_eq!(loaded, vec![1, 2, 3]);
+/// ```
+pub fn save_zstd<P, T>(path: P, data: T, level: i32) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    if !(1..=22).contains(&level) {
+        return Err(StorageError::Io(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("zstd compression level must be between 1 and 22, got {level}"),
+        )));
+    }
+
+    write_atomic(path.as_ref(), |writer| {
+        let mut writer = CountingWriter::new(writer);
+        let mut encoder = zstd::Encoder::new(&mut writer, level)?;
+        write_json(&mut encoder, &data, &SaveOptions::default())?;
+        encoder.finish()?;
+
+        Ok(writer.count)
+    })
+}
+
+/// Synchronously loads data from a zstd-compressed or plain JSON file.
+///
+/// The file is checked for the zstd magic bytes (`0x28 0xb5 0x2f 0xfd`): compressed files are
+/// decoded on the fly, while anything else is parsed as plain JSON, so the same call works for
+/// both.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - [`StorageError::Empty`] if the file or its decompressed contents are
+///   empty, or if file reading, decompression or deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save, load_zstd};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save(temp_file.path(), "plain").unwrap();
+///
+/// let loaded: String = load_zstd(temp_file.path()).unwrap();
+/// assert_eq!(loaded, "plain");
+/// ```
+pub fn load_zstd<P, T>(path: P) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    let path = path.as_ref();
+    let mut reader = io::BufReader::new(std::fs::File::open(path)?);
+
+    let mut json_data = Vec::new();
+    if reader.fill_buf()?.starts_with(&ZSTD_MAGIC) {
+        zstd::Decoder::with_buffer(reader)?.read_to_end(&mut json_data)?;
+    } else {
+        reader.read_to_end(&mut json_data)?;
+    }
+
+    parse_file(path, &json_data)
+}
+
+/// Synchronously saves data to a JSON file, compressing it as implied by the file extension.
+///
+/// Paths ending in `.gz` are written as with [`save_compressed`], paths ending in `.zst` as
+/// with [`save_zstd`] at zstd's default level, and any other path as with
+/// [`save`](crate::save). Together with [`load_auto`] this lets a store mix compressed and
+/// plain files without branching on the format at every call site.
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written to the file, if the operation succeeds
+/// * `Err(StorageError)` - If file creation, compression or writing fails, or if serialization
+///   fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save_auto, load_auto};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// for name in ["plain.json", "small.json.gz", "smaller.json.zst"] {
+///     let path = dir.path().join(name);
+///     save_auto(&path, vec![1, 2, 3]).unwrap();
+///
+///     let loaded: Vec<i32> = load_auto(&path).unwrap();
+///     assert_eq!(loaded, vec![1, 2, 3]);
+/// }
+/// ```
+pub fn save_auto<P, T>(path: P, data: T) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    let path = path.as_ref();
+
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("gz") => save_compressed(path, data),
+        Some("zst") => save_zstd(path, data, zstd::DEFAULT_COMPRESSION_LEVEL),
+        _ => crate::save(path, data),
+    }
+}
+
+/// Synchronously loads data from a gzip-compressed, zstd-compressed or plain JSON file.
+///
+/// The format is detected from the magic bytes at the start of the file rather than from the
+/// extension, so a file is read correctly even if it was renamed. This reads everything
+/// [`save_auto`], [`save_compressed`], [`save_zstd`] and [`save`](crate::save) write.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - [`StorageError::Empty`] if the file or its decompressed contents are
+///   empty, or if file reading, decompression or deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save_zstd, load_auto};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save_zstd(temp_file.path(), "compressed", 3).unwrap();
+///
+/// let loaded: String = load_auto(temp_file.path()).unwrap();
+/// assert_eq!(loaded, "compressed");
+/// ```
+pub fn load_auto<P, T>(path: P) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    let path = path.as_ref();
+    let mut reader = io::BufReader::new(std::fs::File::open(path)?);
+    let header = reader.fill_buf()?;
+
+    let mut json_data = Vec::new();
+    if header.starts_with(&GZIP_MAGIC) {
+        GzDecoder::new(reader).read_to_end(&mut json_data)?;
+    } else if header.starts_with(&ZSTD_MAGIC) {
+        zstd::Decoder::with_buffer(reader)?.read_to_end(&mut json_data)?;
+    } else {
+        reader.read_to_end(&mut json_data)?;
+    }
+
+    parse_file(path, &json_data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load, save};
+    use tempfile::tempdir;
+
+    #[test]
+    fn save_compressed_test() {
+        let dir = tempdir().unwrap();
+        let plain_path = dir.path().join("data.json");
+        let compressed_path = dir.path().join("data.json.gz");
+
+        let data: Vec<String> = (0..1_000).map(|i| format!("record-{}", i % 10)).collect();
+        save(&plain_path, &data).unwrap();
+        let written = save_compressed(&compressed_path, &data).unwrap();
+
+        let compressed = std::fs::read(&compressed_path).unwrap();
+        assert!(compressed.starts_with(&GZIP_MAGIC));
+        assert_eq!(written, compressed.len());
+
+        let plain_size = std::fs::metadata(&plain_path).unwrap().len();
+        assert!((compressed.len() as u64) < plain_size);
+    }
+
+    #[test]
+    fn load_compressed_test() {
+        let dir = tempdir().unwrap();
+        let compressed_path = dir.path().join("data.json.gz");
+        let plain_path = dir.path().join("data.json");
+
+        let data = vec![(1, "one".to_string()), (2, "two".to_string())];
+        save_compressed(&compressed_path, &data).unwrap();
+        save(&plain_path, &data).unwrap();
+
+        let from_compressed: Vec<(i32, String)> = load_compressed(&compressed_path).unwrap();
+        let from_plain: Vec<(i32, String)> = load_compressed(&plain_path).unwrap();
+        assert_eq!(from_compressed, data);
+        assert_eq!(from_plain, data);
+
+        let plain_loaded: Result<Vec<(i32, String)>, _> = load(&compressed_path);
+        assert!(plain_loaded.is_err());
+    }
+
+    #[test]
+    fn save_zstd_test() {
+        let dir = tempdir().unwrap();
+        let fast_path = dir.path().join("fast.json.zst");
+        let small_path = dir.path().join("small.json.zst");
+
+        let data: Vec<String> = (0..20_000u64)
+            .map(|i| format!("sensor-{}:{}", i % 97, (i * 7919) % 1_000))
+            .collect();
+        let written = save_zstd(&fast_path, &data, 1).unwrap();
+        save_zstd(&small_path, &data, 19).unwrap();
+
+        let fast = std::fs::read(&fast_path).unwrap();
+        let small = std::fs::read(&small_path).unwrap();
+        assert!(fast.starts_with(&ZSTD_MAGIC));
+        assert_eq!(written, fast.len());
+        assert!(small.len() < fast.len());
+
+        let result = save_zstd(dir.path().join("invalid.json.zst"), &data, 0);
+        assert!(matches!(
+            result,
+            Err(StorageError::Io(err)) if err.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+
+    #[test]
+    fn load_zstd_test() {
+        let dir = tempdir().unwrap();
+        let compressed_path = dir.path().join("data.json.zst");
+        let plain_path = dir.path().join("data.json");
+
+        let data = vec!["x".repeat(100), "y".repeat(100)];
+        save_zstd(&compressed_path, &data, 3).unwrap();
+        save(&plain_path, &data).unwrap();
+
+        let from_compressed: Vec<String> = load_zstd(&compressed_path).unwrap();
+        let from_plain: Vec<String> = load_zstd(&plain_path).unwrap();
+        assert_eq!(from_compressed, data);
+        assert_eq!(from_plain, data);
+    }
+
+    #[test]
+    fn save_auto_test() {
+        let dir = tempdir().unwrap();
+        let plain_path = dir.path().join("data.json");
+        let gzip_path = dir.path().join("data.json.gz");
+        let zstd_path = dir.path().join("data.json.zst");
+
+        for path in [&plain_path, &gzip_path, &zstd_path] {
+            let written = save_auto(path, "auto").unwrap();
+            assert_eq!(written as u64, std::fs::metadata(path).unwrap().len());
+        }
+
+        assert_eq!(std::fs::read(&plain_path).unwrap(), b"\"auto\"");
+        assert!(std::fs::read(&gzip_path).unwrap().starts_with(&GZIP_MAGIC));
+        assert!(std::fs::read(&zstd_path).unwrap().starts_with(&ZSTD_MAGIC));
+    }
+
+    #[test]
+    fn load_auto_test() {
+        let dir = tempdir().unwrap();
+        let renamed_path = dir.path().join("really-zstd.json.gz");
+        let plain_path = dir.path().join("data.json");
+
+        save_zstd(&renamed_path, [7, 8], 5).unwrap();
+        save(&plain_path, [9]).unwrap();
+
+        let from_zstd: Vec<i32> = load_auto(&renamed_path).unwrap();
+        let from_plain: Vec<i32> = load_auto(&plain_path).unwrap();
+        assert_eq!(from_zstd, vec![7, 8]);
+        assert_eq!(from_plain, vec![9]);
+    }
+
+    #[test]
+    fn load_compressed_parse_error_test() {
+        let dir = tempdir().unwrap();
+        let plain_path = dir.path().join("data.json");
+        let gzip_path = dir.path().join("data.json.gz");
+        let zstd_path = dir.path().join("data.json.zst");
+
+        let corrupt = b"[1, 2,";
+        std::fs::write(&plain_path, corrupt).unwrap();
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        io::Write::write_all(&mut encoder, corrupt).unwrap();
+        std::fs::write(&gzip_path, encoder.finish().unwrap()).unwrap();
+        std::fs::write(&zstd_path, zstd::encode_all(&corrupt[..], 3).unwrap()).unwrap();
+
+        let is_parse_error = |result: Result<Vec<u32>, StorageError>, path: &Path| matches!(result, Err(StorageError::Parse { path: failed, .. }) if failed == path);
+        for path in [&plain_path, &gzip_path] {
+            assert!(is_parse_error(load_compressed(path), path));
+        }
+        for path in [&plain_path, &zstd_path] {
+            assert!(is_parse_error(load_zstd(path), path));
+        }
+        for path in [&plain_path, &gzip_path, &zstd_path] {
+            assert!(is_parse_error(load_auto(path), path));
+        }
+    }
+
+    #[test]
+    fn load_compressed_empty_test() {
+        let dir = tempdir().unwrap();
+        let plain_path = dir.path().join("empty.json");
+        let gzip_path = dir.path().join("empty.json.gz");
+        let zstd_path = dir.path().join("empty.json.zst");
+
+        std::fs::write(&plain_path, b"").unwrap();
+        let encoder = GzEncoder::new(Vec::new(), Compression::default());
+        std::fs::write(&gzip_path, encoder.finish().unwrap()).unwrap();
+        std::fs::write(&zstd_path, zstd::encode_all(&b""[..], 3).unwrap()).unwrap();
+
+        let is_empty_error = |result: Result<u32, StorageError>, path: &Path| matches!(result, Err(StorageError::Empty(empty)) if empty == path);
+        for path in [&plain_path, &gzip_path] {
+            assert!(is_empty_error(load_compressed(path), path));
+        }
+        for path in [&plain_path, &zstd_path] {
+            assert!(is_empty_error(load_zstd(path), path));
+        }
+        for path in [&plain_path, &gzip_path, &zstd_path] {
+            assert!(is_empty_error(load_auto(path), path));
+        }
+    }
+
+    #[test]
+    fn load_compressed_bom_test() {
+        let dir = tempdir().unwrap();
+        let plain_path = dir.path().join("data.json");
+        let gzip_path = dir.path().join("data.json.gz");
+        let zstd_path = dir.path().join("data.json.zst");
+
+        let with_bom = b"\xEF\xBB\xBF[1, 2]";
+        std::fs::write(&plain_path, with_bom).unwrap();
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        io::Write::write_all(&mut encoder, with_bom).unwrap();
+        std::fs::write(&gzip_path, encoder.finish().unwrap()).unwrap();
+        std::fs::write(&zstd_path, zstd::encode_all(&with_bom[..], 3).unwrap()).unwrap();
+
+        for path in [&plain_path, &gzip_path] {
+            assert_eq!(load_compressed::<_, Vec<u32>>(path).unwrap(), [1, 2]);
+        }
+        for path in [&plain_path, &zstd_path] {
+            assert_eq!(load_zstd::<_, Vec<u32>>(path).unwrap(), [1, 2]);
+        }
+        for path in [&plain_path, &gzip_path, &zstd_path] {
+            assert_eq!(load_auto::<_, Vec<u32>>(path).unwrap(), [1, 2]);
+        }
+    }
+}
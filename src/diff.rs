@@ -0,0 +1,218 @@
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{StorageError, try_load};
+
+/// A single difference between two JSON documents, as reported by [`diff`].
+///
+/// Locations are JSON Pointers (RFC 6901), such as `/server/port` or `/tags/0`; the empty
+/// pointer `""` refers to the whole document.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldChange {
+    /// A field or array element exists only in the new data.
+    Added {
+        /// Where the value was added.
+        pointer: String,
+        /// The new value.
+        value: Value,
+    },
+    /// A field or array element exists only in the stored data.
+    Removed {
+        /// Where the value was removed.
+        pointer: String,
+        /// The stored value.
+        value: Value,
+    },
+    /// A value exists on both sides but differs.
+    Modified {
+        /// Where the value changed.
+        pointer: String,
+        /// The stored value.
+        old: Value,
+        /// The new value.
+        new: Value,
+    },
+}
+
+impl FieldChange {
+    /// Returns the JSON Pointer of the changed location.
+    pub fn pointer(&self) -> &str {
+        match self {
+            FieldChange::Added { pointer, .. }
+            | FieldChange::Removed { pointer, .. }
+            | FieldChange::Modified { pointer, .. } => pointer,
+        }
+    }
+}
+
+/// Synchronously compares a stored JSON file with data that is about to replace it.
+///
+/// Both sides are compared as [`serde_json::Value`]s. Objects are compared key by key and
+/// arrays index by index, recursing into values present on both sides, so a change deep in a
+/// document is reported at its own location rather than as a change of the whole parent.
+/// Changes are listed in document order, with object keys sorted. A missing file is reported
+/// as a single [`FieldChange::Added`] of the whole document. The file is not modified.
+///
+/// # Arguments
+///
+/// * `path` - The stored JSON file to compare against
+/// * `new` - The data that would be saved (must implement `Serialize`)
+///
+/// # Returns
+///
+/// * `Ok(Vec<FieldChange>)` - The differences, empty if saving `new` would change nothing
+/// * `Err(StorageError)` - If reading the file fails, or if (de)serialization fails
+///
+/// # Example
+///
+/// ```
+/// use serde_json::json;
+/// use storage_service::{FieldChange, diff, save};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save(temp_file.path(), json!({ "port": 80, "debug": true })).unwrap();
+///
+/// let changes = diff(temp_file.path(), &json!({ "port": 8080, "debug": true })).unwrap();
+/// assert_eq!(
+///     changes,
+///     vec![FieldChange::Modified { pointer: "/port".to_string(), old: json!(80), new: json!(8080) }]
+/// );
+/// ```
+pub fn diff<P, T>(path: P, new: &T) -> Result<Vec<FieldChange>, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize + ?Sized,
+{
+    let new = serde_json::to_value(new).map_err(StorageError::from_serialize)?;
+
+    let mut changes = Vec::new();
+    match try_load::<_, Value>(path)? {
+        Some(old) => diff_values(String::new(), old, new, &mut changes),
+        None => changes.push(FieldChange::Added {
+            pointer: String::new(),
+            value: new,
+        }),
+    }
+
+    Ok(changes)
+}
+
+fn diff_values(pointer: String, old: Value, new: Value, changes: &mut Vec<FieldChange>) {
+    match (old, new) {
+        (Value::Object(mut old), Value::Object(mut new)) => {
+            let mut keys: Vec<String> = old.keys().chain(new.keys()).cloned().collect();
+            keys.sort();
+            keys.dedup();
+
+            for key in keys {
+                let pointer = format!("{pointer}/{}", escape(&key));
+                match (old.remove(&key), new.remove(&key)) {
+                    (Some(old), Some(new)) => diff_values(pointer, old, new, changes),
+                    (Some(value), None) => changes.push(FieldChange::Removed { pointer, value }),
+                    (None, Some(value)) => changes.push(FieldChange::Added { pointer, value }),
+                    (None, None) => {}
+                }
+            }
+        }
+        (Value::Array(old), Value::Array(new)) => {
+            let (old_len, new_len) = (old.len(), new.len());
+            let mut old = old.into_iter();
+            let mut new = new.into_iter();
+
+            for index in 0..old_len.max(new_len) {
+                let pointer = format!("{pointer}/{index}");
+                match (old.next(), new.next()) {
+                    (Some(old), Some(new)) => diff_values(pointer, old, new, changes),
+                    (Some(value), None) => changes.push(FieldChange::Removed { pointer, value }),
+                    (None, Some(value)) => changes.push(FieldChange::Added { pointer, value }),
+                    (None, None) => {}
+                }
+            }
+        }
+        (old, new) if old != new => changes.push(FieldChange::Modified { pointer, old, new }),
+        _ => {}
+    }
+}
+
+/// Escapes a key for use as a JSON Pointer reference token.
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tempfile::{NamedTempFile, tempdir};
+
+    use super::*;
+    use crate::save;
+
+    #[test]
+    fn diff_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+        save(
+            path,
+            json!({
+                "name": "app",
+                "server": { "host": "localhost", "port": 80 },
+                "tags": ["a", "b", "c"],
+                "a/b~c": 1,
+            }),
+        )
+        .unwrap();
+
+        let new = json!({
+            "name": "app",
+            "server": { "host": "localhost", "port": 8080, "tls": true },
+            "tags": ["a", "x"],
+            "a/b~c": 1,
+        });
+        assert_eq!(
+            diff(path, &new).unwrap(),
+            vec![
+                FieldChange::Modified {
+                    pointer: "/server/port".to_string(),
+                    old: json!(80),
+                    new: json!(8080),
+                },
+                FieldChange::Added {
+                    pointer: "/server/tls".to_string(),
+                    value: json!(true),
+                },
+                FieldChange::Modified {
+                    pointer: "/tags/1".to_string(),
+                    old: json!("b"),
+                    new: json!("x"),
+                },
+                FieldChange::Removed {
+                    pointer: "/tags/2".to_string(),
+                    value: json!("c"),
+                },
+            ]
+        );
+
+        let changes = diff(path, &json!({ "a/b~c": 2 })).unwrap();
+        assert_eq!(changes[0].pointer(), "/a~1b~0c");
+
+        let unchanged: serde_json::Value = crate::load(path).unwrap();
+        assert!(diff(path, &unchanged).unwrap().is_empty());
+    }
+
+    #[test]
+    fn diff_missing_file_test() {
+        let dir = tempdir().unwrap();
+
+        let changes = diff(dir.path().join("new.json"), &[1, 2]).unwrap();
+        assert_eq!(
+            changes,
+            vec![FieldChange::Added {
+                pointer: String::new(),
+                value: json!([1, 2]),
+            }]
+        );
+    }
+}
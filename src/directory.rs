@@ -0,0 +1,751 @@
+#[cfg(feature = "async")]
+use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+#[cfg(feature = "async")]
+use tokio::{sync::Semaphore, task::JoinSet};
+
+use crate::{Clock, StorageError, SystemClock, load, save};
+#[cfg(feature = "async")]
+use crate::{load_async, save_async};
+
+/// The outcome of a batch operation that handles each key independently.
+#[derive(Debug, Default)]
+pub struct BatchReport {
+    /// The keys that were processed successfully, in sorted order.
+    pub succeeded: Vec<String>,
+    /// The keys that failed, in sorted order, together with their errors.
+    pub failed: Vec<(String, StorageError)>,
+}
+
+impl BatchReport {
+    /// Returns whether every key was processed successfully.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Synchronously lists the stored files in a directory.
+///
+/// Subdirectories are skipped, and the returned paths are sorted so the result is deterministic
+/// across platforms and filesystems.
+///
+/// # Arguments
+///
+/// * `dir` - The directory to list
+/// * `extension` - If set, only files with this extension (without the leading dot, e.g.
+///   `"json"`) are returned
+///
+/// # Returns
+///
+/// * `Ok(Vec<PathBuf>)` - The sorted paths of the matching files
+/// * `Err(StorageError)` - With an [`io::ErrorKind::NotFound`] error naming the directory if it
+///   does not exist, or any other I/O error raised while reading it
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save, list};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// save(dir.path().join("b.json"), 2).unwrap();
+/// save(dir.path().join("a.json"), 1).unwrap();
+/// std::fs::write(dir.path().join("notes.txt"), "").unwrap();
+///
+/// let files = list(dir.path(), Some("json")).unwrap();
+/// assert_eq!(files, vec![dir.path().join("a.json"), dir.path().join("b.json")]);
+/// ```
+pub fn list<P>(dir: P, extension: Option<&str>) -> Result<Vec<PathBuf>, StorageError>
+where
+    P: AsRef<Path>,
+{
+    let dir = dir.as_ref();
+    let mut files = Vec::new();
+
+    for entry in std::fs::read_dir(dir).map_err(|err| directory_error(dir, err))? {
+        let path = entry?.path();
+        if path.is_file() && matches_extension(&path, extension) {
+            files.push(path);
+        }
+    }
+    files.sort();
+
+    Ok(files)
+}
+
+/// Asynchronously lists the stored files in a directory.
+///
+/// The async counterpart of [`list`], built on [`tokio::fs::read_dir`].
+///
+/// # Arguments
+///
+/// * `dir` - The directory to list
+/// * `extension` - If set, only files with this extension (without the leading dot, e.g.
+///   `"json"`) are returned
+///
+/// # Returns
+///
+/// * `Ok(Vec<PathBuf>)` - The sorted paths of the matching files
+/// * `Err(StorageError)` - With an [`io::ErrorKind::NotFound`] error naming the directory if it
+///   does not exist, or any other I/O error raised while reading it
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save_async, list_async};
+/// use tempfile::tempdir;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let dir = tempdir().unwrap();
+///     save_async(dir.path().join("session.json"), "data").await.unwrap();
+///
+///     let files = list_async(dir.path(), None).await.unwrap();
+///     assert_eq!(files.len(), 1);
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub async fn list_async<P>(dir: P, extension: Option<&str>) -> Result<Vec<PathBuf>, StorageError>
+where
+    P: AsRef<Path>,
+{
+    let dir = dir.as_ref();
+    let mut files = Vec::new();
+
+    let mut entries = tokio::fs::read_dir(dir)
+        .await
+        .map_err(|err| directory_error(dir, err))?;
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        let is_file = tokio::fs::metadata(&path)
+            .await
+            .is_ok_and(|metadata| metadata.is_file());
+        if is_file && matches_extension(&path, extension) {
+            files.push(path);
+        }
+    }
+    files.sort();
+
+    Ok(files)
+}
+
+/// Synchronously saves a set of named documents, one JSON file per key.
+///
+/// Each value is written to `dir/<key>.json`. Keys are saved independently and in sorted order:
+/// a failing key does not stop the others, and the returned [`BatchReport`] says exactly which
+/// keys were written and which were not. Keys that are empty or would leave `dir` (such as
+/// `".."` or `"a/b"`) fail with an [`io::ErrorKind::InvalidInput`] error.
+///
+/// # Arguments
+///
+/// * `dir` - The directory to save the documents in
+/// * `documents` - The documents to save, keyed by name
+///
+/// # Returns
+///
+/// A [`BatchReport`] listing the keys that were saved and the keys that failed.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use storage_service::{save_all, load_all};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let documents = HashMap::from([
+///     ("alice".to_string(), 30),
+///     ("bob".to_string(), 25),
+/// ]);
+///
+/// let report = save_all(dir.path(), &documents);
+/// assert!(report.is_complete());
+///
+/// let loaded: HashMap<String, u32> = load_all(dir.path()).unwrap();
+/// assert_eq!(loaded, documents);
+/// ```
+pub fn save_all<P, T>(dir: P, documents: &HashMap<String, T>) -> BatchReport
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    let dir = dir.as_ref();
+    let mut keys: Vec<&String> = documents.keys().collect();
+    keys.sort();
+
+    let mut report = BatchReport::default();
+    for key in keys {
+        let result = key_path(dir, key).and_then(|path| save(path, &documents[key]));
+        match result {
+            Ok(_) => report.succeeded.push(key.clone()),
+            Err(err) => report.failed.push((key.clone(), err)),
+        }
+    }
+
+    report
+}
+
+/// Synchronously loads every JSON document in a directory, keyed by file stem.
+///
+/// This reads back what [`save_all`] wrote: each `dir/<key>.json` becomes an entry `key`.
+/// Files with other extensions and subdirectories are ignored.
+///
+/// # Arguments
+///
+/// * `dir` - The directory to load the documents from
+///
+/// # Returns
+///
+/// * `Ok(HashMap<String, T>)` - The deserialized documents keyed by name
+/// * `Err(StorageError)` - If the directory cannot be read, or if any document fails to load
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use storage_service::{save, load_all};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// save(dir.path().join("theme.json"), "dark").unwrap();
+///
+/// let loaded: HashMap<String, String> = load_all(dir.path()).unwrap();
+/// assert_eq!(loaded["theme"], "dark");
+/// ```
+pub fn load_all<P, T>(dir: P) -> Result<HashMap<String, T>, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    let mut documents = HashMap::new();
+
+    for path in list(dir, Some("json"))? {
+        let Some(key) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        documents.insert(key.to_string(), load(&path)?);
+    }
+
+    Ok(documents)
+}
+
+/// Synchronously deletes the files in a directory that were last modified more than `age` ago.
+///
+/// This is a simple retention primitive for stores that accumulate files, such as one file per
+/// session. Subdirectories are skipped, and files with a modification time in the future are
+/// kept. A failure to read, inspect or delete one entry does not stop the purge: the remaining
+/// entries are still processed, and the failures are reported together once the directory has
+/// been walked.
+///
+/// # Arguments
+///
+/// * `dir` - The directory to purge
+/// * `age` - The minimum time since a file's last modification for it to be deleted
+/// * `extension` - If set, only files with this extension (without the leading dot, e.g.
+///   `"json"`) are considered
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of files deleted
+/// * `Err(io::Error)` - If the directory cannot be opened, or if any entry could not be read,
+///   inspected or deleted; the error has the kind of the first failure and names every file
+///   that was left behind
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use storage_service::{purge_older_than, save};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// save(dir.path().join("session.json"), "fresh").unwrap();
+///
+/// let purged = purge_older_than(dir.path(), Duration::from_secs(90 * 24 * 60 * 60), Some("json"));
+/// assert_eq!(purged.unwrap(), 0);
+/// ```
+pub fn purge_older_than<P>(
+    dir: P,
+    age: Duration,
+    extension: Option<&str>,
+) -> Result<usize, io::Error>
+where
+    P: AsRef<Path>,
+{
+    purge_older_than_with_clock(dir, age, extension, &SystemClock)
+}
+
+/// Like [`purge_older_than`], measuring the age of files against the time of `clock`.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use storage_service::{ManualClock, purge_older_than_with_clock, save};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// save(dir.path().join("session.json"), "stale").unwrap();
+///
+/// let clock = ManualClock::default();
+/// clock.advance(Duration::from_secs(2 * 60 * 60));
+///
+/// let purged = purge_older_than_with_clock(dir.path(), Duration::from_secs(60 * 60), None, &clock);
+/// assert_eq!(purged.unwrap(), 1);
+/// ```
+pub fn purge_older_than_with_clock<P, C>(
+    dir: P,
+    age: Duration,
+    extension: Option<&str>,
+    clock: &C,
+) -> Result<usize, io::Error>
+where
+    P: AsRef<Path>,
+    C: Clock + ?Sized,
+{
+    let dir = dir.as_ref();
+    let cutoff = clock
+        .now()
+        .checked_sub(age)
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+
+    let entries = std::fs::read_dir(dir).map_err(|err| directory_error(dir, err))?;
+
+    let mut purged = 0;
+    let mut failures = Vec::new();
+    for entry in entries {
+        let entry = match entry {
+            Ok(entry) => entry,
+            // Without an entry there is no file name to report, so name the directory.
+            Err(err) => {
+                failures.push((dir.to_path_buf(), err));
+                continue;
+            }
+        };
+        let path = entry.path();
+        let metadata = match entry.metadata() {
+            Ok(metadata) => metadata,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => {
+                failures.push((path, err));
+                continue;
+            }
+        };
+        if !metadata.is_file() || !matches_extension(&path, extension) {
+            continue;
+        }
+        match metadata.modified() {
+            Ok(modified) if modified >= cutoff => continue,
+            Ok(_) => {}
+            Err(err) => {
+                failures.push((path, err));
+                continue;
+            }
+        }
+
+        match std::fs::remove_file(&path) {
+            Ok(()) => purged += 1,
+            // Already gone, for example removed by a concurrent purge.
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => failures.push((path, err)),
+        }
+    }
+
+    let Some((_, first)) = failures.first() else {
+        return Ok(purged);
+    };
+    let details: Vec<String> = failures
+        .iter()
+        .map(|(path, err)| format!("{}: {err}", path.display()))
+        .collect();
+
+    Err(io::Error::new(
+        first.kind(),
+        format!(
+            "purged {purged} files but failed to purge {}: {}",
+            failures.len(),
+            details.join("; ")
+        ),
+    ))
+}
+
+/// Asynchronously saves a set of named documents, one JSON file per key, concurrently.
+///
+/// The async counterpart of [`save_all`]. At most `concurrency` files are written at the same
+/// time, which keeps the number of open file descriptors bounded; a `concurrency` of zero is
+/// treated as one. Keys are saved independently, and the returned [`BatchReport`] lists them in
+/// sorted order regardless of the order in which the writes finished.
+///
+/// # Arguments
+///
+/// * `dir` - The directory to save the documents in
+/// * `documents` - The documents to save, keyed by name
+/// * `concurrency` - The maximum number of files to write concurrently
+///
+/// # Returns
+///
+/// A [`BatchReport`] listing the keys that were saved and the keys that failed.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use storage_service::save_all_async;
+/// use tempfile::tempdir;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let dir = tempdir().unwrap();
+///     let documents: HashMap<String, usize> = (0..100).map(|i| (format!("doc-{i}"), i)).collect();
+///
+///     let report = save_all_async(dir.path(), documents, 8).await;
+///     assert_eq!(report.succeeded.len(), 100);
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub async fn save_all_async<P, T>(
+    dir: P,
+    documents: HashMap<String, T>,
+    concurrency: usize,
+) -> BatchReport
+where
+    P: AsRef<Path>,
+    T: Serialize + Send + 'static,
+{
+    let dir = dir.as_ref();
+    let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+    let mut tasks = JoinSet::new();
+
+    for (key, data) in documents {
+        let path = key_path(dir, &key);
+        let semaphore = Arc::clone(&semaphore);
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let result = match path {
+                Ok(path) => save_async(path, data).await.map(drop),
+                Err(err) => Err(err),
+            };
+            (key, result)
+        });
+    }
+
+    join_all(tasks).await.1
+}
+
+/// Asynchronously loads every JSON document in a directory, keyed by file stem, concurrently.
+///
+/// The async counterpart of [`load_all`], with two differences: at most `concurrency` files
+/// are read at the same time, a `concurrency` of zero being treated as one, and a document
+/// that fails to load does not fail the whole call but is reported in the returned
+/// [`BatchReport`] instead.
+///
+/// # Arguments
+///
+/// * `dir` - The directory to load the documents from
+/// * `concurrency` - The maximum number of files to read concurrently
+///
+/// # Returns
+///
+/// * `Ok((HashMap<String, T>, BatchReport))` - The documents that loaded, keyed by name, and a
+///   report listing which keys succeeded and which failed
+/// * `Err(StorageError)` - If the directory cannot be read
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use storage_service::{save, load_all_async};
+/// use tempfile::tempdir;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let dir = tempdir().unwrap();
+///     save(dir.path().join("theme.json"), "dark").unwrap();
+///     std::fs::write(dir.path().join("broken.json"), "{").unwrap();
+///
+///     let (loaded, report) = load_all_async::<_, String>(dir.path(), 4).await.unwrap();
+///     assert_eq!(loaded["theme"], "dark");
+///     assert_eq!(report.failed[0].0, "broken");
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub async fn load_all_async<P, T>(
+    dir: P,
+    concurrency: usize,
+) -> Result<(HashMap<String, T>, BatchReport), StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned + Send + 'static,
+{
+    let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+    let mut tasks = JoinSet::new();
+
+    for path in list_async(dir, Some("json")).await? {
+        let Some(key) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let key = key.to_string();
+        let semaphore = Arc::clone(&semaphore);
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            (key, load_async(path).await)
+        });
+    }
+
+    Ok(join_all(tasks).await)
+}
+
+/// Waits for every per-key task, collecting the values and a report sorted by key.
+#[cfg(feature = "async")]
+async fn join_all<V: 'static>(
+    mut tasks: JoinSet<(String, Result<V, StorageError>)>,
+) -> (HashMap<String, V>, BatchReport) {
+    let mut values = HashMap::new();
+    let mut report = BatchReport::default();
+
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok((key, Ok(value))) => {
+                report.succeeded.push(key.clone());
+                values.insert(key, value);
+            }
+            Ok((key, Err(err))) => report.failed.push((key, err)),
+            Err(err) => std::panic::resume_unwind(err.into_panic()),
+        }
+    }
+    report.succeeded.sort();
+    report.failed.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    (values, report)
+}
+
+/// Returns `dir/<key>.json`, rejecting keys that are not a single plain file name.
+fn key_path(dir: &Path, key: &str) -> Result<PathBuf, StorageError> {
+    let is_plain = !key.is_empty()
+        && key != "."
+        && key != ".."
+        && !key.contains(['/', '\\'])
+        && Path::new(key).file_name() == Some(key.as_ref());
+
+    if !is_plain {
+        return Err(StorageError::Io(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid document key: {key:?}"),
+        )));
+    }
+
+    Ok(dir.join(format!("{key}.json")))
+}
+
+/// Returns whether `path` has the expected extension, or `true` if no extension is required.
+fn matches_extension(path: &Path, extension: Option<&str>) -> bool {
+    extension.is_none_or(|extension| path.extension().is_some_and(|actual| actual == extension))
+}
+
+/// Names the directory in a `NotFound` error, which the OS error leaves out.
+fn directory_error(dir: &Path, err: io::Error) -> io::Error {
+    match err.kind() {
+        io::ErrorKind::NotFound => io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("directory not found: {}", dir.display()),
+        ),
+        _ => err,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn list_test() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("c.json"), "3").unwrap();
+        std::fs::write(dir.path().join("a.json"), "1").unwrap();
+        std::fs::write(dir.path().join("b.txt"), "2").unwrap();
+        std::fs::create_dir(dir.path().join("nested.json")).unwrap();
+
+        let all = list(dir.path(), None).unwrap();
+        assert_eq!(
+            all,
+            vec![
+                dir.path().join("a.json"),
+                dir.path().join("b.txt"),
+                dir.path().join("c.json"),
+            ]
+        );
+
+        let json = list(dir.path(), Some("json")).unwrap();
+        assert_eq!(
+            json,
+            vec![dir.path().join("a.json"), dir.path().join("c.json")]
+        );
+
+        let missing = list(dir.path().join("missing"), None);
+        assert!(matches!(
+            missing,
+            Err(StorageError::Io(err)) if err.kind() == io::ErrorKind::NotFound
+        ));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn list_async_test() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("b.json"), "2").unwrap();
+        std::fs::write(dir.path().join("a.json"), "1").unwrap();
+        std::fs::create_dir(dir.path().join("sub")).unwrap();
+
+        let files = list_async(dir.path(), Some("json")).await.unwrap();
+        assert_eq!(
+            files,
+            vec![dir.path().join("a.json"), dir.path().join("b.json")]
+        );
+
+        assert!(list_async(dir.path().join("missing"), None).await.is_err());
+    }
+
+    #[test]
+    fn save_all_test() {
+        let dir = tempdir().unwrap();
+        let documents = HashMap::from([
+            ("b".to_string(), vec![2]),
+            ("a".to_string(), vec![1]),
+            ("../escape".to_string(), vec![0]),
+        ]);
+
+        let report = save_all(dir.path(), &documents);
+        assert!(!report.is_complete());
+        assert_eq!(report.succeeded, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, "../escape");
+        assert!(matches!(
+            &report.failed[0].1,
+            StorageError::Io(err) if err.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(dir.path().join("a.json").exists());
+        assert!(!dir.path().parent().unwrap().join("escape.json").exists());
+    }
+
+    #[test]
+    fn load_all_test() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("one.json"), "1").unwrap();
+        std::fs::write(dir.path().join("two.json"), "2").unwrap();
+        std::fs::write(dir.path().join("ignored.txt"), "x").unwrap();
+
+        let loaded: HashMap<String, u32> = load_all(dir.path()).unwrap();
+        assert_eq!(
+            loaded,
+            HashMap::from([("one".to_string(), 1), ("two".to_string(), 2)])
+        );
+
+        std::fs::write(dir.path().join("broken.json"), "{").unwrap();
+        let result: Result<HashMap<String, u32>, _> = load_all(dir.path());
+        assert!(matches!(result, Err(StorageError::Parse { .. })));
+    }
+
+    #[test]
+    fn purge_older_than_test() {
+        let dir = tempdir().unwrap();
+        let day = Duration::from_secs(24 * 60 * 60);
+        let backdate = |name: &str| {
+            let path = dir.path().join(name);
+            let file = std::fs::File::create(&path).unwrap();
+            file.set_modified(SystemTime::now() - 10 * day).unwrap();
+            path
+        };
+
+        let old_json = backdate("old.json");
+        let old_text = backdate("old.txt");
+        std::fs::write(dir.path().join("new.json"), "1").unwrap();
+        std::fs::create_dir(dir.path().join("archive")).unwrap();
+        let nested = dir.path().join("archive").join("nested.json");
+        std::fs::write(&nested, "2").unwrap();
+
+        assert_eq!(
+            purge_older_than(dir.path(), 7 * day, Some("json")).unwrap(),
+            1
+        );
+        assert!(!old_json.exists());
+        assert!(old_text.exists());
+
+        assert_eq!(purge_older_than(dir.path(), 7 * day, None).unwrap(), 1);
+        assert!(!old_text.exists());
+        assert!(dir.path().join("new.json").exists());
+        assert!(nested.exists());
+
+        let missing = purge_older_than(dir.path().join("missing"), day, None).unwrap_err();
+        assert_eq!(missing.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn purge_older_than_with_clock_test() {
+        let dir = tempdir().unwrap();
+        let hour = Duration::from_secs(60 * 60);
+        std::fs::write(dir.path().join("session.json"), "1").unwrap();
+
+        let clock = crate::ManualClock::default();
+        clock.advance(hour);
+        assert_eq!(
+            purge_older_than_with_clock(dir.path(), 2 * hour, None, &clock).unwrap(),
+            0
+        );
+
+        clock.advance(2 * hour);
+        assert_eq!(
+            purge_older_than_with_clock(dir.path(), 2 * hour, None, &clock).unwrap(),
+            1
+        );
+        assert!(!dir.path().join("session.json").exists());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn save_all_async_test() {
+        let dir = tempdir().unwrap();
+        let mut documents: HashMap<String, usize> =
+            (0..50).map(|i| (format!("doc-{i:02}"), i)).collect();
+        documents.insert("a/b".to_string(), 0);
+
+        let report = save_all_async(dir.path(), documents, 3).await;
+        assert_eq!(report.succeeded.len(), 50);
+        assert!(report.succeeded.is_sorted());
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, "a/b");
+
+        let loaded: HashMap<String, usize> = load_all(dir.path()).unwrap();
+        assert_eq!(loaded["doc-07"], 7);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn load_all_async_test() {
+        let dir = tempdir().unwrap();
+        for i in 0..20 {
+            std::fs::write(dir.path().join(format!("{i}.json")), i.to_string()).unwrap();
+        }
+        std::fs::write(dir.path().join("broken.json"), "{").unwrap();
+
+        let (loaded, report) = load_all_async::<_, u32>(dir.path(), 0).await.unwrap();
+        assert_eq!(loaded.len(), 20);
+        assert_eq!(loaded["13"], 13);
+        assert_eq!(report.succeeded.len(), 20);
+        assert!(matches!(
+            report.failed.as_slice(),
+            [(key, StorageError::Parse { .. })] if key == "broken"
+        ));
+
+        let missing = load_all_async::<_, u32>(dir.path().join("missing"), 4).await;
+        assert!(missing.is_err());
+    }
+}
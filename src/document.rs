@@ -0,0 +1,251 @@
+use std::{
+    fmt,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{Format, Json, StorageError, delete, exists, load_as, lock::update_as, save_as};
+
+/// A typed handle to a single stored file.
+///
+/// `Document` remembers the path, the stored type `T`, and the [`Format`] `F` (JSON unless
+/// specified), so repeated operations on the same file cannot mix up paths or types. It holds
+/// no open file or cached data; every method goes to disk, exactly like the corresponding free
+/// function.
+///
+/// # Example
+///
+/// ```
+/// use serde::{Serialize, Deserialize};
+/// use storage_service::Document;
+/// # #[cfg(feature = "msgpack")]
+/// use storage_service::MsgPack;
+/// use tempfile::tempdir;
+///
+/// #[derive(Serialize, Deserialize, Default, Debug, PartialEq)]
+/// struct Settings {
+///     volume: u8,
+/// }
+///
+/// let dir = tempdir().unwrap();
+/// let settings: Document<Settings> = Document::new(dir.path().join("settings.json"));
+///
+/// settings.save(&Settings { volume: 3 }).unwrap();
+/// settings.update(|s| s.volume += 1).unwrap();
+/// assert_eq!(settings.load().unwrap(), Settings { volume: 4 });
+///
+/// // The same API, stored as MessagePack.
+/// # #[cfg(feature = "msgpack")] {
+/// let binary: Document<Settings, MsgPack> = Document::new(dir.path().join("settings.msgpack"));
+/// binary.save(&Settings { volume: 9 }).unwrap();
+/// assert_eq!(binary.load().unwrap().volume, 9);
+/// # }
+/// ```
+pub struct Document<T, F = Json> {
+    path: PathBuf,
+    read_only: bool,
+    marker: PhantomData<fn() -> (T, F)>,
+}
+
+impl<T, F> Document<T, F> {
+    /// Creates a handle to the file at `path`; the file itself is not touched.
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            read_only: false,
+            marker: PhantomData,
+        }
+    }
+
+    /// Makes every writing method fail with [`StorageError::ReadOnly`] without touching the
+    /// file, while loads proceed normally.
+    ///
+    /// This guards against write paths accidentally running where the storage must not
+    /// change, such as on a replica.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Returns whether the handle was made [`read_only`](Document::read_only).
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Returns the path of the file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns whether the file exists; see [`exists`](crate::exists).
+    pub fn exists(&self) -> bool {
+        exists(&self.path)
+    }
+
+    /// Deletes the file; see [`delete`](crate::delete).
+    pub fn delete(&self) -> Result<(), StorageError> {
+        self.check_writable()?;
+        delete(&self.path)
+    }
+
+    fn check_writable(&self) -> Result<(), StorageError> {
+        if self.read_only {
+            return Err(StorageError::ReadOnly(self.path.clone()));
+        }
+
+        Ok(())
+    }
+}
+
+impl<T, F> Document<T, F>
+where
+    T: Serialize + DeserializeOwned,
+    F: Format,
+{
+    /// Loads and deserializes the stored value; see [`load_as`](crate::load_as).
+    pub fn load(&self) -> Result<T, StorageError> {
+        load_as::<F, _, _>(&self.path)
+    }
+
+    /// Serializes and atomically saves `data`, returning the number of bytes written; see
+    /// [`save_as`](crate::save_as).
+    pub fn save(&self, data: &T) -> Result<usize, StorageError> {
+        self.check_writable()?;
+        save_as::<F, _, _>(&self.path, data)
+    }
+
+    /// Applies `f` to the stored value under an exclusive lock and saves the result; see
+    /// [`update`](crate::update).
+    pub fn update<U>(&self, f: U) -> Result<T, StorageError>
+    where
+        T: Default,
+        U: FnOnce(&mut T),
+    {
+        self.check_writable()?;
+        update_as::<F, _, _, _>(&self.path, f)
+    }
+}
+
+impl<T, F> Clone for Document<T, F> {
+    fn clone(&self) -> Self {
+        Self::new(&self.path).read_only(self.read_only)
+    }
+}
+
+impl<T, F> fmt::Debug for Document<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Document")
+            .field("path", &self.path)
+            .field("read_only", &self.read_only)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use serde::Deserialize;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Default, Debug, PartialEq, Clone)]
+    struct Profile {
+        name: String,
+        visits: u32,
+    }
+
+    #[test]
+    fn document_test() {
+        let dir = tempdir().unwrap();
+        let document: Document<Profile> = Document::new(dir.path().join("profile.json"));
+        assert_eq!(document.path(), dir.path().join("profile.json"));
+
+        assert!(!document.exists());
+        assert!(matches!(
+            document.load(),
+            Err(StorageError::Io(err)) if err.kind() == io::ErrorKind::NotFound
+        ));
+
+        let profile = Profile {
+            name: "ada".to_string(),
+            visits: 1,
+        };
+        let written = document.save(&profile).unwrap();
+        assert!(document.exists());
+        assert_eq!(
+            written as u64,
+            std::fs::metadata(document.path()).unwrap().len()
+        );
+        assert_eq!(document.clone().load().unwrap(), profile);
+
+        let updated = document.update(|profile| profile.visits += 1).unwrap();
+        assert_eq!(updated.visits, 2);
+        assert_eq!(document.load().unwrap(), updated);
+
+        std::fs::write(document.path(), b"").unwrap();
+        assert!(matches!(
+            document.load(),
+            Err(StorageError::Empty(path)) if path == document.path()
+        ));
+
+        std::fs::write(document.path(), "{ \"name\": ").unwrap();
+        assert!(matches!(
+            document.load(),
+            Err(StorageError::Parse { path, .. }) if path == document.path()
+        ));
+        assert!(matches!(
+            document.update(|profile| profile.visits += 1),
+            Err(StorageError::Parse { path, .. }) if path == document.path()
+        ));
+
+        document.delete().unwrap();
+        assert!(!document.exists());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn document_format_test() {
+        let dir = tempdir().unwrap();
+        let document: Document<Profile, crate::Toml> =
+            Document::new(dir.path().join("profile.toml"));
+
+        let created = document
+            .update(|profile| profile.name = "new".to_string())
+            .unwrap();
+        assert_eq!(created.visits, 0);
+
+        let contents = std::fs::read_to_string(document.path()).unwrap();
+        assert_eq!(contents, "name = \"new\"\nvisits = 0\n");
+        assert_eq!(document.load().unwrap(), created);
+    }
+
+    #[test]
+    fn document_read_only_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("profile.json");
+        crate::save(&path, Profile::default()).unwrap();
+
+        let document: Document<Profile> = Document::new(&path).read_only(true);
+        assert!(document.clone().is_read_only());
+        assert_eq!(document.load().unwrap(), Profile::default());
+
+        let profile = Profile {
+            name: "replica".to_string(),
+            visits: 1,
+        };
+        assert!(matches!(document.save(&profile), Err(StorageError::ReadOnly(p)) if p == path));
+        assert!(matches!(
+            document.update(|profile| profile.visits += 1),
+            Err(StorageError::ReadOnly(_))
+        ));
+        assert!(matches!(document.delete(), Err(StorageError::ReadOnly(_))));
+        assert_eq!(document.load().unwrap(), Profile::default());
+
+        document.read_only(false).save(&profile).unwrap();
+        assert_eq!(crate::load::<_, Profile>(&path).unwrap(), profile);
+    }
+}
@@ -0,0 +1,171 @@
+use std::path::Path;
+
+use aes_gcm::{
+    Aes256Gcm, Key, KeyInit,
+    aead::{Aead, Generate, Nonce},
+};
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{SaveOptions, StorageError, parse_json, write_atomic, write_json};
+
+/// The length of the random nonce stored in front of the ciphertext.
+const NONCE_LEN: usize = 12;
+
+/// Synchronously saves data to an AES-256-GCM encrypted JSON file.
+///
+/// The data is serialized to JSON, encrypted with `key`, and written as a fresh random 96-bit
+/// nonce followed by the ciphertext and its authentication tag, using the same
+/// temporary-file-and-rename strategy as [`save`](crate::save). A new nonce is generated for
+/// every write, so the same key can safely be reused across saves.
+///
+/// The caller is responsible for key management: generating keys from a secure random source,
+/// storing them outside the encrypted files, and rotating them. Losing the key makes the data
+/// unrecoverable.
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+/// * `key` - The 256-bit encryption key
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, nonce and tag included, if the operation
+///   succeeds
+/// * `Err(StorageError)` - If file creation, encryption or writing fails, or if serialization
+///   fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save_encrypted, load_encrypted};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// let key = [7u8; 32];
+///
+/// save_encrypted(temp_file.path(), "secret", &key).unwrap();
+/// let loaded: String = load_encrypted(temp_file.path(), &key).unwrap();
+/// assert_eq!(loaded, "secret");
+/// ```
+pub fn save_encrypted<P, T>(path: P, data: T, key: &[u8; 32]) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    let mut plaintext = Vec::new();
+    write_json(&mut plaintext, &data, &SaveOptions::default())?;
+
+    let nonce = Nonce::<Aes256Gcm>::generate();
+    let ciphertext = cipher(key)
+        .encrypt(&nonce, plaintext.as_slice())
+        .map_err(|err| StorageError::Encode(err.into()))?;
+
+    write_atomic(path.as_ref(), |writer| {
+        writer.write_all(&nonce)?;
+        writer.write_all(&ciphertext)?;
+
+        Ok(nonce.len() + ciphertext.len())
+    })
+}
+
+/// Synchronously loads data from a file written by [`save_encrypted`].
+///
+/// The authentication tag is verified before anything is deserialized, so a file that was
+/// tampered with, truncated, or encrypted under a different key is rejected as a whole.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+/// * `key` - The 256-bit key the file was encrypted with
+///
+/// # Returns
+///
+/// * `Ok(T)` - The decrypted and deserialized data
+/// * `Err(StorageError)` - [`StorageError::DecryptionFailed`] if the key is wrong or the file
+///   has been modified, or if file reading or deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{StorageError, save_encrypted, load_encrypted};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save_encrypted(temp_file.path(), 42, &[1u8; 32]).unwrap();
+///
+/// let result = load_encrypted::<_, i32>(temp_file.path(), &[2u8; 32]);
+/// assert!(matches!(result, Err(StorageError::DecryptionFailed)));
+/// ```
+pub fn load_encrypted<P, T>(path: P, key: &[u8; 32]) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    let contents = std::fs::read(path)?;
+    if contents.len() < NONCE_LEN {
+        return Err(StorageError::DecryptionFailed);
+    }
+
+    let (nonce, ciphertext) = contents.split_at(NONCE_LEN);
+    let nonce = Nonce::<Aes256Gcm>::try_from(nonce).map_err(|_| StorageError::DecryptionFailed)?;
+    let plaintext = cipher(key)
+        .decrypt(&nonce, ciphertext)
+        .map_err(|_| StorageError::DecryptionFailed)?;
+
+    parse_json(&plaintext)
+}
+
+fn cipher(key: &[u8; 32]) -> Aes256Gcm {
+    Aes256Gcm::new(&Key::<Aes256Gcm>::from(*key))
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::NamedTempFile;
+
+    use super::*;
+
+    const KEY: [u8; 32] = [0x42; 32];
+
+    #[test]
+    fn save_encrypted_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        let written = save_encrypted(path, "top secret", &KEY).unwrap();
+        let first = std::fs::read(path).unwrap();
+        assert_eq!(written, first.len());
+        save_encrypted(path, "top secret", &KEY).unwrap();
+        let second = std::fs::read(path).unwrap();
+
+        assert!(!first.windows(6).any(|window| window == b"secret"));
+        // A fresh nonce per write means identical data never produces identical files.
+        assert_ne!(first[..NONCE_LEN], second[..NONCE_LEN]);
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn load_encrypted_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        save_encrypted(path, vec![1, 2, 3], &KEY).unwrap();
+        let loaded: Vec<i32> = load_encrypted(path, &KEY).unwrap();
+        assert_eq!(loaded, vec![1, 2, 3]);
+
+        let wrong_key = load_encrypted::<_, Vec<i32>>(path, &[0; 32]);
+        assert!(matches!(wrong_key, Err(StorageError::DecryptionFailed)));
+
+        let mut contents = std::fs::read(path).unwrap();
+        let last = contents.len() - 1;
+        contents[last] ^= 0x01;
+        std::fs::write(path, &contents).unwrap();
+        let tampered = load_encrypted::<_, Vec<i32>>(path, &KEY);
+        assert!(matches!(tampered, Err(StorageError::DecryptionFailed)));
+
+        std::fs::write(path, b"short").unwrap();
+        let truncated = load_encrypted::<_, Vec<i32>>(path, &KEY);
+        assert!(matches!(truncated, Err(StorageError::DecryptionFailed)));
+    }
+}
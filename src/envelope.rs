@@ -0,0 +1,197 @@
+use std::{path::Path, time::SystemTime};
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::{Clock, StorageError, SystemClock, load, save};
+
+/// Provenance information stored next to the data by [`save_envelope`].
+///
+/// `created_at` is written as an RFC 3339 timestamp in UTC with millisecond precision, for
+/// example `2024-05-01T12:30:00.250Z`; `app_version` and `schema` are free-form strings chosen
+/// by the application.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Metadata {
+    /// When the file was written.
+    #[serde(with = "rfc3339")]
+    pub created_at: SystemTime,
+    /// The version of the application that wrote the file.
+    pub app_version: String,
+    /// An identifier of the shape of the data, such as a schema name or version.
+    pub schema: String,
+}
+
+impl Metadata {
+    /// Creates metadata stamped with the current time.
+    pub fn new(app_version: impl Into<String>, schema: impl Into<String>) -> Self {
+        Self::with_clock(app_version, schema, &SystemClock)
+    }
+
+    /// Creates metadata stamped with the current time of `clock`.
+    pub fn with_clock<C: Clock + ?Sized>(
+        app_version: impl Into<String>,
+        schema: impl Into<String>,
+        clock: &C,
+    ) -> Self {
+        Self {
+            created_at: clock.now(),
+            app_version: app_version.into(),
+            schema: schema.into(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct EnvelopeRef<'a, T> {
+    meta: &'a Metadata,
+    data: &'a T,
+}
+
+#[derive(Deserialize)]
+struct Envelope<T> {
+    meta: Metadata,
+    data: T,
+}
+
+/// Synchronously saves data to a JSON file together with provenance metadata.
+///
+/// The file contains a single object, `{"meta": {...}, "data": ...}`, so it stays readable by
+/// other JSON tools; the payload can be recovered with [`load_envelope`].
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+/// * `meta` - The metadata to store alongside the data
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If file creation or writing fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{Metadata, save_envelope, load_envelope};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save_envelope(temp_file.path(), &vec![1, 2], Metadata::new("1.4.0", "numbers/v1")).unwrap();
+///
+/// let (meta, data): (Metadata, Vec<i32>) = load_envelope(temp_file.path()).unwrap();
+/// assert_eq!(meta.app_version, "1.4.0");
+/// assert_eq!(data, vec![1, 2]);
+/// ```
+pub fn save_envelope<P, T>(path: P, data: &T, meta: Metadata) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    save(path, EnvelopeRef { meta: &meta, data })
+}
+
+/// Synchronously loads data and its metadata from a file written by [`save_envelope`].
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok((Metadata, T))` - The stored metadata and the deserialized data
+/// * `Err(StorageError)` - If file reading fails, or if the file is not an envelope or its
+///   data does not match `T`
+///
+/// # Example
+///
+/// ```
+/// use std::time::SystemTime;
+/// use storage_service::{Metadata, save_envelope, load_envelope};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save_envelope(temp_file.path(), &"payload", Metadata::new("2.0.0", "text")).unwrap();
+///
+/// let (meta, _): (Metadata, String) = load_envelope(temp_file.path()).unwrap();
+/// assert!(meta.created_at <= SystemTime::now());
+/// ```
+pub fn load_envelope<P, T>(path: P) -> Result<(Metadata, T), StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    let Envelope { meta, data } = load(path)?;
+
+    Ok((meta, data))
+}
+
+/// (De)serializes a [`SystemTime`] as an RFC 3339 string.
+mod rfc3339 {
+    use std::time::SystemTime;
+
+    use serde::{Deserialize, Deserializer, Serializer, de::Error as _};
+
+    pub fn serialize<S: Serializer>(time: &SystemTime, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&humantime::format_rfc3339_millis(*time))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<SystemTime, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        humantime::parse_rfc3339(&text).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use tempfile::NamedTempFile;
+
+    use super::*;
+
+    #[test]
+    fn save_envelope_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        let clock = crate::ManualClock::new(UNIX_EPOCH + Duration::from_millis(1_700_000_000_250));
+        let meta = Metadata::with_clock("0.3.1", "settings/v2", &clock);
+        save_envelope(path, &[true], meta).unwrap();
+
+        let raw: serde_json::Value = load(path).unwrap();
+        assert_eq!(
+            raw,
+            serde_json::json!({
+                "meta": {
+                    "created_at": "2023-11-14T22:13:20.250Z",
+                    "app_version": "0.3.1",
+                    "schema": "settings/v2",
+                },
+                "data": [true],
+            })
+        );
+    }
+
+    #[test]
+    fn load_envelope_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        let meta = Metadata {
+            created_at: UNIX_EPOCH + Duration::from_secs(86_400),
+            ..Metadata::new("1.0.0", "counter")
+        };
+        save_envelope(path, &42u32, meta.clone()).unwrap();
+
+        let (loaded_meta, data): (Metadata, u32) = load_envelope(path).unwrap();
+        assert_eq!(loaded_meta, meta);
+        assert_eq!(data, 42);
+
+        std::fs::write(
+            path,
+            r#"{"meta":{"created_at":"yesterday","app_version":"","schema":""},"data":1}"#,
+        )
+        .unwrap();
+        let invalid = load_envelope::<_, u32>(path);
+        assert!(matches!(invalid, Err(StorageError::Parse { .. })));
+    }
+}
@@ -0,0 +1,324 @@
+use std::marker::PhantomData;
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use super::Format;
+use crate::StorageError;
+
+/// A binary [`Format`] that can be wrapped in a [`BinaryEnvelope`].
+pub trait BinaryFormat: Format {
+    /// The number written to envelope headers to identify the format.
+    ///
+    /// Ids below 256 are reserved for the formats of this crate: 1 for
+    /// [`Bincode`](crate::Bincode), 2 for [`MsgPack`](crate::MsgPack) and 3 for
+    /// [`Cbor`](crate::Cbor).
+    const FORMAT_ID: u16;
+
+    /// Encodes `data` as the payload of an envelope, without any header.
+    fn encode<T: Serialize>(data: &T) -> Result<Vec<u8>, StorageError>;
+
+    /// Decodes a payload written by [`encode`](BinaryFormat::encode).
+    fn decode<T: DeserializeOwned>(payload: &[u8]) -> Result<T, StorageError>;
+}
+
+/// The identity and schema version of a type stored in a [`BinaryEnvelope`].
+///
+/// Implement this for each struct saved in a binary format, and bump [`VERSION`] whenever
+/// the struct changes in a way that makes old files unreadable or, worse, misread.
+///
+/// [`VERSION`]: Versioned::VERSION
+pub trait Versioned {
+    /// The bytes that files of this type start with.
+    ///
+    /// Override this to tell the files of different types apart, so that loading one type's
+    /// file as another fails even when both are at the same version.
+    const MAGIC: [u8; 4] = *b"SSBE";
+
+    /// The schema version of the type.
+    ///
+    /// Start at 1: version 0 is what [`Bincode`](crate::Bincode) and
+    /// [`MsgPack`](crate::MsgPack) record when used without an envelope.
+    const VERSION: u16;
+}
+
+/// The identity recorded by binary formats that write the envelope header on their own, for
+/// data saved without a [`Versioned`] type.
+#[cfg(any(feature = "bincode", feature = "msgpack"))]
+pub(super) struct Unversioned;
+
+#[cfg(any(feature = "bincode", feature = "msgpack"))]
+impl Versioned for Unversioned {
+    const VERSION: u16 = 0;
+}
+
+/// A binary format whose files carry a header naming the format and the schema version.
+///
+/// Every file starts with an 8-byte header: the [`Versioned::MAGIC`] of `V`, then the
+/// [`BinaryFormat::FORMAT_ID`] of `F` and the [`Versioned::VERSION`] of `V`, both as big-endian
+/// `u16`s, followed by the payload encoded with `F`. Loading checks the header before decoding,
+/// so a file written by an older or newer version of a struct fails with
+/// [`StorageError::VersionMismatch`] instead of decoding to garbage, and a file that is not an
+/// envelope of this type and format fails with [`StorageError::Decode`]. To migrate, load
+/// through an envelope of the old version and save through the new one.
+///
+/// `V` is usually the stored type itself. Use the envelope with [`save_as`](crate::save_as)
+/// and [`load_as`](crate::load_as) or their async variants.
+///
+/// [`Bincode`](crate::Bincode) and [`MsgPack`](crate::MsgPack) write this same header on their
+/// own, with the default magic and version 0. The envelope replaces that header rather than
+/// adding a second one, so a file saved as a [`Versioned`] type fails to load with the plain
+/// format, and the other way round, with [`StorageError::VersionMismatch`].
+///
+/// # Example
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use storage_service::{BinaryEnvelope, StorageError, Versioned, load_as, save_as};
+/// # #[cfg(feature = "msgpack")]
+/// use storage_service::MsgPack;
+/// use tempfile::NamedTempFile;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct State {
+///     counter: u64,
+/// }
+///
+/// impl Versioned for State {
+///     const MAGIC: [u8; 4] = *b"STAT";
+///     const VERSION: u16 = 2;
+/// }
+///
+/// struct StateV1;
+///
+/// impl Versioned for StateV1 {
+///     const MAGIC: [u8; 4] = *b"STAT";
+///     const VERSION: u16 = 1;
+/// }
+///
+/// # #[cfg(feature = "msgpack")] {
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save_as::<BinaryEnvelope<MsgPack, State>, _, _>(temp_file.path(), State { counter: 7 })
+///     .unwrap();
+///
+/// let state: State = load_as::<BinaryEnvelope<MsgPack, State>, _, _>(temp_file.path()).unwrap();
+/// assert_eq!(state.counter, 7);
+///
+/// let old = load_as::<BinaryEnvelope<MsgPack, StateV1>, _, State>(temp_file.path());
+/// assert!(matches!(old, Err(StorageError::VersionMismatch { expected: 1, found: 2 })));
+/// # }
+/// ```
+pub struct BinaryEnvelope<F, V>(PhantomData<fn() -> (F, V)>);
+
+/// The length of the envelope header.
+const HEADER_LEN: usize = 8;
+
+impl<F: BinaryFormat, V: Versioned> Format for BinaryEnvelope<F, V> {
+    const EXTENSION: Option<&'static str> = F::EXTENSION;
+
+    fn serialize<T: Serialize>(data: &T) -> Result<Vec<u8>, StorageError> {
+        let payload = F::encode(data)?;
+
+        let mut bytes = Vec::with_capacity(HEADER_LEN + payload.len());
+        bytes.extend_from_slice(&V::MAGIC);
+        bytes.extend_from_slice(&F::FORMAT_ID.to_be_bytes());
+        bytes.extend_from_slice(&V::VERSION.to_be_bytes());
+        bytes.extend_from_slice(&payload);
+
+        Ok(bytes)
+    }
+
+    fn deserialize<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, StorageError> {
+        let Some((header, payload)) = bytes.split_at_checked(HEADER_LEN) else {
+            return Err(StorageError::Decode(
+                "file is too short for an envelope".into(),
+            ));
+        };
+        if header[..4] != V::MAGIC {
+            return Err(StorageError::Decode(
+                format!(
+                    "expected magic \"{}\", found \"{}\"",
+                    V::MAGIC.escape_ascii(),
+                    header[..4].escape_ascii()
+                )
+                .into(),
+            ));
+        }
+
+        let format_id = u16::from_be_bytes([header[4], header[5]]);
+        if format_id != F::FORMAT_ID {
+            return Err(StorageError::Decode(
+                format!(
+                    "written in format {format_id}, expected format {}",
+                    F::FORMAT_ID
+                )
+                .into(),
+            ));
+        }
+
+        let version = u16::from_be_bytes([header[6], header[7]]);
+        if version != V::VERSION {
+            return Err(StorageError::VersionMismatch {
+                expected: V::VERSION,
+                found: version,
+            });
+        }
+
+        F::decode(payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+    use tempfile::NamedTempFile;
+
+    use super::*;
+    use crate::{Json, load_as, save_as};
+
+    /// JSON under a format id of its own, so the envelope can be tested without the binary
+    /// format features.
+    struct TestFormat;
+
+    impl Format for TestFormat {
+        fn serialize<T: Serialize>(data: &T) -> Result<Vec<u8>, StorageError> {
+            Json::serialize(data)
+        }
+
+        fn deserialize<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, StorageError> {
+            Json::deserialize(bytes)
+        }
+    }
+
+    impl BinaryFormat for TestFormat {
+        const FORMAT_ID: u16 = 0x8001;
+
+        fn encode<T: Serialize>(data: &T) -> Result<Vec<u8>, StorageError> {
+            Json::serialize(data)
+        }
+
+        fn decode<T: DeserializeOwned>(payload: &[u8]) -> Result<T, StorageError> {
+            Json::deserialize(payload)
+        }
+    }
+
+    struct OtherFormat;
+
+    impl Format for OtherFormat {
+        fn serialize<T: Serialize>(data: &T) -> Result<Vec<u8>, StorageError> {
+            Json::serialize(data)
+        }
+
+        fn deserialize<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, StorageError> {
+            Json::deserialize(bytes)
+        }
+    }
+
+    impl BinaryFormat for OtherFormat {
+        const FORMAT_ID: u16 = 0x8002;
+
+        fn encode<T: Serialize>(data: &T) -> Result<Vec<u8>, StorageError> {
+            Json::serialize(data)
+        }
+
+        fn decode<T: DeserializeOwned>(payload: &[u8]) -> Result<T, StorageError> {
+            Json::deserialize(payload)
+        }
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Record {
+        id: u32,
+    }
+
+    impl Versioned for Record {
+        const VERSION: u16 = 3;
+    }
+
+    struct RecordV4;
+
+    impl Versioned for RecordV4 {
+        const VERSION: u16 = 4;
+    }
+
+    struct Other;
+
+    impl Versioned for Other {
+        const MAGIC: [u8; 4] = *b"OTHR";
+        const VERSION: u16 = 3;
+    }
+
+    #[test]
+    fn binary_envelope_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        save_as::<BinaryEnvelope<TestFormat, Record>, _, _>(path, Record { id: 9 }).unwrap();
+        let bytes = std::fs::read(path).unwrap();
+        assert_eq!(&bytes[..HEADER_LEN], b"SSBE\x80\x01\x00\x03");
+        assert_eq!(&bytes[HEADER_LEN..], br#"{"id":9}"#);
+
+        let loaded: Record = load_as::<BinaryEnvelope<TestFormat, Record>, _, _>(path).unwrap();
+        assert_eq!(loaded, Record { id: 9 });
+    }
+
+    #[test]
+    fn binary_envelope_mismatch_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+        save_as::<BinaryEnvelope<TestFormat, Record>, _, _>(path, Record { id: 1 }).unwrap();
+
+        let newer = load_as::<BinaryEnvelope<TestFormat, RecordV4>, _, Record>(path);
+        assert!(matches!(
+            newer,
+            Err(StorageError::VersionMismatch {
+                expected: 4,
+                found: 3
+            })
+        ));
+
+        let other_type = load_as::<BinaryEnvelope<TestFormat, Other>, _, Record>(path);
+        assert!(matches!(other_type, Err(StorageError::Decode(_))));
+        let other_format = load_as::<BinaryEnvelope<OtherFormat, Record>, _, Record>(path);
+        assert!(matches!(other_format, Err(StorageError::Decode(_))));
+
+        std::fs::write(path, b"SSBE").unwrap();
+        let truncated = load_as::<BinaryEnvelope<TestFormat, Record>, _, Record>(path);
+        assert!(matches!(truncated, Err(StorageError::Decode(_))));
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn binary_envelope_bincode_test() {
+        use crate::Bincode;
+
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        save_as::<BinaryEnvelope<Bincode, Record>, _, _>(path, Record { id: 5 }).unwrap();
+        let bytes = std::fs::read(path).unwrap();
+        assert_eq!(&bytes[..HEADER_LEN], b"SSBE\x00\x01\x00\x03");
+        assert_eq!(
+            Bincode::decode::<Record>(&bytes[HEADER_LEN..]).unwrap().id,
+            5
+        );
+
+        let loaded: Record = load_as::<BinaryEnvelope<Bincode, Record>, _, _>(path).unwrap();
+        assert_eq!(loaded.id, 5);
+        assert!(matches!(
+            load_as::<Bincode, _, Record>(path),
+            Err(StorageError::VersionMismatch {
+                expected: 0,
+                found: 3
+            })
+        ));
+
+        save_as::<Bincode, _, _>(path, Record { id: 5 }).unwrap();
+        assert!(matches!(
+            load_as::<BinaryEnvelope<Bincode, Record>, _, Record>(path),
+            Err(StorageError::VersionMismatch {
+                expected: 3,
+                found: 0
+            })
+        ));
+    }
+}
@@ -0,0 +1,342 @@
+use std::path::Path;
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use super::{BinaryEnvelope, BinaryFormat, Format, binary_envelope::Unversioned, load_as, save_as};
+#[cfg(feature = "async")]
+use super::{load_as_async, save_as_async};
+use crate::StorageError;
+
+/// The bincode format, backed by `bincode` 2 with its standard configuration.
+///
+/// Bincode is a fast, compact binary encoding, but it is not self-describing: the file holds
+/// only the field values, so it must be loaded into exactly the type it was saved from. Adding,
+/// removing or reordering fields, or changing their types, makes existing files unreadable or,
+/// worse, silently misread. Use it for internal state that is rewritten by the same build, and a
+/// self-describing format such as [`Json`](crate::Json) or [`MsgPack`](crate::MsgPack) for
+/// data that must survive schema changes. To at least detect them, save through a
+/// `BinaryEnvelope<Bincode, T>`, which records the schema version of `T` in the file.
+///
+/// Files start with the 8-byte header described at [`BinaryEnvelope`], with the magic bytes
+/// `SSBE`, format id 1 and version 0, so loading a file that was not written by this format
+/// fails with [`StorageError::Decode`] instead of producing garbage, as does a file with
+/// trailing bytes left over after decoding. A file saved through an envelope, which records a
+/// schema version, fails with [`StorageError::VersionMismatch`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Bincode;
+
+impl Format for Bincode {
+    const EXTENSION: Option<&'static str> = Some("bincode");
+
+    fn serialize<T: Serialize>(data: &T) -> Result<Vec<u8>, StorageError> {
+        BinaryEnvelope::<Self, Unversioned>::serialize(data)
+    }
+
+    fn deserialize<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, StorageError> {
+        BinaryEnvelope::<Self, Unversioned>::deserialize(bytes)
+    }
+}
+
+impl BinaryFormat for Bincode {
+    const FORMAT_ID: u16 = 1;
+
+    fn encode<T: Serialize>(data: &T) -> Result<Vec<u8>, StorageError> {
+        ::bincode::serde::encode_to_vec(data, ::bincode::config::standard())
+            .map_err(|err| StorageError::Encode(err.into()))
+    }
+
+    fn decode<T: DeserializeOwned>(payload: &[u8]) -> Result<T, StorageError> {
+        let (data, read) =
+            ::bincode::serde::decode_from_slice(payload, ::bincode::config::standard())
+                .map_err(|err| StorageError::Decode(err.into()))?;
+        if read != payload.len() {
+            return Err(StorageError::Decode(
+                format!("{} trailing bytes after bincode data", payload.len() - read).into(),
+            ));
+        }
+
+        Ok(data)
+    }
+}
+
+/// Synchronously saves data to a bincode file.
+///
+/// The bincode counterpart of [`save`](crate::save). See [`Bincode`] for the compatibility
+/// constraints of the format.
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If file creation or writing fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use serde::{Serialize, Deserialize};
+/// use storage_service::{save_bincode, load_bincode};
+/// use tempfile::NamedTempFile;
+///
+/// #[derive(Serialize, Deserialize, Debug, PartialEq)]
+/// struct Player {
+///     level: u32,
+///     position: (f32, f32),
+/// }
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// let player = Player { level: 12, position: (1.5, -3.0) };
+///
+/// save_bincode(temp_file.path(), &player).unwrap();
+/// let loaded: Player = load_bincode(temp_file.path()).unwrap();
+/// assert_eq!(loaded, player);
+/// ```
+pub fn save_bincode<P, T>(path: P, data: T) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    save_as::<Bincode, _, _>(path, data)
+}
+
+/// Asynchronously saves data to a bincode file.
+///
+/// The bincode counterpart of [`save_async`](crate::save_async). See [`Bincode`] for the
+/// compatibility constraints of the format.
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`, `Send`, and `'static`)
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If file creation, writing fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::save_bincode_async;
+/// use tempfile::NamedTempFile;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let temp_file = NamedTempFile::new().unwrap();
+///     save_bincode_async(temp_file.path(), vec![1u64, 2, 3]).await.unwrap();
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub async fn save_bincode_async<P, T>(path: P, data: T) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize + Send + 'static,
+{
+    save_as_async::<Bincode, _, _>(path, data).await
+}
+
+/// Synchronously loads data from a bincode file.
+///
+/// The bincode counterpart of [`load`](crate::load). `T` must be the exact type the file was
+/// saved from.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - If file reading fails, [`StorageError::VersionMismatch`] if the file
+///   was saved through a [`BinaryEnvelope`] with a schema version, or [`StorageError::Decode`]
+///   if it has no valid bincode header or cannot be decoded as `T`
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{StorageError, save, load_bincode};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save(temp_file.path(), [1, 2, 3]).unwrap();
+///
+/// let result = load_bincode::<_, Vec<u8>>(temp_file.path());
+/// assert!(matches!(result, Err(StorageError::Decode(_))));
+/// ```
+pub fn load_bincode<P, T>(path: P) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    load_as::<Bincode, _, _>(path)
+}
+
+/// Asynchronously loads data from a bincode file.
+///
+/// The bincode counterpart of [`load_async`](crate::load_async). `T` must be the exact type the
+/// file was saved from.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - If file reading fails, [`StorageError::VersionMismatch`] if the file
+///   was saved through a [`BinaryEnvelope`] with a schema version, or [`StorageError::Decode`]
+///   if it has no valid bincode header or cannot be decoded as `T`
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save_bincode_async, load_bincode_async};
+/// use tempfile::NamedTempFile;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let temp_file = NamedTempFile::new().unwrap();
+///     save_bincode_async(temp_file.path(), ("seed", 42u64)).await.unwrap();
+///
+///     let loaded: (String, u64) = load_bincode_async(temp_file.path()).await.unwrap();
+///     assert_eq!(loaded, ("seed".to_string(), 42));
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub async fn load_bincode_async<P, T>(path: P) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned + Send + 'static,
+{
+    load_as_async::<Bincode, _, _>(path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::collections::BTreeMap;
+    use tempfile::NamedTempFile;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+    struct SaveGame {
+        slot: u8,
+        player: Player,
+        world: BTreeMap<String, Vec<Chunk>>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+    struct Player {
+        name: String,
+        health: f32,
+        inventory: Vec<Option<u16>>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+    struct Chunk {
+        x: i32,
+        y: i32,
+        tiles: Vec<u8>,
+    }
+
+    fn save_game() -> SaveGame {
+        SaveGame {
+            slot: 2,
+            player: Player {
+                name: "hero".to_string(),
+                health: 87.5,
+                inventory: vec![Some(3), None, Some(1024)],
+            },
+            world: BTreeMap::from([(
+                "overworld".to_string(),
+                vec![
+                    Chunk {
+                        x: 0,
+                        y: -1,
+                        tiles: vec![1; 64],
+                    },
+                    Chunk {
+                        x: 1,
+                        y: -1,
+                        tiles: vec![2; 64],
+                    },
+                ],
+            )]),
+        }
+    }
+
+    #[test]
+    fn save_bincode_test() {
+        let bincode_file = NamedTempFile::new().unwrap();
+        let json_file = NamedTempFile::new().unwrap();
+
+        let bincode_size = save_bincode(bincode_file.path(), save_game()).unwrap();
+        let json_size = crate::save(json_file.path(), save_game()).unwrap();
+
+        let bytes = std::fs::read(bincode_file.path()).unwrap();
+        assert_eq!(bytes[..8], *b"SSBE\x00\x01\x00\x00");
+        assert!(bincode_size < json_size);
+    }
+
+    #[test]
+    fn load_bincode_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        save_bincode(path, save_game()).unwrap();
+        let loaded: SaveGame = load_bincode(path).unwrap();
+        assert_eq!(loaded, save_game());
+
+        let mut bytes = std::fs::read(path).unwrap();
+        bytes[7] = 1;
+        std::fs::write(path, &bytes).unwrap();
+        let result = load_bincode::<_, SaveGame>(path);
+        assert!(matches!(
+            result,
+            Err(StorageError::VersionMismatch {
+                expected: 0,
+                found: 1
+            })
+        ));
+
+        bytes[5] = 2;
+        std::fs::write(path, &bytes).unwrap();
+        let result = load_bincode::<_, SaveGame>(path);
+        assert!(matches!(result, Err(StorageError::Decode(_))));
+
+        save_bincode(path, (1u8, 2u8)).unwrap();
+        let result = load_bincode::<_, u8>(path);
+        assert!(matches!(result, Err(StorageError::Decode(_))));
+
+        std::fs::write(path, b"SS").unwrap();
+        let result = load_bincode::<_, u8>(path);
+        assert!(matches!(result, Err(StorageError::Decode(_))));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn save_bincode_async_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_path_buf();
+
+        save_bincode_async(&path, save_game()).await.unwrap();
+
+        let loaded: SaveGame = load_bincode(&path).unwrap();
+        assert_eq!(loaded, save_game());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn load_bincode_async_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_path_buf();
+
+        save_bincode(&path, save_game()).unwrap();
+
+        let loaded: SaveGame = load_bincode_async(&path).await.unwrap();
+        assert_eq!(loaded, save_game());
+    }
+}
@@ -0,0 +1,328 @@
+use std::path::Path;
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use super::{BinaryFormat, Format, load_as, save_as};
+#[cfg(feature = "async")]
+use super::{load_as_async, save_as_async};
+use crate::StorageError;
+
+/// The CBOR format (RFC 8949), backed by `ciborium`.
+///
+/// CBOR is a compact binary encoding common on embedded and IoT devices. Byte buffers marked
+/// with `#[serde(with = "serde_bytes")]` are stored as native CBOR byte strings rather than
+/// arrays of integers.
+///
+/// Output is deterministic as described in RFC 8949 §4.2: integers and lengths always use their
+/// shortest encoding, and the entries of every map, struct fields included, are sorted by the
+/// bytes of their encoded keys, which puts shorter text keys first. The same value therefore
+/// always encodes to the same bytes, even when it holds a `HashMap`, so snapshots can be
+/// compared byte for byte.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Cbor;
+
+impl Format for Cbor {
+    const EXTENSION: Option<&'static str> = Some("cbor");
+
+    fn serialize<T: Serialize>(data: &T) -> Result<Vec<u8>, StorageError> {
+        let mut value =
+            ciborium::Value::serialized(data).map_err(|err| StorageError::Encode(err.into()))?;
+        canonicalize(&mut value)?;
+
+        let mut bytes = Vec::new();
+        ciborium::into_writer(&value, &mut bytes)
+            .map_err(|err| StorageError::Encode(err.into()))?;
+
+        Ok(bytes)
+    }
+
+    fn deserialize<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, StorageError> {
+        ciborium::from_reader(bytes).map_err(|err| StorageError::Decode(err.into()))
+    }
+}
+
+impl BinaryFormat for Cbor {
+    const FORMAT_ID: u16 = 3;
+
+    fn encode<T: Serialize>(data: &T) -> Result<Vec<u8>, StorageError> {
+        Self::serialize(data)
+    }
+
+    fn decode<T: DeserializeOwned>(payload: &[u8]) -> Result<T, StorageError> {
+        Self::deserialize(payload)
+    }
+}
+
+/// Sorts the entries of every map in `value` by the bytes of their encoded keys.
+fn canonicalize(value: &mut ciborium::Value) -> Result<(), StorageError> {
+    match value {
+        ciborium::Value::Array(items) => {
+            for item in items {
+                canonicalize(item)?;
+            }
+        }
+        ciborium::Value::Map(entries) => {
+            let mut keyed = Vec::with_capacity(entries.len());
+            for (mut key, mut item) in entries.drain(..) {
+                canonicalize(&mut key)?;
+                canonicalize(&mut item)?;
+
+                let mut encoded = Vec::new();
+                ciborium::into_writer(&key, &mut encoded)
+                    .map_err(|err| StorageError::Encode(err.into()))?;
+                keyed.push((encoded, key, item));
+            }
+
+            keyed.sort_by(|a, b| a.0.cmp(&b.0));
+            entries.extend(keyed.into_iter().map(|(_, key, item)| (key, item)));
+        }
+        ciborium::Value::Tag(_, inner) => canonicalize(inner)?,
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Synchronously saves data to a CBOR file.
+///
+/// The CBOR counterpart of [`save`](crate::save).
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If file creation or writing fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save_cbor, load_cbor};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+///
+/// let written = save_cbor(temp_file.path(), (1u8, true)).unwrap();
+/// assert_eq!(written, 3);
+///
+/// let loaded: (u8, bool) = load_cbor(temp_file.path()).unwrap();
+/// assert_eq!(loaded, (1, true));
+/// ```
+pub fn save_cbor<P, T>(path: P, data: T) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    save_as::<Cbor, _, _>(path, data)
+}
+
+/// Asynchronously saves data to a CBOR file.
+///
+/// The CBOR counterpart of [`save_async`](crate::save_async).
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`, `Send`, and `'static`)
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If file creation, writing fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::save_cbor_async;
+/// use tempfile::NamedTempFile;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let temp_file = NamedTempFile::new().unwrap();
+///     save_cbor_async(temp_file.path(), vec![1u16, 2, 3]).await.unwrap();
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub async fn save_cbor_async<P, T>(path: P, data: T) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize + Send + 'static,
+{
+    save_as_async::<Cbor, _, _>(path, data).await
+}
+
+/// Synchronously loads data from a CBOR file.
+///
+/// The CBOR counterpart of [`load`](crate::load).
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - If file reading fails or if deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::load_cbor;
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// // A CBOR text string of length 2: "hi".
+/// std::fs::write(temp_file.path(), [0x62, b'h', b'i']).unwrap();
+///
+/// let loaded: String = load_cbor(temp_file.path()).unwrap();
+/// assert_eq!(loaded, "hi");
+/// ```
+pub fn load_cbor<P, T>(path: P) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    load_as::<Cbor, _, _>(path)
+}
+
+/// Asynchronously loads data from a CBOR file.
+///
+/// The CBOR counterpart of [`load_async`](crate::load_async).
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - If file reading fails or if deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save_cbor_async, load_cbor_async};
+/// use tempfile::NamedTempFile;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let temp_file = NamedTempFile::new().unwrap();
+///     save_cbor_async(temp_file.path(), -7i64).await.unwrap();
+///
+///     let loaded: i64 = load_cbor_async(temp_file.path()).await.unwrap();
+///     assert_eq!(loaded, -7);
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub async fn load_cbor_async<P, T>(path: P) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned + Send + 'static,
+{
+    load_as_async::<Cbor, _, _>(path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::collections::{BTreeMap, HashMap};
+    use tempfile::NamedTempFile;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+    struct Reading {
+        sensor: String,
+        #[serde(with = "serde_bytes")]
+        payload: Vec<u8>,
+        tags: BTreeMap<String, u32>,
+    }
+
+    fn reading() -> Reading {
+        Reading {
+            sensor: "t1".to_string(),
+            payload: vec![0xde, 0xad, 0xbe, 0xef],
+            tags: BTreeMap::from([("room".to_string(), 4), ("floor".to_string(), 2)]),
+        }
+    }
+
+    #[test]
+    fn save_cbor_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+
+        save_cbor(temp_file.path(), reading()).unwrap();
+        let bytes = std::fs::read(temp_file.path()).unwrap();
+
+        // The payload is a native byte string (major type 2, length 4).
+        assert!(
+            bytes
+                .windows(5)
+                .any(|w| w == [0x44, 0xde, 0xad, 0xbe, 0xef])
+        );
+
+        // Shorter keys sort first, so "room" precedes "floor" and "tags" precedes "sensor".
+        let position = |needle: &[u8]| bytes.windows(needle.len()).position(|w| w == needle);
+        assert!(position(b"room") < position(b"floor"));
+        assert!(position(b"tags") < position(b"sensor"));
+    }
+
+    #[test]
+    fn save_cbor_deterministic_test() {
+        let first = NamedTempFile::new().unwrap();
+        let second = NamedTempFile::new().unwrap();
+
+        let entries: Vec<_> = (0..64u32).map(|i| (format!("key-{i}"), i)).collect();
+        let forward: HashMap<_, _> = entries.iter().cloned().collect();
+        let backward: HashMap<_, _> = entries.iter().rev().cloned().collect();
+
+        save_cbor(first.path(), &forward).unwrap();
+        save_cbor(second.path(), &backward).unwrap();
+        assert_eq!(
+            std::fs::read(first.path()).unwrap(),
+            std::fs::read(second.path()).unwrap()
+        );
+
+        let loaded: HashMap<String, u32> = load_cbor(first.path()).unwrap();
+        assert_eq!(loaded, forward);
+    }
+
+    #[test]
+    fn load_cbor_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        save_cbor(path, reading()).unwrap();
+        let loaded: Reading = load_cbor(path).unwrap();
+        assert_eq!(loaded, reading());
+
+        std::fs::write(path, [0xff]).unwrap();
+        let result: Result<Reading, _> = load_cbor(path);
+        assert!(matches!(result, Err(StorageError::Decode(_))));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn save_cbor_async_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_path_buf();
+
+        save_cbor_async(&path, reading()).await.unwrap();
+
+        let loaded: Reading = load_cbor(&path).unwrap();
+        assert_eq!(loaded, reading());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn load_cbor_async_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_path_buf();
+
+        save_cbor(&path, reading()).unwrap();
+
+        let loaded: Reading = load_cbor_async(&path).await.unwrap();
+        assert_eq!(loaded, reading());
+    }
+}
@@ -0,0 +1,182 @@
+use std::path::Path;
+
+use serde::{Serialize, de::DeserializeOwned};
+
+#[cfg(feature = "async")]
+use super::load_as_async;
+use super::{Format, load_as};
+use crate::{StorageError, UTF8_BOM};
+
+/// Lenient JSON for hand-edited configuration, parsed with the `json5` crate.
+///
+/// On load, the JSON5 extensions that people tend to write into JSON files are accepted:
+/// `//` and `/* */` comments, trailing commas, unquoted object keys, single-quoted strings,
+/// and hexadecimal numbers. Data is always saved as strict JSON, exactly as by
+/// [`save`](crate::save), so files stay readable by every JSON tool. Parse errors are reported
+/// as [`StorageError::Decode`] wrapping a [`json5::Error`], whose message names the offending
+/// line and column.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json5;
+
+impl Format for Json5 {
+    const EXTENSION: Option<&'static str> = Some("json5");
+
+    fn serialize<T: Serialize>(data: &T) -> Result<Vec<u8>, StorageError> {
+        serde_json::to_vec(data).map_err(StorageError::from_serialize)
+    }
+
+    fn deserialize<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, StorageError> {
+        let bytes = bytes.strip_prefix(UTF8_BOM).unwrap_or(bytes);
+        let text = std::str::from_utf8(bytes).map_err(|err| StorageError::Decode(err.into()))?;
+
+        ::json5::from_str(text).map_err(|err| StorageError::Decode(err.into()))
+    }
+}
+
+/// Synchronously loads data from a JSON file, tolerating comments and other JSON5 syntax.
+///
+/// A lenient counterpart of [`load`](crate::load) for configuration files; see [`Json5`] for
+/// what is accepted. The strict [`load`](crate::load) is unaffected.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - If file reading fails or if deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use storage_service::load_json5;
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// std::fs::write(
+///     temp_file.path(),
+///     "{\n  // Listen on all interfaces.\n  host: '0.0.0.0',\n  port: 8080,\n}\n",
+/// )
+/// .unwrap();
+///
+/// let loaded: HashMap<String, serde_json::Value> = load_json5(temp_file.path()).unwrap();
+/// assert_eq!(loaded["port"], 8080);
+/// ```
+pub fn load_json5<P, T>(path: P) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    load_as::<Json5, _, _>(path)
+}
+
+/// Asynchronously loads data from a JSON file, tolerating comments and other JSON5 syntax.
+///
+/// The lenient counterpart of [`load_async`](crate::load_async); see [`Json5`] for what is
+/// accepted.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - If file reading fails or if deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::load_json5_async;
+/// use tempfile::NamedTempFile;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let temp_file = NamedTempFile::new().unwrap();
+///     std::fs::write(temp_file.path(), "[1, 2, 3,] /* trailing comma */").unwrap();
+///
+///     let loaded: Vec<u8> = load_json5_async(temp_file.path()).await.unwrap();
+///     assert_eq!(loaded, vec![1, 2, 3]);
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub async fn load_json5_async<P, T>(path: P) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned + Send + 'static,
+{
+    load_as_async::<Json5, _, _>(path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load, save_as};
+    use serde::Deserialize;
+    use tempfile::NamedTempFile;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+    struct Config {
+        name: String,
+        retries: u32,
+        tags: Vec<String>,
+    }
+
+    const LENIENT: &str = "\u{feff}{
+  // The service name.
+  name: 'api',
+  /* Retries before giving up. */
+  \"retries\": 0x3,
+  tags: [\"a\", \"b\",],
+}
+";
+
+    #[test]
+    fn load_json5_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        std::fs::write(path, LENIENT).unwrap();
+        let loaded: Config = load_json5(path).unwrap();
+        assert_eq!(
+            loaded,
+            Config {
+                name: "api".to_string(),
+                retries: 3,
+                tags: vec!["a".to_string(), "b".to_string()],
+            }
+        );
+        assert!(load::<_, Config>(path).is_err());
+
+        std::fs::write(path, "{ name: }").unwrap();
+        let result = load_json5::<_, Config>(path);
+        assert!(matches!(result, Err(StorageError::Decode(_))));
+    }
+
+    #[test]
+    fn save_as_json5_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+        let config = Config {
+            name: "api".to_string(),
+            retries: 1,
+            tags: vec![],
+        };
+
+        save_as::<Json5, _, _>(path, &config).unwrap();
+        assert_eq!(load::<_, Config>(path).unwrap(), config);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn load_json5_async_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_path_buf();
+
+        std::fs::write(&path, LENIENT).unwrap();
+        let loaded: Config = load_json5_async(&path).await.unwrap();
+        assert_eq!(loaded.retries, 3);
+    }
+}
@@ -0,0 +1,533 @@
+mod binary_envelope;
+#[cfg(feature = "bincode")]
+mod bincode;
+#[cfg(feature = "cbor")]
+mod cbor;
+#[cfg(feature = "json5")]
+mod json5;
+#[cfg(feature = "msgpack")]
+mod msgpack;
+#[cfg(feature = "toml")]
+mod toml;
+#[cfg(feature = "yaml")]
+mod yaml;
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+#[cfg(feature = "async")]
+use crate::write_atomic_async;
+use crate::{StorageError, parse_file, parse_json, write_atomic};
+
+pub use binary_envelope::{BinaryEnvelope, BinaryFormat, Versioned};
+
+#[cfg(feature = "bincode")]
+pub use bincode::{Bincode, load_bincode, save_bincode};
+#[cfg(all(feature = "bincode", feature = "async"))]
+pub use bincode::{load_bincode_async, save_bincode_async};
+#[cfg(feature = "cbor")]
+pub use cbor::{Cbor, load_cbor, save_cbor};
+#[cfg(all(feature = "cbor", feature = "async"))]
+pub use cbor::{load_cbor_async, save_cbor_async};
+#[cfg(all(feature = "json5", feature = "async"))]
+pub use json5::load_json5_async;
+#[cfg(feature = "json5")]
+pub use json5::{Json5, load_json5};
+#[cfg(feature = "msgpack")]
+pub use msgpack::{MsgPack, load_msgpack, save_msgpack};
+#[cfg(all(feature = "msgpack", feature = "async"))]
+pub use msgpack::{load_msgpack_async, save_msgpack_async};
+#[cfg(feature = "toml")]
+pub use toml::{Toml, load_toml, save_toml};
+#[cfg(all(feature = "toml", feature = "async"))]
+pub use toml::{load_toml_async, save_toml_async};
+#[cfg(feature = "yaml")]
+pub use yaml::{Yaml, load_yaml, save_yaml};
+#[cfg(all(feature = "yaml", feature = "async"))]
+pub use yaml::{load_yaml_async, save_yaml_async};
+
+/// A serialization format that data can be stored in.
+///
+/// The crate ships [`Json`], which is what [`save`](crate::save) and [`load`](crate::load) use.
+/// Downstream crates can implement this trait for their own formats (TOML, YAML, ...) and use
+/// them through [`save_as`] and [`load_as`]. Format-specific failures should be reported as
+/// [`StorageError::Encode`] and [`StorageError::Decode`].
+///
+/// # Example
+///
+/// ```
+/// use serde::{Serialize, de::DeserializeOwned};
+/// use storage_service::{Format, StorageError, save_as, load_as};
+/// use tempfile::NamedTempFile;
+///
+/// /// Stores data as JSON with a trailing newline.
+/// struct JsonLine;
+///
+/// impl Format for JsonLine {
+///     fn serialize<T: Serialize>(data: &T) -> Result<Vec<u8>, StorageError> {
+///         let mut bytes = serde_json::to_vec(data).map_err(|err| StorageError::Encode(err.into()))?;
+///         bytes.push(b'\n');
+///         Ok(bytes)
+///     }
+///
+///     fn deserialize<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, StorageError> {
+///         serde_json::from_slice(bytes).map_err(|err| StorageError::Decode(err.into()))
+///     }
+/// }
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save_as::<JsonLine, _, _>(temp_file.path(), 7).unwrap();
+/// let loaded: i32 = load_as::<JsonLine, _, _>(temp_file.path()).unwrap();
+/// assert_eq!(loaded, 7);
+/// ```
+pub trait Format {
+    /// Serializes `data` into the bytes that will be written to disk.
+    fn serialize<T: Serialize>(data: &T) -> Result<Vec<u8>, StorageError>;
+
+    /// Deserializes a value from the bytes read from disk.
+    fn deserialize<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, StorageError>;
+
+    /// Deserializes a value from the contents of the file at `path`.
+    ///
+    /// [`load_as`] and [`load_as_async`] call this rather than [`deserialize`](Format::deserialize),
+    /// so that a format can report errors together with the file they occurred in. The default
+    /// ignores `path`.
+    fn deserialize_file<T: DeserializeOwned>(path: &Path, bytes: &[u8]) -> Result<T, StorageError> {
+        let _ = path;
+        Self::deserialize(bytes)
+    }
+
+    /// The file extension conventionally used for this format, without the leading dot.
+    ///
+    /// [`save_ext_as`] appends it to paths that have no extension; formats that leave it as
+    /// `None` have their paths used unchanged.
+    const EXTENSION: Option<&'static str> = None;
+}
+
+/// The JSON format, backed by `serde_json`.
+///
+/// This is the default format of the crate: `save_as::<Json, _, _>` writes exactly the same
+/// bytes as [`save`](crate::save).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json;
+
+impl Format for Json {
+    const EXTENSION: Option<&'static str> = Some("json");
+
+    fn serialize<T: Serialize>(data: &T) -> Result<Vec<u8>, StorageError> {
+        serde_json::to_vec(data).map_err(StorageError::from_serialize)
+    }
+
+    fn deserialize<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, StorageError> {
+        parse_json(bytes)
+    }
+
+    fn deserialize_file<T: DeserializeOwned>(path: &Path, bytes: &[u8]) -> Result<T, StorageError> {
+        parse_file(path, bytes)
+    }
+}
+
+/// Synchronously saves data to a file in the given format.
+///
+/// This is the format-generic form of [`save`](crate::save), using the same
+/// temporary-file-and-rename strategy.
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If file creation or writing fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{Json, save_as};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save_as::<Json, _, _>(temp_file.path(), vec![1, 2, 3]).unwrap();
+/// ```
+pub fn save_as<F, P, T>(path: P, data: T) -> Result<usize, StorageError>
+where
+    F: Format,
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    let bytes = F::serialize(&data)?;
+
+    write_atomic(path.as_ref(), |writer| {
+        writer.write_all(&bytes)?;
+        Ok(bytes.len())
+    })
+}
+
+/// Asynchronously saves data to a file in the given format.
+///
+/// The async counterpart of [`save_as`]. Serialization is performed on a blocking task to avoid
+/// blocking the async runtime.
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`, `Send`, and `'static`)
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If file creation, writing fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{Json, save_as_async};
+/// use tempfile::NamedTempFile;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let temp_file = NamedTempFile::new().unwrap();
+///     save_as_async::<Json, _, _>(temp_file.path(), vec![1, 2, 3]).await.unwrap();
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub async fn save_as_async<F, P, T>(path: P, data: T) -> Result<usize, StorageError>
+where
+    F: Format + 'static,
+    P: AsRef<Path>,
+    T: Serialize + Send + 'static,
+{
+    write_atomic_async(path.as_ref(), move |writer| {
+        let bytes = F::serialize(&data)?;
+        writer.write_all(&bytes)?;
+        Ok(bytes.len())
+    })
+    .await
+}
+
+/// Synchronously saves data to a JSON file, making sure the path ends in `.json`.
+///
+/// The JSON form of [`save_ext_as`]: `save_ext("config", ...)` writes `config.json`, while
+/// `save_ext("config.toml", ...)` is rejected rather than producing a misleadingly named file.
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved, with or without the `.json` extension
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+///
+/// # Returns
+///
+/// * `Ok(PathBuf)` - The path the data was written to
+/// * `Err(StorageError)` - With an [`io::ErrorKind::InvalidInput`] error if the path has a
+///   different extension, if file creation or writing fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::save_ext;
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+///
+/// let written = save_ext(dir.path().join("config"), "value").unwrap();
+/// assert_eq!(written, dir.path().join("config.json"));
+/// assert!(save_ext(dir.path().join("config.yaml"), "value").is_err());
+/// ```
+pub fn save_ext<P, T>(path: P, data: T) -> Result<PathBuf, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    save_ext_as::<Json, _, _>(path, data)
+}
+
+/// Synchronously saves data to a file in the given format, enforcing the format's extension.
+///
+/// If `path` has no extension, [`Format::EXTENSION`] is appended; if it already has that
+/// extension (compared case-insensitively) it is used as is, and any other extension is an
+/// error, checked before anything is written. This keeps a storage directory
+/// self-describing, so that tools and extension-based helpers such as
+/// [`save_auto`](crate::save_auto) can tell formats apart.
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved, with or without the extension
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+///
+/// # Returns
+///
+/// * `Ok(PathBuf)` - The path the data was written to
+/// * `Err(StorageError)` - With an [`io::ErrorKind::InvalidInput`] error if the path has a
+///   different extension, if file creation or writing fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{Json, save_ext_as};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let written = save_ext_as::<Json, _, _>(dir.path().join("data.JSON"), [1, 2]).unwrap();
+/// assert_eq!(written, dir.path().join("data.JSON"));
+/// ```
+pub fn save_ext_as<F, P, T>(path: P, data: T) -> Result<PathBuf, StorageError>
+where
+    F: Format,
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    let path = path.as_ref();
+    let path = match F::EXTENSION {
+        Some(expected) => with_extension(path, expected)?,
+        None => path.to_path_buf(),
+    };
+    save_as::<F, _, _>(&path, data)?;
+
+    Ok(path)
+}
+
+/// Returns `path` with the `expected` extension, appending it if there is none.
+fn with_extension(path: &Path, expected: &str) -> Result<PathBuf, StorageError> {
+    match path.extension() {
+        None => {
+            let mut name = path.as_os_str().to_os_string();
+            name.push(".");
+            name.push(expected);
+            Ok(PathBuf::from(name))
+        }
+        Some(actual) if actual.eq_ignore_ascii_case(expected) => Ok(path.to_path_buf()),
+        Some(actual) => Err(StorageError::Io(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "{} has extension {:?}, expected {expected:?}",
+                path.display(),
+                actual.to_string_lossy()
+            ),
+        ))),
+    }
+}
+
+/// Synchronously loads data from a file in the given format.
+///
+/// This is the format-generic form of [`load`](crate::load).
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - If file reading fails or if deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{Json, save_as, load_as};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save_as::<Json, _, _>(temp_file.path(), "hello").unwrap();
+///
+/// let loaded: String = load_as::<Json, _, _>(temp_file.path()).unwrap();
+/// assert_eq!(loaded, "hello");
+/// ```
+pub fn load_as<F, P, T>(path: P) -> Result<T, StorageError>
+where
+    F: Format,
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    let path = path.as_ref();
+    let bytes = std::fs::read(path)?;
+
+    F::deserialize_file(path, &bytes)
+}
+
+/// Asynchronously loads data from a file in the given format.
+///
+/// The async counterpart of [`load_as`]. Deserialization is performed on a blocking task to
+/// avoid blocking the async runtime.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - If file reading fails or if deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{Json, save_as_async, load_as_async};
+/// use tempfile::NamedTempFile;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let temp_file = NamedTempFile::new().unwrap();
+///     save_as_async::<Json, _, _>(temp_file.path(), 42).await.unwrap();
+///
+///     let loaded: u8 = load_as_async::<Json, _, _>(temp_file.path()).await.unwrap();
+///     assert_eq!(loaded, 42);
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub async fn load_as_async<F, P, T>(path: P) -> Result<T, StorageError>
+where
+    F: Format + 'static,
+    P: AsRef<Path>,
+    T: DeserializeOwned + Send + 'static,
+{
+    let path = path.as_ref().to_path_buf();
+    let bytes = tokio::fs::read(&path).await?;
+
+    tokio::task::spawn_blocking(move || F::deserialize_file(&path, &bytes)).await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load, save};
+    use serde::Deserialize;
+    use tempfile::NamedTempFile;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+    struct TestData {
+        name: String,
+        value: i32,
+    }
+
+    /// A toy format that stores JSON reversed byte-for-byte, standing in for a downstream format.
+    struct Reversed;
+
+    impl Format for Reversed {
+        fn serialize<T: Serialize>(data: &T) -> Result<Vec<u8>, StorageError> {
+            let mut bytes = Json::serialize(data)?;
+            bytes.reverse();
+            Ok(bytes)
+        }
+
+        fn deserialize<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, StorageError> {
+            let mut bytes = bytes.to_vec();
+            bytes.reverse();
+            serde_json::from_slice(&bytes).map_err(|err| StorageError::Decode(err.into()))
+        }
+    }
+
+    #[test]
+    fn save_as_test() {
+        let json_file = NamedTempFile::new().unwrap();
+        let plain_file = NamedTempFile::new().unwrap();
+
+        let data = TestData {
+            name: "format".to_string(),
+            value: 5,
+        };
+        save_as::<Json, _, _>(json_file.path(), &data).unwrap();
+        save(plain_file.path(), &data).unwrap();
+
+        let json_bytes = std::fs::read(json_file.path()).unwrap();
+        let plain_bytes = std::fs::read(plain_file.path()).unwrap();
+        assert_eq!(json_bytes, plain_bytes);
+    }
+
+    #[test]
+    fn load_as_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        let data = TestData {
+            name: "custom".to_string(),
+            value: 11,
+        };
+        save_as::<Reversed, _, _>(path, &data).unwrap();
+
+        let loaded: TestData = load_as::<Reversed, _, _>(path).unwrap();
+        assert_eq!(loaded, data);
+
+        let as_json: Result<TestData, _> = load(path);
+        assert!(as_json.is_err());
+
+        std::fs::write(path, b"\xEF\xBB\xBF[1]").unwrap();
+        assert_eq!(load_as::<Json, _, Vec<u8>>(path).unwrap(), [1]);
+        assert_eq!(
+            Json::deserialize::<Vec<u8>>(b"\xEF\xBB\xBF[1]").unwrap(),
+            [1]
+        );
+
+        std::fs::write(path, b"").unwrap();
+        let result = load_as::<Json, _, TestData>(path);
+        assert!(matches!(result, Err(StorageError::Empty(empty)) if empty == path));
+
+        std::fs::write(path, "{ \"name\": ").unwrap();
+        let result = load_as::<Json, _, TestData>(path);
+        assert!(matches!(result, Err(StorageError::Parse { path: failed, .. }) if failed == path));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn save_as_async_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_path_buf();
+
+        let data = TestData {
+            name: "async_format".to_string(),
+            value: 8,
+        };
+        save_as_async::<Reversed, _, _>(&path, data.clone())
+            .await
+            .unwrap();
+
+        let loaded: TestData = load_as::<Reversed, _, _>(&path).unwrap();
+        assert_eq!(loaded, data);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn load_as_async_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_path_buf();
+        std::fs::write(&path, "not json").unwrap();
+
+        let result: Result<TestData, _> = load_as_async::<Reversed, _, _>(&path).await;
+        assert!(matches!(result, Err(StorageError::Decode(_))));
+    }
+
+    #[test]
+    fn save_ext_test() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let appended = save_ext(dir.path().join("settings"), 1).unwrap();
+        assert_eq!(appended, dir.path().join("settings.json"));
+        assert_eq!(load::<_, i32>(&appended).unwrap(), 1);
+
+        let kept = save_ext(dir.path().join("settings.json"), 2).unwrap();
+        assert_eq!(kept, appended);
+
+        let conflict = save_ext(dir.path().join("settings.toml"), 3);
+        assert!(matches!(
+            conflict,
+            Err(StorageError::Io(err)) if err.kind() == io::ErrorKind::InvalidInput
+        ));
+        assert!(!dir.path().join("settings.toml").exists());
+    }
+
+    #[test]
+    fn save_ext_as_test() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let unchecked = save_ext_as::<Reversed, _, _>(dir.path().join("data.rev"), 1).unwrap();
+        assert_eq!(unchecked, dir.path().join("data.rev"));
+
+        let upper = save_ext_as::<Json, _, _>(dir.path().join("DATA.JSON"), 2).unwrap();
+        assert_eq!(upper, dir.path().join("DATA.JSON"));
+    }
+}
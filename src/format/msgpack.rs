@@ -0,0 +1,284 @@
+use std::path::Path;
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use super::{BinaryEnvelope, BinaryFormat, Format, binary_envelope::Unversioned, load_as, save_as};
+#[cfg(feature = "async")]
+use super::{load_as_async, save_as_async};
+use crate::StorageError;
+
+/// The MessagePack format, backed by `rmp-serde`.
+///
+/// MessagePack is a compact binary encoding that, unlike JSON, can represent maps with
+/// non-string keys. Structs are encoded as maps keyed by field name, so files stay readable
+/// after fields are added or reordered.
+///
+/// Files start with the 8-byte header described at [`BinaryEnvelope`], with the magic bytes
+/// `SSBE`, format id 2 and version 0, so loading a file that was not written by this format
+/// fails with [`StorageError::Decode`]. For changes that old files cannot survive, save through
+/// a `BinaryEnvelope<MsgPack, T>` instead, which records the schema version of `T` in the same
+/// header; loading such a file as plain MessagePack fails with
+/// [`StorageError::VersionMismatch`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MsgPack;
+
+impl Format for MsgPack {
+    const EXTENSION: Option<&'static str> = Some("msgpack");
+
+    fn serialize<T: Serialize>(data: &T) -> Result<Vec<u8>, StorageError> {
+        BinaryEnvelope::<Self, Unversioned>::serialize(data)
+    }
+
+    fn deserialize<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, StorageError> {
+        BinaryEnvelope::<Self, Unversioned>::deserialize(bytes)
+    }
+}
+
+impl BinaryFormat for MsgPack {
+    const FORMAT_ID: u16 = 2;
+
+    fn encode<T: Serialize>(data: &T) -> Result<Vec<u8>, StorageError> {
+        rmp_serde::to_vec_named(data).map_err(|err| StorageError::Encode(err.into()))
+    }
+
+    fn decode<T: DeserializeOwned>(payload: &[u8]) -> Result<T, StorageError> {
+        rmp_serde::from_slice(payload).map_err(|err| StorageError::Decode(err.into()))
+    }
+}
+
+/// Synchronously saves data to a MessagePack file.
+///
+/// The MessagePack counterpart of [`save`](crate::save).
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If file creation or writing fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use storage_service::{save_msgpack, load_msgpack};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// let names = HashMap::from([(1u32, "one".to_string()), (2, "two".to_string())]);
+///
+/// save_msgpack(temp_file.path(), &names).unwrap();
+/// let loaded: HashMap<u32, String> = load_msgpack(temp_file.path()).unwrap();
+/// assert_eq!(loaded, names);
+/// ```
+pub fn save_msgpack<P, T>(path: P, data: T) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    save_as::<MsgPack, _, _>(path, data)
+}
+
+/// Asynchronously saves data to a MessagePack file.
+///
+/// The MessagePack counterpart of [`save_async`](crate::save_async).
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`, `Send`, and `'static`)
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If file creation, writing fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::save_msgpack_async;
+/// use tempfile::NamedTempFile;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let temp_file = NamedTempFile::new().unwrap();
+///     save_msgpack_async(temp_file.path(), vec![1u64, 2, 3]).await.unwrap();
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub async fn save_msgpack_async<P, T>(path: P, data: T) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize + Send + 'static,
+{
+    save_as_async::<MsgPack, _, _>(path, data).await
+}
+
+/// Synchronously loads data from a MessagePack file.
+///
+/// The MessagePack counterpart of [`load`](crate::load).
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - If file reading fails, [`StorageError::VersionMismatch`] if the file
+///   was saved through a [`BinaryEnvelope`] with a schema version, or if deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save_msgpack, load_msgpack};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save_msgpack(temp_file.path(), (1u8, "one")).unwrap();
+///
+/// let loaded: (u8, String) = load_msgpack(temp_file.path()).unwrap();
+/// assert_eq!(loaded, (1, "one".to_string()));
+/// ```
+pub fn load_msgpack<P, T>(path: P) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    load_as::<MsgPack, _, _>(path)
+}
+
+/// Asynchronously loads data from a MessagePack file.
+///
+/// The MessagePack counterpart of [`load_async`](crate::load_async).
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - If file reading fails, [`StorageError::VersionMismatch`] if the file
+///   was saved through a [`BinaryEnvelope`] with a schema version, or if deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save_msgpack_async, load_msgpack_async};
+/// use tempfile::NamedTempFile;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let temp_file = NamedTempFile::new().unwrap();
+///     save_msgpack_async(temp_file.path(), 3.5f64).await.unwrap();
+///
+///     let loaded: f64 = load_msgpack_async(temp_file.path()).await.unwrap();
+///     assert_eq!(loaded, 3.5);
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub async fn load_msgpack_async<P, T>(path: P) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned + Send + 'static,
+{
+    load_as_async::<MsgPack, _, _>(path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::collections::HashMap;
+    use tempfile::NamedTempFile;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+    struct Record {
+        id: u64,
+        readings: Vec<f32>,
+    }
+
+    #[test]
+    fn save_msgpack_test() {
+        let msgpack_file = NamedTempFile::new().unwrap();
+        let json_file = NamedTempFile::new().unwrap();
+
+        let records: Vec<Record> = (0..100)
+            .map(|id| Record {
+                id,
+                readings: vec![1.5, 2.5, 3.5],
+            })
+            .collect();
+        let msgpack_size = save_msgpack(msgpack_file.path(), &records).unwrap();
+        let json_size = crate::save(json_file.path(), &records).unwrap();
+
+        assert!(msgpack_size < json_size);
+    }
+
+    #[test]
+    fn load_msgpack_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        let names: HashMap<u32, String> =
+            HashMap::from([(1, "one".to_string()), (42, "answer".to_string())]);
+        save_msgpack(path, &names).unwrap();
+
+        let loaded: HashMap<u32, String> = load_msgpack(path).unwrap();
+        assert_eq!(loaded, names);
+
+        let mut bytes = std::fs::read(path).unwrap();
+        assert_eq!(bytes[..8], *b"SSBE\x00\x02\x00\x00");
+        bytes[7] = 1;
+        std::fs::write(path, &bytes).unwrap();
+        let result: Result<HashMap<u32, String>, _> = load_msgpack(path);
+        assert!(matches!(
+            result,
+            Err(StorageError::VersionMismatch {
+                expected: 0,
+                found: 1
+            })
+        ));
+
+        std::fs::write(path, rmp_serde::to_vec_named(&names).unwrap()).unwrap();
+        let result: Result<HashMap<u32, String>, _> = load_msgpack(path);
+        assert!(matches!(result, Err(StorageError::Decode(_))));
+
+        std::fs::write(path, b"SSBE\x00\x02\x00\x00\xc1").unwrap();
+        let result: Result<HashMap<u32, String>, _> = load_msgpack(path);
+        assert!(matches!(result, Err(StorageError::Decode(_))));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn save_msgpack_async_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_path_buf();
+
+        let record = Record {
+            id: 7,
+            readings: vec![0.25],
+        };
+        save_msgpack_async(&path, record.clone()).await.unwrap();
+
+        let loaded: Record = load_msgpack(&path).unwrap();
+        assert_eq!(loaded, record);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn load_msgpack_async_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_path_buf();
+
+        let names: HashMap<u32, String> = HashMap::from([(7, "seven".to_string())]);
+        save_msgpack(&path, &names).unwrap();
+
+        let loaded: HashMap<u32, String> = load_msgpack_async(&path).await.unwrap();
+        assert_eq!(loaded, names);
+    }
+}
@@ -0,0 +1,301 @@
+use std::path::Path;
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use super::{Format, load_as, save_as};
+#[cfg(feature = "async")]
+use super::{load_as_async, save_as_async};
+use crate::StorageError;
+
+/// The TOML format, backed by the `toml` crate.
+///
+/// TOML is convenient for configuration that people edit by hand. The top-level value must be
+/// a table, so structs and maps can be stored but bare sequences or scalars cannot. Parse
+/// errors are reported as [`StorageError::Decode`] wrapping a [`toml::de::Error`], whose
+/// message names the offending line and column and which can be downcast to get the byte
+/// span.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Toml;
+
+impl Format for Toml {
+    const EXTENSION: Option<&'static str> = Some("toml");
+
+    fn serialize<T: Serialize>(data: &T) -> Result<Vec<u8>, StorageError> {
+        ::toml::to_string(data)
+            .map(String::into_bytes)
+            .map_err(|err| StorageError::Encode(err.into()))
+    }
+
+    fn deserialize<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, StorageError> {
+        ::toml::from_slice(bytes).map_err(|err| StorageError::Decode(err.into()))
+    }
+}
+
+/// Synchronously saves data to a TOML file.
+///
+/// The TOML counterpart of [`save`](crate::save).
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize` and serialize to a
+///   table)
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If file creation or writing fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use serde::{Serialize, Deserialize};
+/// use storage_service::{save_toml, load_toml};
+/// use tempfile::NamedTempFile;
+///
+/// #[derive(Serialize, Deserialize, Debug, PartialEq)]
+/// struct Config {
+///     name: String,
+///     verbose: bool,
+/// }
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// let config = Config { name: "app".to_string(), verbose: true };
+///
+/// save_toml(temp_file.path(), &config).unwrap();
+/// assert_eq!(
+///     std::fs::read_to_string(temp_file.path()).unwrap(),
+///     "name = \"app\"\nverbose = true\n"
+/// );
+///
+/// let loaded: Config = load_toml(temp_file.path()).unwrap();
+/// assert_eq!(loaded, config);
+/// ```
+pub fn save_toml<P, T>(path: P, data: T) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    save_as::<Toml, _, _>(path, data)
+}
+
+/// Asynchronously saves data to a TOML file.
+///
+/// The TOML counterpart of [`save_async`](crate::save_async).
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`, `Send`, and `'static`,
+///   and serialize to a table)
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If file creation, writing fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use storage_service::save_toml_async;
+/// use tempfile::NamedTempFile;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let temp_file = NamedTempFile::new().unwrap();
+///     let limits = BTreeMap::from([("connections", 100), ("requests", 5000)]);
+///     save_toml_async(temp_file.path(), limits).await.unwrap();
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub async fn save_toml_async<P, T>(path: P, data: T) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize + Send + 'static,
+{
+    save_as_async::<Toml, _, _>(path, data).await
+}
+
+/// Synchronously loads data from a TOML file.
+///
+/// The TOML counterpart of [`load`](crate::load).
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - If file reading fails, or [`StorageError::Decode`] with the line and
+///   column if the file is not valid TOML or does not match `T`
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use storage_service::load_toml;
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// std::fs::write(temp_file.path(), "[server]\nport = 8080\n").unwrap();
+///
+/// let loaded: HashMap<String, HashMap<String, u16>> = load_toml(temp_file.path()).unwrap();
+/// assert_eq!(loaded["server"]["port"], 8080);
+/// ```
+pub fn load_toml<P, T>(path: P) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    load_as::<Toml, _, _>(path)
+}
+
+/// Asynchronously loads data from a TOML file.
+///
+/// The TOML counterpart of [`load_async`](crate::load_async).
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - If file reading fails, or [`StorageError::Decode`] with the line and
+///   column if the file is not valid TOML or does not match `T`
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use storage_service::{save_toml_async, load_toml_async};
+/// use tempfile::NamedTempFile;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let temp_file = NamedTempFile::new().unwrap();
+///     let flags = HashMap::from([("beta".to_string(), true)]);
+///     save_toml_async(temp_file.path(), flags.clone()).await.unwrap();
+///
+///     let loaded: HashMap<String, bool> = load_toml_async(temp_file.path()).await.unwrap();
+///     assert_eq!(loaded, flags);
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub async fn load_toml_async<P, T>(path: P) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned + Send + 'static,
+{
+    load_as_async::<Toml, _, _>(path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::collections::BTreeMap;
+    use tempfile::NamedTempFile;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+    struct Config {
+        title: String,
+        database: Database,
+        servers: BTreeMap<String, Server>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+    struct Database {
+        ports: Vec<u16>,
+        enabled: bool,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+    struct Server {
+        ip: String,
+        weight: f64,
+    }
+
+    fn config() -> Config {
+        Config {
+            title: "example".to_string(),
+            database: Database {
+                ports: vec![8000, 8001],
+                enabled: true,
+            },
+            servers: BTreeMap::from([
+                (
+                    "alpha".to_string(),
+                    Server {
+                        ip: "10.0.0.1".to_string(),
+                        weight: 1.0,
+                    },
+                ),
+                (
+                    "beta".to_string(),
+                    Server {
+                        ip: "10.0.0.2".to_string(),
+                        weight: 0.5,
+                    },
+                ),
+            ]),
+        }
+    }
+
+    #[test]
+    fn save_toml_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        let written = save_toml(path, config()).unwrap();
+        let contents = std::fs::read_to_string(path).unwrap();
+        assert_eq!(written, contents.len());
+        assert!(contents.contains("[servers.alpha]"));
+
+        let result = save_toml(path, vec![1, 2, 3]);
+        assert!(matches!(result, Err(StorageError::Encode(_))));
+    }
+
+    #[test]
+    fn load_toml_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        save_toml(path, config()).unwrap();
+        let loaded: Config = load_toml(path).unwrap();
+        assert_eq!(loaded, config());
+
+        std::fs::write(path, "title = \"ok\"\ndatabase = [\n").unwrap();
+        let Err(StorageError::Decode(err)) = load_toml::<_, Config>(path) else {
+            panic!("expected a decode error");
+        };
+        assert!(err.to_string().contains("line 2"));
+        assert!(err.downcast_ref::<::toml::de::Error>().is_some());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn save_toml_async_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_path_buf();
+
+        save_toml_async(&path, config()).await.unwrap();
+
+        let loaded: Config = load_toml(&path).unwrap();
+        assert_eq!(loaded, config());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn load_toml_async_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_path_buf();
+
+        save_toml(&path, config()).unwrap();
+
+        let loaded: Config = load_toml_async(&path).await.unwrap();
+        assert_eq!(loaded, config());
+    }
+}
@@ -0,0 +1,295 @@
+use std::path::Path;
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use super::{Format, load_as, save_as};
+#[cfg(feature = "async")]
+use super::{load_as_async, save_as_async};
+use crate::StorageError;
+
+/// The YAML format, backed by `serde_yaml`.
+///
+/// Files are saved as a single document without a leading `---` marker. On load, anchors and
+/// aliases are resolved, so a value defined once with `&name` and referenced with `*name`
+/// deserializes as if it had been written out in full. Files containing more than one
+/// document are rejected with [`StorageError::Decode`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Yaml;
+
+impl Format for Yaml {
+    const EXTENSION: Option<&'static str> = Some("yaml");
+
+    fn serialize<T: Serialize>(data: &T) -> Result<Vec<u8>, StorageError> {
+        serde_yaml::to_string(data)
+            .map(String::into_bytes)
+            .map_err(|err| StorageError::Encode(err.into()))
+    }
+
+    fn deserialize<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, StorageError> {
+        serde_yaml::from_slice(bytes).map_err(|err| StorageError::Decode(err.into()))
+    }
+}
+
+/// Synchronously saves data to a YAML file.
+///
+/// The YAML counterpart of [`save`](crate::save).
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If file creation or writing fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use serde::Serialize;
+/// use storage_service::save_yaml;
+/// use tempfile::NamedTempFile;
+///
+/// #[derive(Serialize)]
+/// struct Deployment {
+///     name: String,
+///     replicas: u32,
+/// }
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// let deployment = Deployment { name: "web".to_string(), replicas: 3 };
+///
+/// save_yaml(temp_file.path(), &deployment).unwrap();
+/// assert_eq!(
+///     std::fs::read_to_string(temp_file.path()).unwrap(),
+///     "name: web\nreplicas: 3\n"
+/// );
+/// ```
+pub fn save_yaml<P, T>(path: P, data: T) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    save_as::<Yaml, _, _>(path, data)
+}
+
+/// Asynchronously saves data to a YAML file.
+///
+/// The YAML counterpart of [`save_async`](crate::save_async).
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`, `Send`, and `'static`)
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If file creation, writing fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::save_yaml_async;
+/// use tempfile::NamedTempFile;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let temp_file = NamedTempFile::new().unwrap();
+///     save_yaml_async(temp_file.path(), vec!["a", "b"]).await.unwrap();
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub async fn save_yaml_async<P, T>(path: P, data: T) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize + Send + 'static,
+{
+    save_as_async::<Yaml, _, _>(path, data).await
+}
+
+/// Synchronously loads data from a YAML file.
+///
+/// The YAML counterpart of [`load`](crate::load).
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - If file reading fails or if deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use storage_service::load_yaml;
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// std::fs::write(
+///     temp_file.path(),
+///     "defaults: &defaults\n  timeout: 30\nservice: *defaults\n",
+/// )
+/// .unwrap();
+///
+/// let loaded: HashMap<String, HashMap<String, u32>> = load_yaml(temp_file.path()).unwrap();
+/// assert_eq!(loaded["service"]["timeout"], 30);
+/// ```
+pub fn load_yaml<P, T>(path: P) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    load_as::<Yaml, _, _>(path)
+}
+
+/// Asynchronously loads data from a YAML file.
+///
+/// The YAML counterpart of [`load_async`](crate::load_async).
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - If file reading fails or if deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save_yaml_async, load_yaml_async};
+/// use tempfile::NamedTempFile;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let temp_file = NamedTempFile::new().unwrap();
+///     save_yaml_async(temp_file.path(), (1u8, "one")).await.unwrap();
+///
+///     let loaded: (u8, String) = load_yaml_async(temp_file.path()).await.unwrap();
+///     assert_eq!(loaded, (1, "one".to_string()));
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub async fn load_yaml_async<P, T>(path: P) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned + Send + 'static,
+{
+    load_as_async::<Yaml, _, _>(path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::collections::BTreeMap;
+    use tempfile::NamedTempFile;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+    struct Manifest {
+        name: String,
+        labels: BTreeMap<String, String>,
+        containers: Vec<Container>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+    struct Container {
+        image: String,
+        resources: Resources,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+    struct Resources {
+        cpu: String,
+        memory: String,
+    }
+
+    fn manifest() -> Manifest {
+        Manifest {
+            name: "web".to_string(),
+            labels: BTreeMap::from([("tier".to_string(), "frontend".to_string())]),
+            containers: vec![Container {
+                image: "nginx:1.27".to_string(),
+                resources: Resources {
+                    cpu: "500m".to_string(),
+                    memory: "128Mi".to_string(),
+                },
+            }],
+        }
+    }
+
+    #[test]
+    fn save_yaml_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        let written = save_yaml(path, manifest()).unwrap();
+        let contents = std::fs::read_to_string(path).unwrap();
+
+        assert_eq!(written, contents.len());
+        assert!(contents.starts_with("name: web\n"));
+        assert!(!contents.contains("---"));
+
+        let loaded: Manifest = load_yaml(path).unwrap();
+        assert_eq!(loaded, manifest());
+    }
+
+    #[test]
+    fn load_yaml_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        std::fs::write(
+            path,
+            "\
+small: &small
+  cpu: 250m
+  memory: 64Mi
+web: *small
+worker:
+  cpu: '2'
+  memory: 1Gi
+",
+        )
+        .unwrap();
+        let loaded: BTreeMap<String, Resources> = load_yaml(path).unwrap();
+        assert_eq!(loaded.len(), 3);
+        assert_eq!(loaded["web"], loaded["small"]);
+        assert_eq!(loaded["web"].memory, "64Mi");
+        assert_eq!(loaded["worker"].cpu, "2");
+
+        std::fs::write(path, "a: 1\n---\na: 2\n").unwrap();
+        let result = load_yaml::<_, BTreeMap<String, u32>>(path);
+        assert!(matches!(result, Err(StorageError::Decode(_))));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn save_yaml_async_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_path_buf();
+
+        save_yaml_async(&path, manifest()).await.unwrap();
+
+        let loaded: Manifest = load_yaml(&path).unwrap();
+        assert_eq!(loaded, manifest());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn load_yaml_async_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_path_buf();
+
+        save_yaml(&path, manifest()).unwrap();
+
+        let loaded: Manifest = load_yaml_async(&path).await.unwrap();
+        assert_eq!(loaded, manifest());
+    }
+}
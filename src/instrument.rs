@@ -0,0 +1,166 @@
+//! Optional `tracing` instrumentation of storage operations.
+//!
+//! With the `tracing` feature enabled, every instrumented operation runs in a `storage` span
+//! carrying the operation name, the path and, once known, the number of bytes read or written,
+//! and ends with a `DEBUG` event on success or a `WARN` event with the error on failure, both
+//! recording the elapsed time. Without the feature, [`Operation`] is a zero-sized type whose
+//! methods compile to nothing.
+//!
+//! Failures that cannot be returned to the caller, such as a save from a `Drop` impl, go
+//! through [`report_unhandled`]: an `ERROR` event with the feature, a line on standard error
+//! without it.
+
+use std::path::Path;
+
+use crate::StorageError;
+
+/// An in-flight storage operation being traced.
+pub(crate) struct Operation {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(feature = "tracing")]
+    start: std::time::Instant,
+}
+
+impl Operation {
+    /// Starts tracing the operation `name` on `path`.
+    #[inline]
+    pub(crate) fn start(name: &'static str, path: &Path) -> Self {
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::debug_span!(
+                "storage",
+                operation = name,
+                path = %path.display(),
+                bytes = tracing::field::Empty,
+            );
+            Self {
+                span,
+                start: std::time::Instant::now(),
+            }
+        }
+        #[cfg(not(feature = "tracing"))]
+        {
+            let _ = (name, path);
+            Self {}
+        }
+    }
+
+    /// Records the number of bytes read or written.
+    #[inline]
+    pub(crate) fn record_bytes(&self, bytes: usize) {
+        #[cfg(feature = "tracing")]
+        self.span.record("bytes", bytes);
+        #[cfg(not(feature = "tracing"))]
+        let _ = bytes;
+    }
+
+    /// Ends the operation with `result`, which is passed through unchanged.
+    #[inline]
+    pub(crate) fn finish<R>(self, result: Result<R, StorageError>) -> Result<R, StorageError> {
+        #[cfg(feature = "tracing")]
+        {
+            let elapsed = self.start.elapsed();
+            let _entered = self.span.enter();
+            match &result {
+                Ok(_) => tracing::debug!(?elapsed, "storage operation completed"),
+                Err(err) => tracing::warn!(?elapsed, error = %err, "storage operation failed"),
+            }
+        }
+
+        result
+    }
+}
+
+/// Reports an error that has no caller to be returned to.
+pub(crate) fn report_unhandled(name: &'static str, path: &Path, err: &StorageError) {
+    #[cfg(feature = "tracing")]
+    tracing::error!(operation = name, path = %path.display(), error = %err, "storage operation failed");
+    #[cfg(not(feature = "tracing"))]
+    eprintln!(
+        "storage_service: {name} of {} failed: {err}",
+        path.display()
+    );
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use std::sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    };
+
+    use tracing::{
+        Event, Metadata, Subscriber,
+        span::{Attributes, Id, Record},
+    };
+
+    use super::*;
+
+    /// Collects the level and message of every event.
+    #[derive(Default)]
+    struct Collector {
+        next_id: AtomicU64,
+        events: Arc<Mutex<Vec<(tracing::Level, String)>>>,
+    }
+
+    impl Subscriber for Collector {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _span: &Attributes<'_>) -> Id {
+            Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+        }
+
+        fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let level = *event.metadata().level();
+            let mut message = String::new();
+            event.record(
+                &mut |field: &tracing::field::Field, value: &dyn std::fmt::Debug| {
+                    if field.name() == "message" {
+                        message = format!("{value:?}");
+                    }
+                },
+            );
+            self.events.lock().unwrap().push((level, message));
+        }
+
+        fn enter(&self, _span: &Id) {}
+
+        fn exit(&self, _span: &Id) {}
+    }
+
+    #[test]
+    fn operation_test() {
+        let collector = Collector::default();
+        let events = Arc::clone(&collector.events);
+
+        tracing::subscriber::with_default(collector, || {
+            let operation = Operation::start("save", Path::new("data.json"));
+            operation.record_bytes(12);
+            assert_eq!(operation.finish(Ok(12)).unwrap(), 12);
+
+            let operation = Operation::start("load", Path::new("missing.json"));
+            let failed: Result<(), _> =
+                operation.finish(Err(StorageError::Validation("boom".to_string())));
+            assert!(failed.is_err());
+        });
+
+        let events = events.lock().unwrap();
+        assert_eq!(
+            *events,
+            [
+                (
+                    tracing::Level::DEBUG,
+                    "storage operation completed".to_string()
+                ),
+                (tracing::Level::WARN, "storage operation failed".to_string()),
+            ]
+        );
+    }
+}
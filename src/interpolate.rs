@@ -0,0 +1,209 @@
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+
+use crate::{StorageError, parse_file};
+
+/// Synchronously loads data from a JSON file after expanding environment variables in it.
+///
+/// Before the file is parsed, every `${NAME}` placeholder is replaced with the value of the
+/// environment variable `NAME`, and every `${NAME:-default}` with its value or, if it is unset
+/// or empty, with `default`. A placeholder inside a JSON string is replaced with the value
+/// escaped as string content, so values containing quotes or backslashes are safe; elsewhere
+/// the value is inserted as is, which allows numbers and booleans such as `"port": ${PORT}`.
+/// Variable names consist of ASCII letters, digits and underscores. Write `$${` for a literal
+/// `${`; any other `$` that does not start a placeholder is left unchanged.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - [`StorageError::UndefinedVariable`] if a placeholder without a
+///   default names a variable that is not set, or if file reading or deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use serde::Deserialize;
+/// use storage_service::load_interpolated;
+/// use tempfile::NamedTempFile;
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     url: String,
+///     port: u16,
+/// }
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// std::fs::write(
+///     temp_file.path(),
+///     r#"{"url": "${EXAMPLE_UNSET_URL:-postgres://localhost}", "port": ${EXAMPLE_UNSET_PORT:-5432}}"#,
+/// )
+/// .unwrap();
+///
+/// let config: Config = load_interpolated(temp_file.path()).unwrap();
+/// assert_eq!(config.url, "postgres://localhost");
+/// assert_eq!(config.port, 5432);
+/// ```
+pub fn load_interpolated<P, T>(path: P) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path)?;
+
+    let expanded = interpolate(&text, |name| std::env::var(name).ok()).map_err(|name| {
+        StorageError::UndefinedVariable {
+            path: path.to_path_buf(),
+            name,
+        }
+    })?;
+
+    parse_file(path, expanded.as_bytes())
+}
+
+/// Splits a `${NAME}` or `${NAME:-default}` placeholder at the start of `text` into the name,
+/// the default and the text after it.
+fn placeholder(text: &str) -> Option<(&str, Option<&str>, &str)> {
+    let (inner, after) = text.strip_prefix("${")?.split_once('}')?;
+    let (name, default) = match inner.split_once(":-") {
+        Some((name, default)) => (name, Some(default)),
+        None => (inner, None),
+    };
+
+    let is_name = !name.is_empty()
+        && name
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || byte == b'_');
+    is_name.then_some((name, default, after))
+}
+
+/// Expands the placeholders in the JSON `text`, looking variables up with `lookup`.
+///
+/// Returns the name of the first variable that has neither a value nor a default.
+fn interpolate<F>(text: &str, lookup: F) -> Result<String, String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    while let Some(c) = rest.chars().next() {
+        if let Some(after) = rest.strip_prefix("$${") {
+            output.push_str("${");
+            rest = after;
+            continue;
+        }
+        if let Some((name, default, after)) = placeholder(rest) {
+            let value = match (lookup(name), default) {
+                (Some(value), Some(default)) if value.is_empty() => default.to_string(),
+                (Some(value), _) => value,
+                (None, Some(default)) => default.to_string(),
+                (None, None) => return Err(name.to_string()),
+            };
+
+            if in_string {
+                let quoted = serde_json::Value::String(value).to_string();
+                output.push_str(&quoted[1..quoted.len() - 1]);
+            } else {
+                output.push_str(&value);
+            }
+            rest = after;
+            continue;
+        }
+
+        if escaped {
+            escaped = false;
+        } else if in_string && c == '\\' {
+            escaped = true;
+        } else if c == '"' {
+            in_string = !in_string;
+        }
+        output.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+    use tempfile::NamedTempFile;
+
+    use super::*;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "HOME" => Some("/home/ada".to_string()),
+            "PORT" => Some("8080".to_string()),
+            "QUOTED" => Some(r#"say "hi" \ bye"#.to_string()),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn interpolate_test() {
+        let expand = |text: &str| interpolate(text, lookup);
+
+        assert_eq!(
+            expand(r#"{"dir": "${HOME}/data", "port": ${PORT}}"#).unwrap(),
+            r#"{"dir": "/home/ada/data", "port": 8080}"#
+        );
+        assert_eq!(
+            expand(r#"["${QUOTED}"]"#).unwrap(),
+            r#"["say \"hi\" \\ bye"]"#
+        );
+        assert_eq!(
+            expand(r#"["${UNSET:-fallback}", "${EMPTY:-fallback}", "${EMPTY}", ${HOME:-x}]"#)
+                .unwrap(),
+            r#"["fallback", "fallback", "", /home/ada]"#
+        );
+        assert_eq!(
+            expand(r#"["$${HOME}", "$5", "${HOME", "${}", "\"${PORT}"]"#).unwrap(),
+            r#"["${HOME}", "$5", "${HOME", "${}", "\"8080"]"#
+        );
+        assert_eq!(expand(r#"{"a": "${UNSET}"}"#).unwrap_err(), "UNSET");
+    }
+
+    #[test]
+    fn load_interpolated_test() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Config {
+            package: String,
+            mode: String,
+        }
+
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        // Cargo sets this variable for test runs.
+        std::fs::write(
+            path,
+            r#"{"package": "${CARGO_PKG_NAME}", "mode": "${STORAGE_SERVICE_TEST_UNSET:-dev}"}"#,
+        )
+        .unwrap();
+        let config: Config = load_interpolated(path).unwrap();
+        assert_eq!(
+            config,
+            Config {
+                package: env!("CARGO_PKG_NAME").to_string(),
+                mode: "dev".to_string(),
+            }
+        );
+
+        std::fs::write(path, r#"{"url": "${STORAGE_SERVICE_TEST_UNSET}"}"#).unwrap();
+        let result = load_interpolated::<_, serde_json::Value>(path);
+        assert!(matches!(
+            result,
+            Err(StorageError::UndefinedVariable { name, .. }) if name == "STORAGE_SERVICE_TEST_UNSET"
+        ));
+    }
+}
@@ -0,0 +1,568 @@
+use std::io::{self, Write};
+
+use serde::{
+    Deserialize, Deserializer, Serialize,
+    de::{MapAccess, Visitor},
+};
+use serde_json::{
+    ser::{CompactFormatter, Formatter, PrettyFormatter, Serializer},
+    value::RawValue,
+};
+
+/// Options controlling the JSON text produced by [`save_with`](crate::save_with).
+///
+/// Pass them to [`SaveOptions::json`](crate::SaveOptions::json), or convert them directly
+/// with `SaveOptions::from`. The defaults match [`save`](crate::save): compact output, no
+/// trailing newline, and non-ASCII characters written as UTF-8.
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{JsonOptions, SaveOptions, load, save_with};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// let json = JsonOptions::new().ascii_only(true).trailing_newline(true);
+///
+/// save_with(temp_file.path(), "café", &SaveOptions::from(json)).unwrap();
+///
+/// let contents = std::fs::read_to_string(temp_file.path()).unwrap();
+/// assert_eq!(contents, "\"caf\\u00e9\"\n");
+/// assert_eq!(load::<_, String>(temp_file.path()).unwrap(), "café");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct JsonOptions {
+    pub(crate) pretty: bool,
+    pub(crate) trailing_newline: bool,
+    pub(crate) ascii_only: bool,
+    pub(crate) sort_keys: bool,
+    pub(crate) skip_nulls: bool,
+    pub(crate) skip_nulls_in_arrays: bool,
+    pub(crate) indent: Option<Box<[u8]>>,
+}
+
+impl JsonOptions {
+    /// Creates options with the default, compact output.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pretty-prints the output instead of writing it compactly, indented with two spaces
+    /// unless [`indent`](JsonOptions::indent) says otherwise.
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Sets the string written once per nesting level when pretty-printing, such as `b"    "`
+    /// or `b"\t"`.
+    ///
+    /// This has no effect unless [`pretty`](JsonOptions::pretty) is set. The indent is copied,
+    /// so it can be built at runtime, for example from an `.editorconfig` indent size. It must
+    /// consist of spaces and tabs; saving with any other indent fails with an
+    /// [`io::ErrorKind::InvalidInput`] error before anything is written.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use storage_service::{JsonOptions, SaveOptions, save_with};
+    /// use tempfile::NamedTempFile;
+    ///
+    /// let temp_file = NamedTempFile::new().unwrap();
+    /// let width = 3;
+    /// let json = JsonOptions::new().pretty(true).indent(&vec![b' '; width]);
+    ///
+    /// save_with(temp_file.path(), [1], &SaveOptions::from(json)).unwrap();
+    ///
+    /// let contents = std::fs::read_to_string(temp_file.path()).unwrap();
+    /// assert_eq!(contents, "[\n   1\n]");
+    /// ```
+    pub fn indent(mut self, indent: &[u8]) -> Self {
+        self.indent = Some(indent.into());
+        self
+    }
+
+    /// Terminates the output with a newline, as most text tools and editors expect.
+    pub fn trailing_newline(mut self, trailing_newline: bool) -> Self {
+        self.trailing_newline = trailing_newline;
+        self
+    }
+
+    /// Escapes every non-ASCII character in strings as `\uXXXX`.
+    ///
+    /// The output stays valid JSON that decodes to the same strings, but can pass through
+    /// tools or transports that are not 8-bit clean.
+    pub fn ascii_only(mut self, ascii_only: bool) -> Self {
+        self.ascii_only = ascii_only;
+        self
+    }
+
+    /// Writes the keys of every object in alphabetical order, whatever the field order of the
+    /// structs or the iteration order of the maps being serialized.
+    ///
+    /// This makes the output byte-for-byte reproducible, for golden files or content hashes.
+    /// The data is first converted to a [`serde_json::Value`], which costs an extra in-memory
+    /// copy and rejects integers that do not fit in 64 bits.
+    pub fn sort_keys(mut self, sort_keys: bool) -> Self {
+        self.sort_keys = sort_keys;
+        self
+    }
+
+    /// Leaves out object fields whose value is `null`, at every level of nesting.
+    ///
+    /// This has the effect of `#[serde(skip_serializing_if = "Option::is_none")]` on every
+    /// `Option` field without annotating them, and keeps files written from sparse structs
+    /// small. Fields are kept in their serialized order, and `null` array elements are kept
+    /// unless [`skip_nulls_in_arrays`](JsonOptions::skip_nulls_in_arrays) is set as well. The
+    /// data is first serialized to an in-memory buffer, which is then filtered.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use serde::Serialize;
+    /// use storage_service::{JsonOptions, SaveOptions, save_with};
+    /// use tempfile::NamedTempFile;
+    ///
+    /// #[derive(Serialize)]
+    /// struct Profile {
+    ///     name: String,
+    ///     email: Option<String>,
+    ///     phone: Option<String>,
+    /// }
+    ///
+    /// let temp_file = NamedTempFile::new().unwrap();
+    /// let profile = Profile { name: "Ada".to_string(), email: None, phone: Some("555".into()) };
+    /// let options = SaveOptions::from(JsonOptions::new().skip_nulls(true));
+    /// save_with(temp_file.path(), &profile, &options).unwrap();
+    ///
+    /// let contents = std::fs::read_to_string(temp_file.path()).unwrap();
+    /// assert_eq!(contents, r#"{"name":"Ada","phone":"555"}"#);
+    /// ```
+    pub fn skip_nulls(mut self, skip_nulls: bool) -> Self {
+        self.skip_nulls = skip_nulls;
+        self
+    }
+
+    /// Leaves out `null` elements of arrays, at every level of nesting.
+    ///
+    /// Note that this shifts the indices of the following elements, so a `Vec<Option<T>>`
+    /// does not round-trip; it is meant for arrays whose `null`s carry no meaning.
+    pub fn skip_nulls_in_arrays(mut self, skip_nulls_in_arrays: bool) -> Self {
+        self.skip_nulls_in_arrays = skip_nulls_in_arrays;
+        self
+    }
+
+    /// Serializes `data` into `writer` using the configured formatting, without the trailing
+    /// newline.
+    pub(crate) fn write<W, T>(&self, writer: W, data: &T) -> Result<(), serde_json::Error>
+    where
+        W: Write,
+        T: Serialize + ?Sized,
+    {
+        if self.skip_nulls || self.skip_nulls_in_arrays {
+            let compact = serde_json::to_string(data)?;
+            let raw: &RawValue = serde_json::from_str(&compact)?;
+            let filtered = Filtered::new(raw, self)?;
+
+            let plain = Self {
+                sort_keys: false,
+                skip_nulls: false,
+                skip_nulls_in_arrays: false,
+                ..self.clone()
+            };
+            return plain.write(writer, &filtered);
+        }
+
+        if self.sort_keys {
+            // `Value` objects are `BTreeMap`s, so converting sorts every level.
+            let sorted = serde_json::to_value(data)?;
+            return self.clone().sort_keys(false).write(writer, &sorted);
+        }
+
+        let indent = self.indent.as_deref().unwrap_or(b"  ");
+        let pretty = || PrettyFormatter::with_indent(indent);
+        match (self.pretty, self.ascii_only) {
+            (false, false) => serialize(writer, data, CompactFormatter),
+            (true, false) => serialize(writer, data, pretty()),
+            (false, true) => serialize(writer, data, AsciiFormatter(CompactFormatter)),
+            (true, true) => serialize(writer, data, AsciiFormatter(pretty())),
+        }
+    }
+
+    /// Checks that the options can be used to write JSON.
+    pub(crate) fn validate(&self) -> Result<(), io::Error> {
+        match &self.indent {
+            Some(indent) if !indent.iter().all(|&byte| byte == b' ' || byte == b'\t') => {
+                Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "indent \"{}\" contains characters other than spaces and tabs",
+                        indent.escape_ascii()
+                    ),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn serialize<W, T, F>(writer: W, data: &T, formatter: F) -> Result<(), serde_json::Error>
+where
+    W: Write,
+    T: Serialize + ?Sized,
+    F: Formatter,
+{
+    data.serialize(&mut Serializer::with_formatter(writer, formatter))
+}
+
+/// A JSON document with `null`s filtered out, keeping object fields in their original order.
+///
+/// Scalars other than strings are kept as raw text, so numbers are written exactly as they
+/// were serialized, including integers that do not fit in 64 bits.
+enum Filtered<'a> {
+    Null,
+    Raw(&'a RawValue),
+    String(String),
+    Array(Vec<Filtered<'a>>),
+    Object(Vec<(String, Filtered<'a>)>),
+}
+
+impl<'a> Filtered<'a> {
+    /// Parses compact JSON text, removing the `null`s selected by `options` and sorting the
+    /// keys if requested.
+    fn new(raw: &'a RawValue, options: &JsonOptions) -> Result<Self, serde_json::Error> {
+        let text = raw.get();
+        let filtered = match text.as_bytes().first() {
+            Some(b'{') => {
+                let Entries(entries) = serde_json::from_str(text)?;
+                let mut fields = Vec::with_capacity(entries.len());
+                for (key, value) in entries {
+                    let value = Self::new(value, options)?;
+                    if !(options.skip_nulls && matches!(value, Filtered::Null)) {
+                        fields.push((key, value));
+                    }
+                }
+                if options.sort_keys {
+                    fields.sort_by(|(a, _), (b, _)| a.cmp(b));
+                }
+                Filtered::Object(fields)
+            }
+            Some(b'[') => {
+                let elements: Vec<&RawValue> = serde_json::from_str(text)?;
+                let mut items = Vec::with_capacity(elements.len());
+                for element in elements {
+                    let item = Self::new(element, options)?;
+                    if !(options.skip_nulls_in_arrays && matches!(item, Filtered::Null)) {
+                        items.push(item);
+                    }
+                }
+                Filtered::Array(items)
+            }
+            Some(b'"') => Filtered::String(serde_json::from_str(text)?),
+            Some(b'n') => Filtered::Null,
+            _ => Filtered::Raw(raw),
+        };
+
+        Ok(filtered)
+    }
+}
+
+impl Serialize for Filtered<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Filtered::Null => serializer.serialize_unit(),
+            Filtered::Raw(raw) => raw.serialize(serializer),
+            Filtered::String(string) => serializer.serialize_str(string),
+            Filtered::Array(items) => serializer.collect_seq(items),
+            Filtered::Object(fields) => {
+                serializer.collect_map(fields.iter().map(|(key, value)| (key, value)))
+            }
+        }
+    }
+}
+
+/// The fields of a JSON object in document order, with their values left unparsed.
+struct Entries<'a>(Vec<(String, &'a RawValue)>);
+
+impl<'de> Deserialize<'de> for Entries<'de> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EntriesVisitor;
+
+        impl<'de> Visitor<'de> for EntriesVisitor {
+            type Value = Entries<'de>;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("a JSON object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut entries = Vec::with_capacity(map.size_hint().unwrap_or_default());
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(Entries(entries))
+            }
+        }
+
+        deserializer.deserialize_map(EntriesVisitor)
+    }
+}
+
+/// Wraps another formatter, escaping non-ASCII characters in strings.
+struct AsciiFormatter<F>(F);
+
+impl<F: Formatter> Formatter for AsciiFormatter<F> {
+    fn write_string_fragment<W>(&mut self, writer: &mut W, fragment: &str) -> io::Result<()>
+    where
+        W: ?Sized + Write,
+    {
+        let mut rest = fragment;
+        while let Some(index) = rest.find(|c: char| !c.is_ascii()) {
+            writer.write_all(&rest.as_bytes()[..index])?;
+
+            let c = rest[index..].chars().next().unwrap_or_default();
+            for unit in c.encode_utf16(&mut [0; 2]) {
+                write!(writer, "\\u{unit:04x}")?;
+            }
+            rest = &rest[index + c.len_utf8()..];
+        }
+        writer.write_all(rest.as_bytes())
+    }
+
+    fn begin_array<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + Write,
+    {
+        self.0.begin_array(writer)
+    }
+
+    fn end_array<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + Write,
+    {
+        self.0.end_array(writer)
+    }
+
+    fn begin_array_value<W>(&mut self, writer: &mut W, first: bool) -> io::Result<()>
+    where
+        W: ?Sized + Write,
+    {
+        self.0.begin_array_value(writer, first)
+    }
+
+    fn end_array_value<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + Write,
+    {
+        self.0.end_array_value(writer)
+    }
+
+    fn begin_object<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + Write,
+    {
+        self.0.begin_object(writer)
+    }
+
+    fn end_object<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + Write,
+    {
+        self.0.end_object(writer)
+    }
+
+    fn begin_object_key<W>(&mut self, writer: &mut W, first: bool) -> io::Result<()>
+    where
+        W: ?Sized + Write,
+    {
+        self.0.begin_object_key(writer, first)
+    }
+
+    fn begin_object_value<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + Write,
+    {
+        self.0.begin_object_value(writer)
+    }
+
+    fn end_object_value<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + Write,
+    {
+        self.0.end_object_value(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn render<T: Serialize>(options: JsonOptions, data: &T) -> String {
+        let mut out = Vec::new();
+        options.write(&mut out, data).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn json_options_test() {
+        let data = serde_json::json!({ "name": "Zoë 🦀", "tags": ["a"] });
+
+        assert_eq!(
+            render(JsonOptions::new(), &data),
+            r#"{"name":"Zoë 🦀","tags":["a"]}"#
+        );
+        assert_eq!(
+            render(JsonOptions::new().ascii_only(true), &data),
+            r#"{"name":"Zo\u00eb \ud83e\udd80","tags":["a"]}"#
+        );
+        assert_eq!(
+            render(JsonOptions::new().pretty(true).ascii_only(true), &data),
+            "{\n  \"name\": \"Zo\\u00eb \\ud83e\\udd80\",\n  \"tags\": [\n    \"a\"\n  ]\n}"
+        );
+
+        assert_eq!(
+            render(JsonOptions::new().sort_keys(true), &data),
+            render(JsonOptions::new(), &data)
+        );
+
+        let escaped = render(JsonOptions::new().ascii_only(true), &data);
+        assert!(escaped.is_ascii());
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&escaped).unwrap(),
+            data
+        );
+    }
+
+    #[test]
+    fn json_options_indent_test() {
+        let data = serde_json::json!({ "list": [1], "empty": {} });
+
+        assert_eq!(
+            render(JsonOptions::new().pretty(true).indent(b"\t"), &data),
+            "{\n\t\"empty\": {},\n\t\"list\": [\n\t\t1\n\t]\n}"
+        );
+        assert_eq!(
+            render(
+                JsonOptions::new()
+                    .pretty(true)
+                    .indent(b"    ")
+                    .ascii_only(true),
+                &data
+            ),
+            "{\n    \"empty\": {},\n    \"list\": [\n        1\n    ]\n}"
+        );
+        assert_eq!(
+            render(JsonOptions::new().indent(b"\t"), &data),
+            r#"{"empty":{},"list":[1]}"#
+        );
+
+        let width = 3;
+        let runtime = vec![b' '; width];
+        assert_eq!(
+            render(JsonOptions::new().pretty(true).indent(&runtime), &[1]),
+            "[\n   1\n]"
+        );
+        assert_eq!(
+            render(JsonOptions::new().pretty(true).indent(b""), &[1]),
+            "[\n1\n]"
+        );
+
+        let long = vec![b'\t'; 40];
+        let rendered = render(JsonOptions::new().pretty(true).indent(&long), &[1]);
+        assert_eq!(rendered, format!("[\n{}1\n]", "\t".repeat(40)));
+
+        assert!(JsonOptions::new().indent(b" \t").validate().is_ok());
+        let err = JsonOptions::new().indent(b"--").validate().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn json_options_sort_keys_test() {
+        #[derive(Serialize)]
+        struct Unsorted {
+            zebra: u8,
+            apple: HashMap<&'static str, u8>,
+        }
+
+        let forward: HashMap<_, _> = [("b", 2), ("a", 1), ("c", 3)].into_iter().collect();
+        let mut backward = HashMap::new();
+        for (key, value) in [("c", 3), ("a", 1), ("b", 2)] {
+            backward.insert(key, value);
+        }
+
+        let options = JsonOptions::new().sort_keys(true);
+        let first = render(
+            options.clone(),
+            &Unsorted {
+                zebra: 0,
+                apple: forward,
+            },
+        );
+        let second = render(
+            options,
+            &Unsorted {
+                zebra: 0,
+                apple: backward,
+            },
+        );
+        assert_eq!(first, second);
+        assert_eq!(first, r#"{"apple":{"a":1,"b":2,"c":3},"zebra":0}"#);
+    }
+
+    #[test]
+    fn json_options_skip_nulls_test() {
+        #[derive(Serialize)]
+        struct Sparse {
+            zebra: Option<u8>,
+            apple: Option<&'static str>,
+            nested: serde_json::Value,
+            huge: u128,
+        }
+
+        let data = Sparse {
+            zebra: Some(1),
+            apple: None,
+            nested: serde_json::json!({ "x": null, "list": [1, null, { "y": null }], "é": "ü" }),
+            huge: u128::MAX,
+        };
+
+        assert_eq!(
+            render(JsonOptions::new().skip_nulls(true), &data),
+            format!(
+                r#"{{"zebra":1,"nested":{{"list":[1,null,{{}}],"é":"ü"}},"huge":{}}}"#,
+                u128::MAX
+            )
+        );
+        assert_eq!(
+            render(
+                JsonOptions::new()
+                    .skip_nulls(true)
+                    .skip_nulls_in_arrays(true)
+                    .sort_keys(true)
+                    .ascii_only(true),
+                &data
+            ),
+            format!(
+                r#"{{"huge":{},"nested":{{"list":[1,{{}}],"\u00e9":"\u00fc"}},"zebra":1}}"#,
+                u128::MAX
+            )
+        );
+        assert_eq!(
+            render(
+                JsonOptions::new().skip_nulls_in_arrays(true),
+                &[None, Some(2)]
+            ),
+            "[2]"
+        );
+        assert_eq!(render(JsonOptions::new().skip_nulls(true), &()), "null");
+        assert_eq!(
+            render(
+                JsonOptions::new().skip_nulls(true).pretty(true),
+                &serde_json::json!({ "a": [1], "b": null })
+            ),
+            "{\n  \"a\": [\n    1\n  ]\n}"
+        );
+    }
+}
@@ -1,15 +1,514 @@
 use std::{
-    io::{self, Read, Write},
-    path::Path,
+    ffi::OsStr,
+    fmt,
+    fs::File,
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::{Component, Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, SystemTime},
 };
 
-use serde::{Serialize, de::DeserializeOwned};
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
+#[cfg(feature = "async")]
+use tokio::io::AsyncWriteExt;
+#[cfg(feature = "async")]
+use tokio_util::io::SyncIoBridge;
+
+use instrument::Operation;
+
+mod autosave;
+mod backend;
+mod backup;
+mod batched;
+mod borrowed;
+mod cache;
+mod cas;
+mod checksum;
+mod clock;
+#[cfg(feature = "compression")]
+mod compression;
+mod diff;
+mod directory;
+mod document;
+#[cfg(feature = "encryption")]
+mod encryption;
+mod envelope;
+mod format;
+mod instrument;
+mod interpolate;
+mod json;
+mod lines;
+mod lock;
+mod migrate;
+#[cfg(feature = "mmap")]
+mod mmap;
+#[cfg(any(feature = "parallel", feature = "async"))]
+mod parallel;
+mod patch;
+mod recover;
+mod redact;
+mod replica;
+#[cfg(feature = "async")]
+mod retry;
+#[cfg(feature = "schema")]
+mod schema;
+#[cfg(feature = "async")]
+mod serialized;
+mod sharded;
+mod stdio;
+mod store;
+mod temp;
+#[cfg(feature = "async")]
+mod timeout;
+mod transaction;
+mod trash;
+#[cfg(feature = "watch")]
+mod watch;
+
+pub use autosave::Autosave;
+pub use backend::{FileBackend, MemoryBackend, StorageBackend};
+pub use backup::{restore_backup, save_rotating, save_with_backup};
+pub use batched::BatchedStore;
+pub use borrowed::{LoadedBytes, load_borrowed};
+pub use cache::CachedStore;
+pub use cas::{get_cas, put_cas};
+pub use checksum::{HashAlgo, checksum, load_checksummed, save_checksummed};
+pub use clock::{Clock, ManualClock, SystemClock};
+#[cfg(feature = "compression")]
+pub use compression::{
+    load_auto, load_compressed, load_zstd, save_auto, save_compressed, save_zstd,
+};
+pub use diff::{FieldChange, diff};
+pub use directory::{
+    BatchReport, list, load_all, purge_older_than, purge_older_than_with_clock, save_all,
+};
+#[cfg(feature = "async")]
+pub use directory::{list_async, load_all_async, save_all_async};
+pub use document::Document;
+#[cfg(feature = "encryption")]
+pub use encryption::{load_encrypted, save_encrypted};
+pub use envelope::{Metadata, load_envelope, save_envelope};
+#[cfg(all(feature = "json5", feature = "async"))]
+pub use format::load_json5_async;
+pub use format::{
+    BinaryEnvelope, BinaryFormat, Format, Json, Versioned, load_as, save_as, save_ext, save_ext_as,
+};
+#[cfg(feature = "bincode")]
+pub use format::{Bincode, load_bincode, save_bincode};
+#[cfg(feature = "cbor")]
+pub use format::{Cbor, load_cbor, save_cbor};
+#[cfg(feature = "json5")]
+pub use format::{Json5, load_json5};
+#[cfg(feature = "msgpack")]
+pub use format::{MsgPack, load_msgpack, save_msgpack};
+#[cfg(feature = "toml")]
+pub use format::{Toml, load_toml, save_toml};
+#[cfg(feature = "yaml")]
+pub use format::{Yaml, load_yaml, save_yaml};
+#[cfg(feature = "async")]
+pub use format::{load_as_async, save_as_async};
+#[cfg(all(feature = "bincode", feature = "async"))]
+pub use format::{load_bincode_async, save_bincode_async};
+#[cfg(all(feature = "cbor", feature = "async"))]
+pub use format::{load_cbor_async, save_cbor_async};
+#[cfg(all(feature = "msgpack", feature = "async"))]
+pub use format::{load_msgpack_async, save_msgpack_async};
+#[cfg(all(feature = "toml", feature = "async"))]
+pub use format::{load_toml_async, save_toml_async};
+#[cfg(all(feature = "yaml", feature = "async"))]
+pub use format::{load_yaml_async, save_yaml_async};
+pub use interpolate::load_interpolated;
+pub use json::JsonOptions;
+#[cfg(feature = "async")]
+pub use lines::load_lines_stream;
+pub use lines::{append_line, load_lines};
+#[cfg(feature = "compression")]
+pub use lines::{append_line_gz, load_lines_gz};
+pub use lock::{LockMode, load_locked, save_locked, update};
+pub use migrate::load_migrated;
+#[cfg(feature = "mmap")]
+pub use mmap::load_mmap;
+#[cfg(feature = "parallel")]
+pub use parallel::load_many;
+#[cfg(feature = "async")]
+pub use parallel::load_many_async;
+pub use patch::{apply_patch, merge};
+pub use recover::{Recovered, load_best_effort};
+pub use redact::save_redacted;
+pub use replica::{load_replicated, save_replicated};
+#[cfg(feature = "async")]
+pub use retry::{RetryConfig, save_async_retry};
+#[cfg(feature = "schema")]
+pub use schema::load_schema_validated;
+#[cfg(feature = "async")]
+pub use serialized::{load_async_serialized, save_async_serialized};
+pub use sharded::ShardedStore;
+pub use stdio::{load_or_stdin, save_or_stdout};
+pub use store::Store;
+pub use temp::{TempDocument, save_temp};
+#[cfg(feature = "async")]
+pub use timeout::{load_async_timeout, save_async_timeout};
+pub use transaction::Transaction;
+pub use trash::{purge_trash, purge_trash_with_clock, trash};
+#[cfg(feature = "watch")]
+pub use watch::{StorageWatcher, watch};
+
+/// Errors that can occur while saving or loading data.
+#[derive(Debug)]
+pub enum StorageError {
+    /// Reading from or writing to the filesystem failed.
+    Io(io::Error),
+    /// The data could not be serialized to JSON.
+    Serialize(serde_json::Error),
+    /// The file contents could not be deserialized from JSON.
+    Deserialize(serde_json::Error),
+    /// A JSON file could not be deserialized; returned by [`load`] and the functions built on
+    /// it, which know which file was being read.
+    ///
+    /// The error's [`line`](serde_json::Error::line) and
+    /// [`column`](serde_json::Error::column) locate the problem within the file.
+    Parse {
+        /// The file that failed to parse.
+        path: PathBuf,
+        /// The underlying parse error.
+        source: serde_json::Error,
+    },
+    /// The file exists but is empty, as left behind by a save that crashed before writing
+    /// anything; returned by [`load`] and the functions built on it instead of a parse error.
+    ///
+    /// Callers can treat this like a missing file and fall back to a default value.
+    Empty(PathBuf),
+    /// A line of a JSON-lines file could not be deserialized.
+    InvalidLine {
+        /// The 1-based number of the offending line.
+        line: usize,
+        /// The underlying parse error.
+        source: serde_json::Error,
+    },
+    /// The checksum stored with the data does not match the data itself.
+    ChecksumMismatch {
+        /// The checksum recorded when the file was saved.
+        expected: String,
+        /// The checksum of the data as it was read back.
+        actual: String,
+    },
+    /// A binary file was written with a different schema version of its type than the one it
+    /// is being loaded as; see [`BinaryEnvelope`].
+    VersionMismatch {
+        /// The schema version being loaded, from [`Versioned::VERSION`] or 0 for a plain
+        /// binary format.
+        expected: u16,
+        /// The schema version recorded in the file.
+        found: u16,
+    },
+    /// A save with [`SaveOptions::create_new`] found a file already present at the path.
+    AlreadyExists(PathBuf),
+    /// None of the files passed to [`load_first`] exist.
+    NotFound(Vec<PathBuf>),
+    /// The replicas checked by [`load_replicated`] do not all hold the same data; lists the
+    /// replicas that differ from the first readable one or could not be read.
+    ReplicaMismatch(Vec<PathBuf>),
+    /// The data was deserialized but rejected by a validation check.
+    Validation(String),
+    /// The file does not conform to the JSON Schema passed to `load_schema_validated`.
+    ///
+    /// Each entry describes one violation, prefixed with its location in the document as a
+    /// JSON Pointer, for example `/port: 70000 is greater than the maximum of 65535`.
+    SchemaViolation(Vec<String>),
+    /// A `${NAME}` placeholder in a file passed to [`load_interpolated`] names an environment
+    /// variable that is not set, or not valid Unicode, and gives no default.
+    UndefinedVariable {
+        /// The file containing the placeholder.
+        path: PathBuf,
+        /// The name of the environment variable.
+        name: String,
+    },
+    /// The file is locked by another process and [`LockMode::FailFast`] was requested.
+    Locked(PathBuf),
+    /// A write was attempted through a [`Document`] marked [`read_only`](Document::read_only).
+    ReadOnly(PathBuf),
+    /// An async operation did not complete within the given duration.
+    Timeout(Duration),
+    /// The file is larger than the limit passed to [`load_limited`].
+    TooLarge {
+        /// The maximum number of bytes that was allowed.
+        limit: u64,
+        /// The size of the file, or a lower bound on it if it grew while being read.
+        size: u64,
+    },
+    /// An encrypted file could not be authenticated, because the key is wrong or the file has
+    /// been modified.
+    DecryptionFailed,
+    /// A non-JSON [`Format`] could not encode the data.
+    Encode(Box<dyn std::error::Error + Send + Sync>),
+    /// A non-JSON [`Format`] could not decode the file contents.
+    Decode(Box<dyn std::error::Error + Send + Sync>),
+    /// A JSON Patch passed to [`apply_patch`] could not be applied, for example because an
+    /// operation refers to a path that does not exist or a `test` operation failed.
+    Patch(json_patch::PatchError),
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::Io(err) => write!(f, "I/O error: {err}"),
+            StorageError::Serialize(err) => write!(f, "failed to serialize data: {err}"),
+            StorageError::Deserialize(err) => write!(f, "failed to deserialize data: {err}"),
+            StorageError::Parse { path, source } => {
+                let (line, column) = (source.line(), source.column());
+                let message = source.to_string();
+                let position = format!(" at line {line} column {column}");
+                match message.strip_suffix(&position) {
+                    Some(message) if line > 0 => write!(
+                        f,
+                        "failed to parse {} at line {line} column {column}: {message}",
+                        path.display()
+                    ),
+                    _ => write!(f, "failed to parse {}: {message}", path.display()),
+                }
+            }
+            StorageError::Empty(path) => write!(f, "file is empty: {}", path.display()),
+            StorageError::InvalidLine { line, source } => {
+                write!(f, "failed to deserialize line {line}: {source}")
+            }
+            StorageError::ChecksumMismatch { expected, actual } => {
+                write!(f, "checksum mismatch: expected {expected}, found {actual}")
+            }
+            StorageError::VersionMismatch { expected, found } => {
+                write!(
+                    f,
+                    "schema version mismatch: expected version {expected}, found version {found}"
+                )
+            }
+            StorageError::AlreadyExists(path) => {
+                write!(f, "refusing to overwrite existing file: {}", path.display())
+            }
+            StorageError::NotFound(paths) => {
+                write!(f, "none of the candidate files exist:")?;
+                for path in paths {
+                    write!(f, " {}", path.display())?;
+                }
+                Ok(())
+            }
+            StorageError::ReplicaMismatch(paths) => {
+                write!(f, "replicas disagree:")?;
+                for path in paths {
+                    write!(f, " {}", path.display())?;
+                }
+                Ok(())
+            }
+            StorageError::Validation(reason) => write!(f, "validation failed: {reason}"),
+            StorageError::SchemaViolation(violations) => {
+                write!(f, "schema validation failed: {}", violations.join("; "))
+            }
+            StorageError::UndefinedVariable { path, name } => {
+                write!(
+                    f,
+                    "environment variable {name} used in {} is not set",
+                    path.display()
+                )
+            }
+            StorageError::Locked(path) => {
+                write!(f, "file is locked by another process: {}", path.display())
+            }
+            StorageError::ReadOnly(path) => {
+                write!(f, "refusing to modify read-only file: {}", path.display())
+            }
+            StorageError::Timeout(duration) => {
+                write!(f, "operation timed out after {duration:?}")
+            }
+            StorageError::TooLarge { limit, size } => {
+                write!(
+                    f,
+                    "file is {size} bytes, which exceeds the limit of {limit} bytes"
+                )
+            }
+            StorageError::DecryptionFailed => write!(f, "failed to decrypt data"),
+            StorageError::Encode(err) => write!(f, "failed to encode data: {err}"),
+            StorageError::Decode(err) => write!(f, "failed to decode data: {err}"),
+            StorageError::Patch(err) => write!(f, "failed to apply patch: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StorageError::Io(err) => Some(err),
+            StorageError::Serialize(err) | StorageError::Deserialize(err) => Some(err),
+            StorageError::InvalidLine { source, .. } | StorageError::Parse { source, .. } => {
+                Some(source)
+            }
+            StorageError::Empty(_)
+            | StorageError::ChecksumMismatch { .. }
+            | StorageError::VersionMismatch { .. }
+            | StorageError::AlreadyExists(_)
+            | StorageError::NotFound(_)
+            | StorageError::ReplicaMismatch(_)
+            | StorageError::Validation(_)
+            | StorageError::SchemaViolation(_)
+            | StorageError::UndefinedVariable { .. }
+            | StorageError::Locked(_)
+            | StorageError::ReadOnly(_)
+            | StorageError::Timeout(_)
+            | StorageError::TooLarge { .. }
+            | StorageError::DecryptionFailed => None,
+            StorageError::Encode(err) | StorageError::Decode(err) => Some(err.as_ref()),
+            StorageError::Patch(err) => Some(err),
+        }
+    }
+}
+
+impl StorageError {
+    /// Classifies a `serde_json` error raised while serializing, keeping I/O failures of the
+    /// underlying writer as [`StorageError::Io`].
+    fn from_serialize(err: serde_json::Error) -> Self {
+        if err.is_io() {
+            StorageError::Io(err.into())
+        } else {
+            StorageError::Serialize(err)
+        }
+    }
+
+    /// Classifies a `serde_json` error raised while deserializing, keeping I/O failures of the
+    /// underlying reader as [`StorageError::Io`].
+    fn from_deserialize(err: serde_json::Error) -> Self {
+        if err.is_io() {
+            StorageError::Io(err.into())
+        } else {
+            StorageError::Deserialize(err)
+        }
+    }
+
+    /// Attaches the path of the file being read to a deserialization error.
+    fn in_file(self, path: &Path) -> Self {
+        match self {
+            StorageError::Deserialize(source) => StorageError::Parse {
+                path: path.to_path_buf(),
+                source,
+            },
+            err => err,
+        }
+    }
+}
+
+impl From<io::Error> for StorageError {
+    fn from(err: io::Error) -> Self {
+        StorageError::Io(err)
+    }
+}
+
+#[cfg(feature = "async")]
+impl From<tokio::task::JoinError> for StorageError {
+    fn from(err: tokio::task::JoinError) -> Self {
+        StorageError::Io(err.into())
+    }
+}
+
+/// Options controlling how [`save_with`] and [`save_with_async`] serialize and write data.
+///
+/// The default options produce the same compact output as [`save`].
+///
+/// # Example
+///
+/// ```
+/// use storage_service::SaveOptions;
+///
+/// let options = SaveOptions::new().pretty(true);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SaveOptions {
+    json: JsonOptions,
+    mode: Option<u32>,
+    create_dirs: bool,
+    durable: bool,
+    create_new: bool,
+}
+
+impl SaveOptions {
+    /// Creates options with the default, compact output.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pretty-prints the JSON output and terminates it with a trailing newline.
+    ///
+    /// This is a shorthand for setting both [`JsonOptions::pretty`] and
+    /// [`JsonOptions::trailing_newline`].
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.json = self.json.pretty(pretty).trailing_newline(pretty);
+        self
+    }
+
+    /// Replaces the JSON formatting options.
+    pub fn json(mut self, json: JsonOptions) -> Self {
+        self.json = json;
+        self
+    }
+
+    /// Creates the file with the given Unix permission bits (for example `0o600`).
+    ///
+    /// The permissions are applied before any data is written, so the contents are never
+    /// readable with looser permissions. Without this setting, a file that replaces an
+    /// existing one keeps the permissions of the file it replaces. This setting is ignored on
+    /// non-Unix platforms.
+    pub fn mode(mut self, mode: u32) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Creates missing parent directories of the target file before writing.
+    ///
+    /// This is off by default so that a mistyped directory is reported instead of silently
+    /// created.
+    pub fn create_dirs(mut self, create_dirs: bool) -> Self {
+        self.create_dirs = create_dirs;
+        self
+    }
+
+    /// Flushes the file contents to the storage device before the save returns.
+    ///
+    /// Without this, a save that has returned successfully can still be lost on power failure
+    /// or an operating system crash, because the data may only have reached the page cache.
+    /// Enabling it calls `sync_all` on the written file before it replaces the target, and on
+    /// the parent directory after the rename, so that the new directory entry survives a crash
+    /// too. This typically costs several milliseconds per save on SSDs and far more on spinning
+    /// disks or network storage, so it is off by default. Directories cannot be synced on
+    /// Windows, where the rename is made durable by the filesystem itself and the second step
+    /// is skipped.
+    pub fn durable(mut self, durable: bool) -> Self {
+        self.durable = durable;
+        self
+    }
+
+    /// Fails with [`StorageError::AlreadyExists`] instead of replacing an existing file.
+    ///
+    /// This gives saves "create once" semantics, the filesystem equivalent of an
+    /// insert-if-not-exists: of several processes creating the same file, exactly one
+    /// succeeds. The data is still written to a temporary file first, which is then hard-linked
+    /// into place, an operation that fails atomically if the target exists; so, unlike opening
+    /// the target with `create_new`, readers never see a half-written file and a crash never
+    /// leaves one behind. The filesystem must support hard links.
+    pub fn create_new(mut self, create_new: bool) -> Self {
+        self.create_new = create_new;
+        self
+    }
+}
+
+impl From<JsonOptions> for SaveOptions {
+    fn from(json: JsonOptions) -> Self {
+        Self::new().json(json)
+    }
+}
 
 /// Synchronously saves data to a JSON file.
 ///
 /// This function serializes the provided data to JSON format and writes it to the specified file.
-/// If the file already exists, it will be truncated.
+/// The data is first written to a temporary file in the same directory, which is then renamed
+/// over the target, so an existing file is never left truncated or half-written. On Unix, the
+/// new file keeps the owner, group and permissions of the file it replaces, as far as the
+/// process is allowed to set them.
 ///
 /// # Arguments
 ///
@@ -18,8 +517,8 @@ use tokio::io::{AsyncReadExt, AsyncWriteExt};
 ///
 /// # Returns
 ///
-/// * `Ok(())` - If the operation succeeds
-/// * `Err(io::Error)` - If file creation or writing fails, or if serialization fails
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If file creation or writing fails, or if serialization fails
 ///
 /// # Example
 ///
@@ -43,22 +542,27 @@ use tokio::io::{AsyncReadExt, AsyncWriteExt};
 /// let result = save(temp_file.path(), &user);
 /// assert!(result.is_ok());
 /// ```
-pub fn save<P, T>(path: P, data: T) -> Result<(), io::Error>
+pub fn save<P, T>(path: P, data: T) -> Result<usize, StorageError>
 where
     P: AsRef<Path>,
     T: Serialize,
 {
-    let json_data = serde_json::to_string(&data)?;
-    let mut file = std::fs::File::create(path)?;
-
-    file.write_all(json_data.as_bytes())
+    save_with(path, data, &SaveOptions::default())
 }
 
 /// Asynchronously saves data to a JSON file.
 ///
 /// This async function serializes the provided data to JSON format and writes it to the specified
 /// file using tokio's async file I/O. Serialization is performed on a blocking task to avoid
-/// blocking the async runtime.
+/// blocking the async runtime, and streams into a buffered writer so large payloads are never
+/// held in memory as a single string. Like [`save`], the data is written to a temporary file in
+/// the same directory and then renamed over the target.
+///
+/// This makes the future cancellation-safe: if it is dropped before completing, for example by
+/// a `select!` or a timeout, the target file keeps its previous contents (or, if the final
+/// rename was already under way, has the new ones), never a mix of both. The temporary file
+/// is removed as well, except when the future is dropped while tokio is still creating it on a
+/// blocking thread; such a leftover is a hidden `.<name>.<pid>.<n>.tmp` file next to the target.
 ///
 /// # Arguments
 ///
@@ -67,8 +571,8 @@ where
 ///
 /// # Returns
 ///
-/// * `Ok(())` - If the operation succeeds
-/// * `Err(io::Error)` - If file creation, writing fails, or if serialization fails
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If file creation, writing fails, or if serialization fails
 ///
 /// # Example
 ///
@@ -95,199 +599,3354 @@ where
 ///     assert!(result.is_ok());
 /// }
 /// ```
-pub async fn save_async<P, T>(path: P, data: T) -> Result<(), io::Error>
+#[cfg(feature = "async")]
+pub async fn save_async<P, T>(path: P, data: T) -> Result<usize, StorageError>
 where
     P: AsRef<Path>,
     T: Serialize + Send + 'static,
 {
-    let json_data = tokio::task::spawn_blocking(move || serde_json::to_string(&data)).await??;
-    let mut file = tokio::fs::File::create(path).await?;
-
-    file.write_all(json_data.as_bytes()).await
+    save_with_async(path, data, &SaveOptions::default()).await
 }
 
-/// Synchronously loads data from a JSON file.
+/// Asynchronously saves data to a JSON file, doing all of the work on a blocking thread.
 ///
-/// This function reads a JSON file and deserializes its contents into the specified type.
+/// Unlike [`save_async`], which serializes on a blocking thread but hands every buffered chunk
+/// to tokio's file API, this runs [`save`] in its entirety inside
+/// [`spawn_blocking`](tokio::task::spawn_blocking). For large payloads this avoids a round trip
+/// through the runtime per chunk, so the write finishes sooner and never occupies a runtime
+/// worker, which keeps other tasks responsive. For small payloads the difference is
+/// negligible. The file is written with the same temporary-file-and-rename strategy.
 ///
 /// # Arguments
 ///
-/// * `path` - The file path to read from
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`, `Send`, and `'static`)
 ///
 /// # Returns
 ///
-/// * `Ok(T)` - The deserialized data if successful
-/// * `Err(io::Error)` - If file reading fails or if deserialization fails
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If file creation, writing fails, or if serialization fails
 ///
 /// # Example
 ///
 /// ```
-/// use storage_service::{save, load};
-/// use serde::{Serialize, Deserialize};
+/// use storage_service::{save_async_blocking, load};
 /// use tempfile::NamedTempFile;
 ///
-/// #[derive(Serialize, Deserialize, PartialEq, Debug)]
-/// struct Person {
-///     name: String,
-///     age: u32,
+/// #[tokio::main]
+/// async fn main() {
+///     let temp_file = NamedTempFile::new().unwrap();
+///     let samples: Vec<u32> = (0..10_000).collect();
+///
+///     save_async_blocking(temp_file.path(), samples.clone()).await.unwrap();
+///
+///     let loaded: Vec<u32> = load(temp_file.path()).unwrap();
+///     assert_eq!(loaded, samples);
 /// }
+/// ```
+#[cfg(feature = "async")]
+pub async fn save_async_blocking<P, T>(path: P, data: T) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize + Send + 'static,
+{
+    let path = path.as_ref().to_path_buf();
+
+    tokio::task::spawn_blocking(move || save(path, data)).await?
+}
+
+/// Synchronously saves data to a JSON file using the given options.
+///
+/// This is the configurable form of [`save`]; see [`SaveOptions`] for the available settings.
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+/// * `options` - Controls how the data is serialized and written
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If file creation or writing fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save_with, SaveOptions};
+/// use tempfile::NamedTempFile;
 ///
 /// let temp_file = NamedTempFile::new().unwrap();
-/// let original = Person {
-///     name: "Bob".to_string(),
-///     age: 25,
-/// };
+/// let options = SaveOptions::new().pretty(true);
 ///
-/// save(temp_file.path(), &original).unwrap();
-/// let loaded: Person = load(temp_file.path()).unwrap();
-/// assert_eq!(loaded, original);
+/// save_with(temp_file.path(), vec![1, 2, 3], &options).unwrap();
 /// ```
-pub fn load<'de, P, T>(path: P) -> Result<T, io::Error>
+pub fn save_with<P, T>(path: P, data: T, options: &SaveOptions) -> Result<usize, StorageError>
 where
     P: AsRef<Path>,
-    T: DeserializeOwned,
+    T: Serialize,
 {
-    let mut file = std::fs::File::open(path)?;
-    let mut json_data = String::new();
-    file.read_to_string(&mut json_data)?;
+    let path = path.as_ref();
+    let operation = Operation::start("save", path);
 
-    let data: T = serde_json::from_str(&json_data)?;
+    let result = write_atomic_with(path, options, |writer| write_json(writer, &data, options));
+    if let Ok(bytes) = result {
+        operation.record_bytes(bytes);
+    }
 
-    Ok(data)
+    operation.finish(result)
 }
 
-/// Asynchronously loads data from a JSON file.
+/// Asynchronously saves data to a JSON file using the given options.
 ///
-/// This async function reads a JSON file using tokio's async file I/O and deserializes
-/// its contents into the specified type. Deserialization is performed on a blocking task
-/// to avoid blocking the async runtime.
+/// This is the configurable form of [`save_async`]; see [`SaveOptions`] for the available
+/// settings.
 ///
 /// # Arguments
 ///
-/// * `path` - The file path to read from
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`, `Send`, and `'static`)
+/// * `options` - Controls how the data is serialized and written
 ///
 /// # Returns
 ///
-/// * `Ok(T)` - The deserialized data if successful
-/// * `Err(io::Error)` - If file reading fails or if deserialization fails
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If file creation, writing fails, or if serialization fails
 ///
 /// # Example
 ///
 /// ```
-/// use storage_service::{save_async, load_async};
-/// use serde::{Serialize, Deserialize};
+/// use storage_service::{save_with_async, SaveOptions};
 /// use tempfile::NamedTempFile;
 ///
-/// #[derive(Serialize, Deserialize, Clone, Debug)]
-/// struct Settings {
-///     theme: String,
-///     language: String,
-/// }
-///
 /// #[tokio::main]
 /// async fn main() {
 ///     let temp_file = NamedTempFile::new().unwrap();
-///     let original = Settings {
-///         theme: "dark".to_string(),
-///         language: "en".to_string(),
-///     };
+///     let options = SaveOptions::new().pretty(true);
 ///
-///     save_async(temp_file.path(), original.clone()).await.unwrap();
-///     let loaded: Settings = load_async(temp_file.path()).await.unwrap();
-///     assert_eq!(loaded.theme, "dark");
+///     save_with_async(temp_file.path(), vec![1, 2, 3], &options).await.unwrap();
 /// }
 /// ```
-pub async fn load_async<'de, P, T>(path: P) -> Result<T, io::Error>
+#[cfg(feature = "async")]
+pub async fn save_with_async<P, T>(
+    path: P,
+    data: T,
+    options: &SaveOptions,
+) -> Result<usize, StorageError>
 where
     P: AsRef<Path>,
-    T: DeserializeOwned + Send + 'static,
+    T: Serialize + Send + 'static,
 {
-    let mut file = tokio::fs::File::open(path).await?;
-    let mut json_data = String::new();
-    file.read_to_string(&mut json_data).await?;
+    let path = path.as_ref();
+    let operation = Operation::start("save_async", path);
+    let serialize_options = options.clone();
 
-    let data: T = tokio::task::spawn_blocking(move || serde_json::from_str(&json_data)).await??;
+    let result = write_atomic_with_async(path, options, move |writer| {
+        write_json(writer, &data, &serialize_options)
+    })
+    .await;
+    if let Ok(bytes) = result {
+        operation.record_bytes(bytes);
+    }
 
-    Ok(data)
+    operation.finish(result)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use tempfile::NamedTempFile;
+/// Synchronously saves data to a pretty-printed JSON file.
+///
+/// The output is indented for readability and ends with a trailing newline, which makes it
+/// suitable for human-edited files and clean diffs.
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If file creation or writing fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::save_pretty;
+/// use serde::Serialize;
+/// use tempfile::NamedTempFile;
+///
+/// #[derive(Serialize)]
+/// struct Settings {
+///     theme: String,
+/// }
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// let settings = Settings {
+///     theme: "dark".to_string(),
+/// };
+///
+/// save_pretty(temp_file.path(), &settings).unwrap();
+/// let content = std::fs::read_to_string(temp_file.path()).unwrap();
+/// assert!(content.ends_with("}\n"));
+/// ```
+pub fn save_pretty<P, T>(path: P, data: T) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    save_with(path, data, &SaveOptions::new().pretty(true))
+}
 
-    #[derive(Serialize, serde::Deserialize, Debug, PartialEq)]
-    struct TestData {
-        name: String,
-        value: i32,
-    }
+/// Synchronously saves data to a pretty-printed JSON file with the given indentation.
+///
+/// Like [`save_pretty`], but each nesting level is indented with `indent` instead of two
+/// spaces, for example `b"    "` or `b"\t"`, so stored files can follow the conventions of the
+/// repository they live in. Combine [`JsonOptions::indent`] with other options through
+/// [`save_with`].
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+/// * `indent` - The spaces and tabs written once per nesting level
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - With an [`io::ErrorKind::InvalidInput`] error if `indent` contains
+///   anything other than spaces and tabs, or if file creation, writing or serialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::save_pretty_with;
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save_pretty_with(temp_file.path(), vec![1, 2], b"\t").unwrap();
+///
+/// let content = std::fs::read_to_string(temp_file.path()).unwrap();
+/// assert_eq!(content, "[\n\t1,\n\t2\n]\n");
+/// ```
+pub fn save_pretty_with<P, T>(path: P, data: T, indent: &[u8]) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    let json = JsonOptions::new()
+        .pretty(true)
+        .trailing_newline(true)
+        .indent(indent);
 
-    #[test]
-    fn save_test() {
-        let temp_file = NamedTempFile::new().unwrap();
-        let path = temp_file.path();
+    save_with(path, data, &json.into())
+}
 
-        let data = TestData {
-            name: "test".to_string(),
-            value: 42,
-        };
+/// Synchronously saves data to a JSON file with the keys of every object sorted.
+///
+/// The same bytes are written for equal data no matter the field order of its structs or the
+/// iteration order of its maps, which makes the output suitable for golden-file tests and
+/// content-addressed storage. See [`JsonOptions::sort_keys`] for the trade-offs.
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If file creation or writing fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use storage_service::save_sorted;
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// let scores = HashMap::from([("carol", 3), ("alice", 1), ("bob", 2)]);
+///
+/// save_sorted(temp_file.path(), &scores).unwrap();
+/// let content = std::fs::read_to_string(temp_file.path()).unwrap();
+/// assert_eq!(content, r#"{"alice":1,"bob":2,"carol":3}"#);
+/// ```
+pub fn save_sorted<P, T>(path: P, data: T) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    save_with(path, data, &JsonOptions::new().sort_keys(true).into())
+}
 
-        let result = save(path, &data);
-        assert!(result.is_ok());
+/// Asynchronously saves data to a pretty-printed JSON file.
+///
+/// The async counterpart of [`save_pretty`].
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`, `Send`, and `'static`)
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If file creation, writing fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::save_pretty_async;
+/// use tempfile::NamedTempFile;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let temp_file = NamedTempFile::new().unwrap();
+///
+///     save_pretty_async(temp_file.path(), vec!["a", "b"]).await.unwrap();
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub async fn save_pretty_async<P, T>(path: P, data: T) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize + Send + 'static,
+{
+    save_with_async(path, data, &SaveOptions::new().pretty(true)).await
+}
 
-        let content = std::fs::read_to_string(path).unwrap();
-        assert!(content.contains("test"));
+/// Synchronously saves data to a JSON file created with the given Unix permissions.
+///
+/// This is meant for files holding secrets, which should not end up world-readable under the
+/// default umask. The permissions are applied to the file before any data is written. On
+/// non-Unix platforms `mode` is ignored and this behaves like [`save`].
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+/// * `mode` - The Unix permission bits of the file, for example `0o600`
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If file creation, setting permissions or writing fails, or if
+///   serialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::save_with_permissions;
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let path = dir.path().join("token.json");
+///
+/// save_with_permissions(&path, "secret", 0o600).unwrap();
+///
+/// #[cfg(unix)]
+/// {
+///     use std::os::unix::fs::PermissionsExt;
+///     let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+///     assert_eq!(mode & 0o777, 0o600);
+/// }
+/// ```
+pub fn save_with_permissions<P, T>(path: P, data: T, mode: u32) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    save_with(path, data, &SaveOptions::new().mode(mode))
+}
+
+/// Synchronously saves data to a new JSON file, failing if the file already exists.
+///
+/// A shorthand for [`save_with`] with [`SaveOptions::create_new`], for files that must be
+/// created once and never clobbered, for example by another process racing to create them.
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the file was created
+/// * `Err(StorageError)` - [`StorageError::AlreadyExists`] if a file is already present at
+///   `path`, or if file creation or writing fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{StorageError, load, save_new};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let path = dir.path().join("claim.json");
+///
+/// save_new(&path, "first").unwrap();
+/// let second = save_new(&path, "second");
+/// assert!(matches!(second, Err(StorageError::AlreadyExists(_))));
+/// assert_eq!(load::<_, String>(&path).unwrap(), "first");
+/// ```
+pub fn save_new<P, T>(path: P, data: T) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    save_with(path, data, &SaveOptions::new().create_new(true))
+}
+
+/// Synchronously saves data to a JSON file only if its contents would change.
+///
+/// The data is serialized and compared byte-for-byte with the existing file; the file is only
+/// rewritten when they differ, which avoids needless disk writes and mtime updates that would
+/// wake up file watchers. Because the comparison is done on the serialized bytes, it works for
+/// any `Serialize` type.
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+///
+/// # Returns
+///
+/// * `Ok(true)` - If the file was written
+/// * `Ok(false)` - If the file already contained exactly this data
+/// * `Err(StorageError)` - If reading the existing file, file creation or writing fails, or if
+///   serialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::save_if_changed;
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let path = dir.path().join("config.json");
+///
+/// assert!(save_if_changed(&path, "dark").unwrap());
+/// assert!(!save_if_changed(&path, "dark").unwrap());
+/// assert!(save_if_changed(&path, "light").unwrap());
+/// ```
+pub fn save_if_changed<P, T>(path: P, data: T) -> Result<bool, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    let path = path.as_ref();
+
+    let mut json_data = Vec::new();
+    write_json(&mut json_data, &data, &SaveOptions::default())?;
+
+    match std::fs::read(path) {
+        Ok(existing) if existing == json_data => return Ok(false),
+        Ok(_) => {}
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(StorageError::Io(err)),
+    }
+
+    write_atomic(path, |writer| Ok(writer.write_all(&json_data)?))?;
+
+    Ok(true)
+}
+
+/// Synchronously saves the items of an iterator as a JSON array.
+///
+/// The items are serialized one at a time as the iterator yields them and streamed into a
+/// buffered writer, so peak memory is a single item plus the buffer rather than the whole
+/// collection. The file is written compactly, like [`save`], and can be read back with
+/// [`load`] into a `Vec<T>`.
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `iter` - The items to serialize and save (each must implement `Serialize`)
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If file creation or writing fails, or if serialization of any item
+///   fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save_iter, load};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+///
+/// save_iter(temp_file.path(), (1..=3).map(|n| n * 10)).unwrap();
+///
+/// let loaded: Vec<i32> = load(temp_file.path()).unwrap();
+/// assert_eq!(loaded, vec![10, 20, 30]);
+/// ```
+pub fn save_iter<P, I, T>(path: P, iter: I) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    I: IntoIterator<Item = T>,
+    T: Serialize,
+{
+    write_atomic(path.as_ref(), |writer| {
+        let mut writer = CountingWriter::new(writer);
+
+        writer.write_all(b"[")?;
+        for (index, item) in iter.into_iter().enumerate() {
+            if index > 0 {
+                writer.write_all(b",")?;
+            }
+            serde_json::to_writer(&mut writer, &item).map_err(StorageError::from_serialize)?;
+        }
+        writer.write_all(b"]")?;
+
+        Ok(writer.count)
+    })
+}
+
+/// Serializes data as JSON into any writer.
+///
+/// This produces the same compact output as [`save`], but leaves opening, syncing and closing
+/// the destination to the caller, so it works with a caller-configured [`File`](std::fs::File),
+/// an in-memory buffer, a socket, or a compression stream. Unlike [`save`], nothing is written
+/// atomically and the writer is not flushed; wrap unbuffered writers in a
+/// [`BufWriter`](io::BufWriter) for large payloads.
+///
+/// # Arguments
+///
+/// * `writer` - The destination to write the JSON to
+/// * `data` - The data to serialize (must implement `Serialize`)
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If writing fails or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::save_to_writer;
+///
+/// let mut buffer = Vec::new();
+/// let written = save_to_writer(&mut buffer, &vec![1, 2, 3]).unwrap();
+///
+/// assert_eq!(buffer, b"[1,2,3]");
+/// assert_eq!(written, buffer.len());
+/// ```
+pub fn save_to_writer<W, T>(writer: &mut W, data: &T) -> Result<usize, StorageError>
+where
+    W: Write + ?Sized,
+    T: Serialize + ?Sized,
+{
+    write_json(writer, data, &SaveOptions::default())
+}
+
+/// Deserializes JSON data from any reader.
+///
+/// The reader is consumed to its end before parsing, so errors carry positions just like those
+/// of [`load`], and trailing data after the JSON value is rejected.
+///
+/// # Arguments
+///
+/// * `reader` - The source to read the JSON from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - If reading fails or if deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// use storage_service::load_from_reader;
+///
+/// let loaded: Vec<u32> = load_from_reader(Cursor::new("[4, 5, 6]")).unwrap();
+/// assert_eq!(loaded, vec![4, 5, 6]);
+/// ```
+pub fn load_from_reader<R, T>(mut reader: R) -> Result<T, StorageError>
+where
+    R: Read,
+    T: DeserializeOwned,
+{
+    let mut json_data = Vec::new();
+    reader.read_to_end(&mut json_data)?;
+
+    parse_json(&json_data)
+}
+
+/// Synchronously saves data as JSON into a file the caller has already opened.
+///
+/// The file is rewritten from the start and truncated to the new contents, then synced to
+/// disk. Because the path is never reopened, a lock the caller holds on the file (for example
+/// an advisory lock taken with `fs2` or `fd-lock`) stays in effect across a [`load_from`] and
+/// the following save. Unlike [`save`], the file is overwritten in place rather than
+/// atomically replaced; the data is serialized before anything is written, so a serialization
+/// error leaves the file untouched, but a crash mid-write can leave it truncated.
+///
+/// # Arguments
+///
+/// * `file` - The open file to overwrite (must be opened for writing)
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If seeking, writing or syncing fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use std::fs::OpenOptions;
+/// use storage_service::{load_from, save_into};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// let mut file = OpenOptions::new().read(true).write(true).open(temp_file.path()).unwrap();
+///
+/// save_into(&mut file, &vec![1, 2, 3]).unwrap();
+/// let mut numbers: Vec<u32> = load_from(&mut file).unwrap();
+/// numbers.push(4);
+/// save_into(&mut file, &numbers).unwrap();
+///
+/// assert_eq!(std::fs::read_to_string(temp_file.path()).unwrap(), "[1,2,3,4]");
+/// ```
+pub fn save_into<T>(file: &mut File, data: &T) -> Result<usize, StorageError>
+where
+    T: Serialize + ?Sized,
+{
+    let mut json_data = Vec::new();
+    let written = write_json(&mut json_data, data, &SaveOptions::default())?;
+
+    file.seek(SeekFrom::Start(0))?;
+    file.write_all(&json_data)?;
+    file.set_len(json_data.len() as u64)?;
+    file.sync_data()?;
+
+    Ok(written)
+}
+
+/// Synchronously loads data from a JSON file the caller has already opened.
+///
+/// The file is read from the start regardless of its current position, so it can be called
+/// repeatedly on the same handle, for example between calls to [`save_into`] while holding a
+/// lock on the file.
+///
+/// # Arguments
+///
+/// * `file` - The open file to read from (must be opened for reading)
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - If seeking or reading fails, or if deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use std::fs::File;
+/// use storage_service::{load_from, save};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save(temp_file.path(), "locked").unwrap();
+///
+/// let mut file = File::open(temp_file.path()).unwrap();
+/// let loaded: String = load_from(&mut file).unwrap();
+/// assert_eq!(loaded, "locked");
+/// ```
+pub fn load_from<T>(file: &mut File) -> Result<T, StorageError>
+where
+    T: DeserializeOwned,
+{
+    file.seek(SeekFrom::Start(0))?;
+
+    load_from_reader(file)
+}
+
+/// Synchronously loads data from a JSON file.
+///
+/// This function reads a JSON file and deserializes its contents into the specified type.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - If file reading fails or if deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save, load};
+/// use serde::{Serialize, Deserialize};
+/// use tempfile::NamedTempFile;
+///
+/// #[derive(Serialize, Deserialize, PartialEq, Debug)]
+/// struct Person {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// let original = Person {
+///     name: "Bob".to_string(),
+///     age: 25,
+/// };
+///
+/// save(temp_file.path(), &original).unwrap();
+/// let loaded: Person = load(temp_file.path()).unwrap();
+/// assert_eq!(loaded, original);
+/// ```
+pub fn load<P, T>(path: P) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    let path = path.as_ref();
+    let operation = Operation::start("load", path);
+
+    let result = std::fs::read(path)
+        .map_err(StorageError::from)
+        .and_then(|json_data| {
+            operation.record_bytes(json_data.len());
+            parse_file(path, &json_data)
+        });
+
+    operation.finish(result)
+}
+
+/// Asynchronously loads data from a JSON file.
+///
+/// This async function reads a JSON file as raw bytes using tokio's async file I/O and
+/// deserializes its contents into the specified type. Deserialization is performed on a blocking task
+/// to avoid blocking the async runtime.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - If file reading fails or if deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save_async, load_async};
+/// use serde::{Serialize, Deserialize};
+/// use tempfile::NamedTempFile;
+///
+/// #[derive(Serialize, Deserialize, Clone, Debug)]
+/// struct Settings {
+///     theme: String,
+///     language: String,
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let temp_file = NamedTempFile::new().unwrap();
+///     let original = Settings {
+///         theme: "dark".to_string(),
+///         language: "en".to_string(),
+///     };
+///
+///     save_async(temp_file.path(), original.clone()).await.unwrap();
+///     let loaded: Settings = load_async(temp_file.path()).await.unwrap();
+///     assert_eq!(loaded.theme, "dark");
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub async fn load_async<P, T>(path: P) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned + Send + 'static,
+{
+    let path = path.as_ref();
+    let operation = Operation::start("load_async", path);
+
+    let result = async {
+        let json_data = tokio::fs::read(path).await?;
+        operation.record_bytes(json_data.len());
+
+        let path = path.to_path_buf();
+        tokio::task::spawn_blocking(move || parse_file(&path, &json_data)).await?
+    }
+    .await;
+
+    operation.finish(result)
+}
+
+/// Synchronously loads data from a JSON file together with the file's modification time.
+///
+/// The modification time is read from the opened file before its contents, so it can never be
+/// newer than the data returned: if the file is replaced in between, the returned time is
+/// older than the file on disk and a later "reload if newer" check loads it again, rather than
+/// the update being missed. This takes no more system calls than [`load`] followed by a
+/// separate [`std::fs::metadata`], without the race between the two.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok((T, SystemTime))` - The deserialized data and the file's modification time
+/// * `Err(StorageError)` - If file reading fails, if the platform does not record modification
+///   times, or if deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{load_with_meta, save};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save(temp_file.path(), "cached").unwrap();
+///
+/// let (value, modified) = load_with_meta::<_, String>(temp_file.path()).unwrap();
+/// assert_eq!(value, "cached");
+///
+/// let current = std::fs::metadata(temp_file.path()).unwrap().modified().unwrap();
+/// let is_newer = current > modified;
+/// assert!(!is_newer);
+/// ```
+pub fn load_with_meta<P, T>(path: P) -> Result<(T, SystemTime), StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    let path = path.as_ref();
+    let mut file = File::open(path)?;
+    let metadata = file.metadata()?;
+    let modified = metadata.modified()?;
+
+    let mut json_data = Vec::with_capacity(usize::try_from(metadata.len()).unwrap_or_default());
+    file.read_to_end(&mut json_data)?;
+
+    Ok((parse_file(path, &json_data)?, modified))
+}
+
+/// Asynchronously loads data from a JSON file together with the file's modification time.
+///
+/// The async counterpart of [`load_with_meta`]: the modification time is read from the opened
+/// file before its contents, and deserialization is performed on a blocking task.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok((T, SystemTime))` - The deserialized data and the file's modification time
+/// * `Err(StorageError)` - If file reading fails, if the platform does not record modification
+///   times, or if deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{load_async_with_meta, save};
+/// use tempfile::NamedTempFile;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let temp_file = NamedTempFile::new().unwrap();
+///     save(temp_file.path(), 7).unwrap();
+///
+///     let (value, _modified) = load_async_with_meta::<_, u32>(temp_file.path()).await.unwrap();
+///     assert_eq!(value, 7);
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub async fn load_async_with_meta<P, T>(path: P) -> Result<(T, SystemTime), StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned + Send + 'static,
+{
+    use tokio::io::AsyncReadExt;
+
+    let path = path.as_ref();
+    let mut file = tokio::fs::File::open(path).await?;
+    let metadata = file.metadata().await?;
+    let modified = metadata.modified()?;
+
+    let mut json_data = Vec::with_capacity(usize::try_from(metadata.len()).unwrap_or_default());
+    file.read_to_end(&mut json_data).await?;
+
+    let path = path.to_path_buf();
+    let data = tokio::task::spawn_blocking(move || parse_file(&path, &json_data)).await??;
+
+    Ok((data, modified))
+}
+
+/// Synchronously loads data from a JSON file and validates it.
+///
+/// After deserialization, `validate` is called on the value; if it returns an error, that
+/// error is reported as [`StorageError::Validation`]. This keeps "parsed but semantically
+/// invalid" data distinct from data that could not be parsed at all.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+/// * `validate` - A check run on the deserialized value, returning a reason on failure
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized and validated data
+/// * `Err(StorageError)` - [`StorageError::Validation`] if `validate` rejects the data, or if
+///   file reading or deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{StorageError, save, load_validated};
+/// use serde::{Serialize, Deserialize};
+/// use tempfile::NamedTempFile;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Server {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save(temp_file.path(), Server { host: String::new(), port: 8080 }).unwrap();
+///
+/// let result = load_validated(temp_file.path(), |server: &Server| {
+///     if server.host.is_empty() {
+///         return Err("host must not be empty".to_string());
+///     }
+///     Ok(())
+/// });
+/// assert!(matches!(result, Err(StorageError::Validation(_))));
+/// ```
+pub fn load_validated<P, T, F>(path: P, validate: F) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+    F: FnOnce(&T) -> Result<(), String>,
+{
+    let data = load(path)?;
+    validate(&data).map_err(StorageError::Validation)?;
+
+    Ok(data)
+}
+
+/// Synchronously loads data from a JSON file, refusing files larger than `max_bytes`.
+///
+/// The size is checked against the file's metadata before anything is read, and the read itself
+/// is capped, so a file that grows in the meantime cannot bypass the limit either. Use this
+/// instead of [`load`] for storage locations that are not fully trusted.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+/// * `max_bytes` - The largest file size, in bytes, that will be read
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - [`StorageError::TooLarge`] if the file exceeds `max_bytes`, or if
+///   file reading or deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{StorageError, save, load_limited};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save(temp_file.path(), vec![0u8; 100]).unwrap();
+///
+/// let result = load_limited::<_, Vec<u8>>(temp_file.path(), 16);
+/// assert!(matches!(result, Err(StorageError::TooLarge { limit: 16, .. })));
+/// ```
+pub fn load_limited<P, T>(path: P, max_bytes: u64) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    let path = path.as_ref();
+    let file = std::fs::File::open(path)?;
+    let size = file.metadata()?.len();
+    if size > max_bytes {
+        return Err(StorageError::TooLarge {
+            limit: max_bytes,
+            size,
+        });
+    }
+
+    let mut json_data = Vec::with_capacity(size as usize);
+    file.take(max_bytes.saturating_add(1))
+        .read_to_end(&mut json_data)?;
+    if json_data.len() as u64 > max_bytes {
+        return Err(StorageError::TooLarge {
+            limit: max_bytes,
+            size: json_data.len() as u64,
+        });
+    }
+
+    parse_file(path, &json_data)
+}
+
+/// Synchronously loads data from a JSON file, falling back to `T::default()` if it is missing.
+///
+/// Only a missing file is treated as "no data yet"; any other I/O error and any
+/// deserialization error is still returned to the caller.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data, or `T::default()` if the file does not exist
+/// * `Err(StorageError)` - If file reading fails for another reason or if deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::load_or_default;
+/// use serde::Deserialize;
+/// use tempfile::tempdir;
+///
+/// #[derive(Deserialize, Default, Debug, PartialEq)]
+/// struct Counter {
+///     hits: u64,
+/// }
+///
+/// let dir = tempdir().unwrap();
+/// let counter: Counter = load_or_default(dir.path().join("counter.json")).unwrap();
+/// assert_eq!(counter, Counter::default());
+/// ```
+pub fn load_or_default<P, T>(path: P) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned + Default,
+{
+    try_load(path).map(Option::unwrap_or_default)
+}
+
+/// Asynchronously loads data from a JSON file, falling back to `T::default()` if it is missing.
+///
+/// The async counterpart of [`load_or_default`].
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data, or `T::default()` if the file does not exist
+/// * `Err(StorageError)` - If file reading fails for another reason or if deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::load_or_default_async;
+/// use tempfile::tempdir;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let dir = tempdir().unwrap();
+///     let names: Vec<String> = load_or_default_async(dir.path().join("names.json"))
+///         .await
+///         .unwrap();
+///     assert!(names.is_empty());
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub async fn load_or_default_async<P, T>(path: P) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned + Default + Send + 'static,
+{
+    try_load_async(path).await.map(Option::unwrap_or_default)
+}
+
+/// Synchronously loads data from a JSON file, returning `None` if it is missing.
+///
+/// Only a missing file is treated as "no data"; any other I/O error and any deserialization
+/// error is still returned to the caller.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(Some(T))` - The deserialized data if the file exists
+/// * `Ok(None)` - If the file does not exist
+/// * `Err(StorageError)` - If file reading fails for another reason or if deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save, try_load};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let path = dir.path().join("session.json");
+/// assert_eq!(try_load::<_, String>(&path).unwrap(), None);
+///
+/// save(&path, "token").unwrap();
+/// assert_eq!(try_load(&path).unwrap(), Some("token".to_string()));
+/// ```
+pub fn try_load<P, T>(path: P) -> Result<Option<T>, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    match load(path) {
+        Ok(data) => Ok(Some(data)),
+        Err(StorageError::Io(err)) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Asynchronously loads data from a JSON file, returning `None` if it is missing.
+///
+/// The async counterpart of [`try_load`].
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(Some(T))` - The deserialized data if the file exists
+/// * `Ok(None)` - If the file does not exist
+/// * `Err(StorageError)` - If file reading fails for another reason or if deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::try_load_async;
+/// use tempfile::tempdir;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let dir = tempdir().unwrap();
+///     let loaded: Option<Vec<u8>> = try_load_async(dir.path().join("missing.json"))
+///         .await
+///         .unwrap();
+///     assert!(loaded.is_none());
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub async fn try_load_async<P, T>(path: P) -> Result<Option<T>, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned + Send + 'static,
+{
+    match load_async(path).await {
+        Ok(data) => Ok(Some(data)),
+        Err(StorageError::Io(err)) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Synchronously loads data from the first of several candidate JSON files that exists.
+///
+/// The paths are tried in order and missing files are skipped, which suits configuration that
+/// may live in several locations of decreasing priority. A file that exists but cannot be read
+/// or parsed stops the search, so a broken high-priority file is reported rather than silently
+/// shadowed by a lower-priority one.
+///
+/// # Arguments
+///
+/// * `paths` - The candidate file paths, highest priority first
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data of the first existing file
+/// * `Err(StorageError)` - [`StorageError::NotFound`] listing every path if none exist, or if
+///   reading or deserializing the first existing file fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save, load_first};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let local = dir.path().join("config.json");
+/// let global = dir.path().join("global.json");
+/// save(&global, "global").unwrap();
+///
+/// let loaded: String = load_first(&[&local, &global]).unwrap();
+/// assert_eq!(loaded, "global");
+/// ```
+pub fn load_first<P, T>(paths: &[P]) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    for path in paths {
+        if let Some(data) = try_load(path)? {
+            return Ok(data);
+        }
+    }
+
+    let tried = paths.iter().map(|path| path.as_ref().to_path_buf());
+    Err(StorageError::NotFound(tried.collect()))
+}
+
+/// Synchronously saves an untyped JSON value to a file.
+///
+/// A thin wrapper around [`save`] for dynamic-JSON workflows, such as arrays of records of
+/// different shapes assembled with [`serde_json::json!`]; the file is written atomically in the
+/// same way.
+///
+/// # Arguments
+///
+/// * `path` - The file path where the value will be saved
+/// * `value` - The JSON value to save
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If file creation or writing fails
+///
+/// # Example
+///
+/// ```
+/// use serde_json::json;
+/// use storage_service::{load_value, save_value};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// let records = json!([{ "kind": "user", "name": "Ada" }, { "kind": "event", "at": 1700000000 }]);
+/// save_value(temp_file.path(), &records).unwrap();
+///
+/// assert_eq!(load_value(temp_file.path()).unwrap(), records);
+/// ```
+pub fn save_value<P>(path: P, value: &Value) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+{
+    save(path, value)
+}
+
+/// Synchronously loads a JSON file as an untyped value.
+///
+/// A thin wrapper around [`load`] with the target type fixed to [`serde_json::Value`], so any
+/// well-formed JSON file loads regardless of its shape.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(Value)` - The parsed JSON value if successful
+/// * `Err(StorageError)` - If file reading fails, or if the file is not valid JSON
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{load_value, save};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save(temp_file.path(), vec![1, 2, 3]).unwrap();
+///
+/// let value = load_value(temp_file.path()).unwrap();
+/// assert_eq!(value[2], 3);
+/// ```
+pub fn load_value<P>(path: P) -> Result<Value, StorageError>
+where
+    P: AsRef<Path>,
+{
+    load(path)
+}
+
+/// Synchronously rewrites a JSON file in the format given by `options`.
+///
+/// The file is loaded as `T` and saved again through a temporary file, as by [`save_with`],
+/// so it is replaced atomically. This normalizes files that have picked up inconsistent
+/// formatting from manual edits or other writers; use [`JsonOptions::sort_keys`] and
+/// [`JsonOptions::pretty`] for a canonical form. Going through `T` also drops unknown fields
+/// and fills in defaults, so the rewritten file matches what the current program would save.
+/// To normalize JSON without a concrete type, use [`reformat`].
+///
+/// # Arguments
+///
+/// * `path` - The file to rewrite
+/// * `options` - The formatting and file options for the rewritten file
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written
+/// * `Err(StorageError)` - If loading or saving the file fails
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use storage_service::{JsonOptions, compact};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// std::fs::write(temp_file.path(), "{ \"b\": 2,\n\t\"a\":1 }").unwrap();
+///
+/// compact::<_, HashMap<String, u32>>(temp_file.path(), &JsonOptions::new().sort_keys(true).into())
+///     .unwrap();
+/// let content = std::fs::read_to_string(temp_file.path()).unwrap();
+/// assert_eq!(content, r#"{"a":1,"b":2}"#);
+/// ```
+pub fn compact<P, T>(path: P, options: &SaveOptions) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize + DeserializeOwned,
+{
+    let path = path.as_ref();
+    let data: T = load(path)?;
+
+    save_with(path, &data, options)
+}
+
+/// Synchronously rewrites any JSON file in the format given by `options`.
+///
+/// The untyped counterpart of [`compact`]: the file is loaded as a [`Value`] and saved again
+/// atomically, so no concrete type is needed and every field is kept. Object keys always come
+/// out sorted, because [`Value`] stores them sorted. Numbers that fit neither `u64`, `i64` nor
+/// `f64` exactly are rounded to the nearest `f64`.
+///
+/// # Arguments
+///
+/// * `path` - The file to rewrite
+/// * `options` - The formatting and file options for the rewritten file
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written
+/// * `Err(StorageError)` - If loading or saving the file fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{SaveOptions, reformat};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// std::fs::write(temp_file.path(), r#"{"z":[1,2],"a":null}"#).unwrap();
+///
+/// reformat(temp_file.path(), &SaveOptions::new().pretty(true)).unwrap();
+/// let content = std::fs::read_to_string(temp_file.path()).unwrap();
+/// assert_eq!(content, "{\n  \"a\": null,\n  \"z\": [\n    1,\n    2\n  ]\n}\n");
+/// ```
+pub fn reformat<P>(path: P, options: &SaveOptions) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+{
+    compact::<_, Value>(path, options)
+}
+
+/// Synchronously deletes a stored file.
+///
+/// # Arguments
+///
+/// * `path` - The path of the file to delete
+///
+/// # Returns
+///
+/// * `Ok(())` - If the file was removed
+/// * `Err(StorageError)` - With an [`io::ErrorKind::NotFound`] error naming the path if the file
+///   does not exist, or any other I/O error raised while removing it
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save, delete};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let path = dir.path().join("session.json");
+/// save(&path, "data").unwrap();
+///
+/// delete(&path).unwrap();
+/// assert!(!path.exists());
+/// assert!(delete(&path).is_err());
+/// ```
+pub fn delete<P>(path: P) -> Result<(), StorageError>
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+
+    std::fs::remove_file(path).map_err(|err| not_found_error(path, err))
+}
+
+/// Synchronously deletes a stored file, treating a missing file as success.
+///
+/// This is the idempotent form of [`delete`], which is usually what cleanup code wants.
+///
+/// # Arguments
+///
+/// * `path` - The path of the file to delete
+///
+/// # Returns
+///
+/// * `Ok(true)` - If the file was removed
+/// * `Ok(false)` - If there was no file to remove
+/// * `Err(StorageError)` - If removing the file fails for another reason
+///
+/// # Example
+///
+/// ```
+/// use storage_service::delete_if_exists;
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let removed = delete_if_exists(dir.path().join("missing.json")).unwrap();
+/// assert!(!removed);
+/// ```
+pub fn delete_if_exists<P>(path: P) -> Result<bool, StorageError>
+where
+    P: AsRef<Path>,
+{
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(true),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(err) => Err(StorageError::Io(err)),
+    }
+}
+
+/// Returns whether a stored file exists at `path`.
+///
+/// Unlike [`Path::exists`], this is `true` only for regular files (following symlinks), so a
+/// directory at `path` is not mistaken for something [`load`] could read.
+///
+/// # Arguments
+///
+/// * `path` - The path to check
+///
+/// # Returns
+///
+/// `true` if `path` is a regular file, `false` otherwise or if it cannot be inspected.
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save, exists};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let path = dir.path().join("config.json");
+/// assert!(!exists(&path));
+/// assert!(!exists(dir.path()));
+///
+/// save(&path, "data").unwrap();
+/// assert!(exists(&path));
+/// ```
+pub fn exists<P>(path: P) -> bool
+where
+    P: AsRef<Path>,
+{
+    path.as_ref().is_file()
+}
+
+/// Returns whether the file at `path` is empty.
+///
+/// An empty file holds no data yet (for example after an interrupted first write) and would
+/// otherwise only surface as a deserialization error from [`load`].
+///
+/// # Arguments
+///
+/// * `path` - The file to check
+///
+/// # Returns
+///
+/// * `Ok(bool)` - Whether the file has a length of zero
+/// * `Err(StorageError)` - If the file's metadata cannot be read, for example because it does
+///   not exist
+///
+/// # Example
+///
+/// ```
+/// use storage_service::is_empty;
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// assert!(is_empty(temp_file.path()).unwrap());
+/// ```
+pub fn is_empty<P>(path: P) -> Result<bool, StorageError>
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    let metadata = std::fs::metadata(path).map_err(|err| not_found_error(path, err))?;
+
+    Ok(metadata.len() == 0)
+}
+
+/// Reads up to `len` raw bytes of the file at `path`, starting at byte `offset`.
+///
+/// This is a low-level escape hatch for files too large to load whole, such as indexed blob
+/// storage built from concatenated records: only the requested range is read, and nothing
+/// is deserialized. If fewer than `len` bytes follow `offset`, the bytes up to the end of the
+/// file are returned.
+///
+/// # Arguments
+///
+/// * `path` - The file to read from
+/// * `offset` - The position of the first byte to read
+/// * `len` - The maximum number of bytes to read
+///
+/// # Returns
+///
+/// * `Ok(Vec<u8>)` - The bytes read, `len` long unless the end of the file came first
+/// * `Err(io::Error)` - With [`io::ErrorKind::InvalidInput`] if `offset` is past the end of
+///   the file, or any I/O error raised while opening or reading it
+///
+/// # Example
+///
+/// ```
+/// use storage_service::read_range;
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// std::fs::write(temp_file.path(), b"headerPAYLOADfooter").unwrap();
+///
+/// assert_eq!(read_range(temp_file.path(), 6, 7).unwrap(), b"PAYLOAD");
+/// assert_eq!(read_range(temp_file.path(), 13, 100).unwrap(), b"footer");
+/// assert!(read_range(temp_file.path(), 20, 1).is_err());
+/// ```
+pub fn read_range<P>(path: P, offset: u64, len: usize) -> Result<Vec<u8>, io::Error>
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    let mut file = File::open(path)?;
+
+    let file_len = file.metadata()?.len();
+    if offset > file_len {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "offset {offset} is past the end of {} ({file_len} bytes)",
+                path.display()
+            ),
+        ));
+    }
+
+    let available = usize::try_from(file_len - offset).unwrap_or(usize::MAX);
+    let mut bytes = Vec::with_capacity(len.min(available));
+    file.seek(SeekFrom::Start(offset))?;
+    file.take(len as u64).read_to_end(&mut bytes)?;
+
+    Ok(bytes)
+}
+
+/// Synchronously copies a stored file to a new location.
+///
+/// Parent directories of `to` are created as needed. The copy is written through a temporary
+/// file and moved into place, so a failed copy never leaves a truncated file at `to` and an
+/// existing destination is never left half-written.
+///
+/// # Arguments
+///
+/// * `from` - The file to copy
+/// * `to` - The destination path
+/// * `overwrite` - Whether an existing file at `to` may be replaced
+///
+/// # Returns
+///
+/// * `Ok(u64)` - The number of bytes copied
+/// * `Err(StorageError)` - With an [`io::ErrorKind::NotFound`] error naming `from` if it does
+///   not exist, [`StorageError::AlreadyExists`] if `to` exists and `overwrite` is `false`, or
+///   any other I/O error raised while copying
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save, copy, load};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let original = dir.path().join("session.json");
+/// let archived = dir.path().join("archive").join("session.json");
+/// save(&original, 1).unwrap();
+///
+/// copy(&original, &archived, false).unwrap();
+/// let loaded: u32 = load(&archived).unwrap();
+/// assert_eq!(loaded, 1);
+/// assert!(copy(&original, &archived, false).is_err());
+/// ```
+pub fn copy<P, Q>(from: P, to: Q, overwrite: bool) -> Result<u64, StorageError>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let (from, to) = (from.as_ref(), to.as_ref());
+
+    let mut source = std::fs::File::open(from).map_err(|err| not_found_error(from, err))?;
+    let options = SaveOptions::new().create_dirs(true).create_new(!overwrite);
+
+    write_atomic_with(to, &options, |writer| Ok(io::copy(&mut source, writer)?))
+}
+
+/// Synchronously moves a stored file to a new location.
+///
+/// Parent directories of `to` are created as needed, and an existing file at `to` is replaced.
+/// The move is a filesystem rename, so it is atomic but cannot cross filesystems.
+///
+/// # Arguments
+///
+/// * `from` - The file to move
+/// * `to` - The destination path
+///
+/// # Returns
+///
+/// * `Ok(())` - If the file was moved
+/// * `Err(StorageError)` - With an [`io::ErrorKind::NotFound`] error naming `from` if it does
+///   not exist, or any other I/O error raised while moving it
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save, rename};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let session = dir.path().join("session.json");
+/// let archived = dir.path().join("archive").join("session.json");
+/// save(&session, 1).unwrap();
+///
+/// rename(&session, &archived).unwrap();
+/// assert!(!session.exists());
+/// assert!(archived.exists());
+/// ```
+pub fn rename<P, Q>(from: P, to: Q) -> Result<(), StorageError>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let (from, to) = (from.as_ref(), to.as_ref());
+
+    if !from.is_file() {
+        return Err(not_found_error(from, io::ErrorKind::NotFound.into()));
+    }
+    create_parent_dirs(to)?;
+
+    Ok(std::fs::rename(from, to)?)
+}
+
+/// Joins an untrusted relative file name onto `base`, refusing to escape it.
+///
+/// The name is normalized lexically: `.` components are dropped, while `..` components and
+/// absolute paths (including Windows drive prefixes) are rejected, so the result always lies
+/// inside `base`. Subdirectories such as `users/42.json` are allowed. The file system is not
+/// consulted, so a symbolic link inside `base` can still point elsewhere; keep such links out
+/// of directories that store untrusted names.
+///
+/// # Arguments
+///
+/// * `base` - The directory the result must stay within
+/// * `filename` - The relative file name, typically taken from user input
+///
+/// # Returns
+///
+/// * `Ok(PathBuf)` - `base` joined with the normalized file name
+/// * `Err(StorageError)` - [`io::ErrorKind::InvalidInput`] if the name is empty, absolute, or
+///   contains `..`
+///
+/// # Example
+///
+/// ```
+/// use std::path::Path;
+/// use storage_service::sanitize_filename;
+///
+/// let base = Path::new("/var/lib/app");
+///
+/// let path = sanitize_filename(base, "./users/42.json").unwrap();
+/// assert_eq!(path, Path::new("/var/lib/app/users/42.json"));
+///
+/// assert!(sanitize_filename(base, "../../etc/passwd").is_err());
+/// assert!(sanitize_filename(base, "/etc/passwd").is_err());
+/// ```
+pub fn sanitize_filename<P, Q>(base: P, filename: Q) -> Result<PathBuf, StorageError>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let filename = filename.as_ref();
+    let mut path = base.as_ref().to_path_buf();
+    let mut is_empty = true;
+
+    for component in filename.components() {
+        match component {
+            Component::Normal(part) => {
+                path.push(part);
+                is_empty = false;
+            }
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                return Err(invalid_filename(filename));
+            }
+        }
+    }
+
+    if is_empty {
+        return Err(invalid_filename(filename));
+    }
+
+    Ok(path)
+}
+
+/// Reports a file name rejected by [`sanitize_filename`].
+fn invalid_filename(filename: &Path) -> StorageError {
+    StorageError::Io(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("invalid file name: {}", filename.display()),
+    ))
+}
+
+/// Returns the directory containing `path`, using `.` for bare file names.
+fn parent_dir(path: &Path) -> &Path {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    }
+}
+
+/// Reports an `AlreadyExists` error raised while creating `path` as
+/// [`StorageError::AlreadyExists`].
+fn exists_error(path: &Path, err: io::Error) -> StorageError {
+    if err.kind() == io::ErrorKind::AlreadyExists {
+        StorageError::AlreadyExists(path.to_path_buf())
+    } else {
+        StorageError::Io(err)
+    }
+}
+
+/// Flushes the entries of `dir` to the storage device, making a rename into it durable.
+///
+/// This is a no-op on platforms where directories cannot be opened and synced.
+fn sync_dir(dir: &Path) -> Result<(), io::Error> {
+    #[cfg(unix)]
+    {
+        File::open(dir)?.sync_all()
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = dir;
+        Ok(())
+    }
+}
+
+/// Creates the parent directories of `path`.
+fn create_parent_dirs(path: &Path) -> Result<(), io::Error> {
+    std::fs::create_dir_all(parent_dir(path))
+}
+
+/// Explains a `NotFound` error raised while creating a file in a directory that does not exist.
+fn parent_error(path: &Path, err: io::Error) -> io::Error {
+    match err.kind() {
+        io::ErrorKind::NotFound => io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "parent directory does not exist: {}",
+                parent_dir(path).display()
+            ),
+        ),
+        _ => err,
+    }
+}
+
+/// Names the file in a `NotFound` error, which the OS error leaves out.
+fn not_found_error(path: &Path, err: io::Error) -> StorageError {
+    match err.kind() {
+        io::ErrorKind::NotFound => StorageError::Io(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("file not found: {}", path.display()),
+        )),
+        _ => StorageError::Io(err),
+    }
+}
+
+/// The UTF-8 byte-order mark some Windows tools write at the start of text files.
+pub(crate) const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Deserializes the contents of the file at `path`, naming the file in errors and reporting an
+/// empty file as [`StorageError::Empty`].
+fn parse_file<'a, T: Deserialize<'a>>(path: &Path, json_data: &'a [u8]) -> Result<T, StorageError> {
+    if json_data.is_empty() {
+        return Err(StorageError::Empty(path.to_path_buf()));
+    }
+
+    parse_json(json_data).map_err(|err| err.in_file(path))
+}
+
+/// Deserializes a JSON value from the raw bytes of a file.
+///
+/// Working on bytes rather than a `String` avoids a second full-size copy of the file and lets
+/// invalid UTF-8 surface as a positioned parse error rather than an I/O error. A leading UTF-8
+/// byte-order mark is skipped, and a UTF-16 one is called out in the error, since otherwise
+/// both only produce an opaque "expected value" error at the very first byte.
+fn parse_json<'a, T: Deserialize<'a>>(json_data: &'a [u8]) -> Result<T, StorageError> {
+    let json_data = json_data.strip_prefix(UTF8_BOM).unwrap_or(json_data);
+
+    serde_json::from_slice(json_data).map_err(|err| {
+        if json_data.starts_with(b"\xFF\xFE") || json_data.starts_with(b"\xFE\xFF") {
+            StorageError::Deserialize(serde::de::Error::custom(format!(
+                "{err} (the file starts with a UTF-16 byte-order mark; save it as UTF-8)"
+            )))
+        } else {
+            StorageError::Deserialize(err)
+        }
+    })
+}
+
+/// Serializes `data` as JSON into `writer` according to `options`, returning the number of
+/// bytes written.
+fn write_json<W, T>(writer: &mut W, data: &T, options: &SaveOptions) -> Result<usize, StorageError>
+where
+    W: Write + ?Sized,
+    T: Serialize + ?Sized,
+{
+    options.json.validate()?;
+    let mut writer = CountingWriter::new(writer);
+
+    options
+        .json
+        .write(&mut writer, data)
+        .map_err(StorageError::from_serialize)?;
+
+    if options.json.trailing_newline {
+        writer.write_all(b"\n")?;
+    }
+
+    Ok(writer.count)
+}
+
+/// A writer that counts the bytes passed through to the inner writer.
+struct CountingWriter<W> {
+    inner: W,
+    count: usize,
+}
+
+impl<W> CountingWriter<W> {
+    fn new(inner: W) -> Self {
+        Self { inner, count: 0 }
+    }
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.count += written;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Returns `path` with `suffix` appended to its file name, e.g. `config.json.bak`.
+fn with_suffix(path: &Path, suffix: impl AsRef<OsStr>) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(suffix);
+
+    path.with_file_name(file_name)
+}
+
+/// Builds a unique temporary path next to `path`.
+///
+/// Keeping the temporary file in the destination directory guarantees that the final rename
+/// happens within one filesystem and never degrades into a cross-device copy.
+fn temp_path(path: &Path) -> PathBuf {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let unique = COUNTER.fetch_add(1, Ordering::Relaxed);
+
+    path.with_file_name(format!(".{file_name}.{}.{unique}.tmp", std::process::id()))
+}
+
+/// A temporary file that is removed when dropped, unless it was [kept](TempFile::keep).
+///
+/// This cleans up after failed writes, panics and, for async writes, futures that are dropped
+/// before completing (for example on timeout).
+struct TempFile {
+    path: PathBuf,
+    keep: bool,
+}
+
+impl TempFile {
+    fn new(path: PathBuf) -> Self {
+        Self { path, keep: false }
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Disarms the cleanup, typically once the file has been renamed into place.
+    fn keep(mut self) {
+        self.keep = true;
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        if !self.keep {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Streams the output of `write` into a temporary file and renames it over `path`, removing
+/// the temporary file if any step fails.
+fn write_atomic<F, R>(path: &Path, write: F) -> Result<R, StorageError>
+where
+    F: FnOnce(&mut dyn Write) -> Result<R, StorageError>,
+{
+    write_atomic_with(path, &SaveOptions::default(), write)
+}
+
+/// Like [`write_atomic`], applying the file-level settings of `options` to the temporary file.
+fn write_atomic_with<F, R>(path: &Path, options: &SaveOptions, write: F) -> Result<R, StorageError>
+where
+    F: FnOnce(&mut dyn Write) -> Result<R, StorageError>,
+{
+    if options.create_new && std::fs::symlink_metadata(path).is_ok() {
+        return Err(StorageError::AlreadyExists(path.to_path_buf()));
+    }
+    if options.create_dirs {
+        create_parent_dirs(path)?;
+    }
+    let temp = TempFile::new(temp_path(path));
+
+    let file =
+        create_temp_file(temp.path(), path, options).map_err(|err| parent_error(path, err))?;
+    let mut writer = io::BufWriter::new(file);
+    let output = write(&mut writer)?;
+    let file = writer
+        .into_inner()
+        .map_err(io::IntoInnerError::into_error)?;
+    if options.durable {
+        file.sync_all()?;
+    }
+    drop(file);
+
+    if options.create_new {
+        // Unlike a rename, linking fails instead of replacing the target; the temporary name
+        // is removed when `temp` is dropped.
+        std::fs::hard_link(temp.path(), path).map_err(|err| exists_error(path, err))?;
+    } else {
+        std::fs::rename(temp.path(), path)?;
+        temp.keep();
+    }
+    if options.durable {
+        sync_dir(parent_dir(path))?;
+    }
+
+    Ok(output)
+}
+
+/// Async counterpart of [`write_atomic`].
+#[cfg(feature = "async")]
+async fn write_atomic_async<F, R>(path: &Path, write: F) -> Result<R, StorageError>
+where
+    F: FnOnce(&mut dyn Write) -> Result<R, StorageError> + Send + 'static,
+    R: Send + 'static,
+{
+    write_atomic_with_async(path, &SaveOptions::default(), write).await
+}
+
+/// Async counterpart of [`write_atomic_with`].
+///
+/// The file is written through a tokio [`BufWriter`](tokio::io::BufWriter), while `write` runs
+/// on a blocking task behind a [`SyncIoBridge`], so serializers can stream into the file without
+/// the whole payload being buffered in memory first.
+#[cfg(feature = "async")]
+async fn write_atomic_with_async<F, R>(
+    path: &Path,
+    options: &SaveOptions,
+    write: F,
+) -> Result<R, StorageError>
+where
+    F: FnOnce(&mut dyn Write) -> Result<R, StorageError> + Send + 'static,
+    R: Send + 'static,
+{
+    if options.create_new && tokio::fs::symlink_metadata(path).await.is_ok() {
+        return Err(StorageError::AlreadyExists(path.to_path_buf()));
+    }
+    if options.create_dirs {
+        tokio::fs::create_dir_all(parent_dir(path)).await?;
+    }
+    let temp = TempFile::new(temp_path(path));
+
+    let file = create_temp_file_async(temp.path(), path, options)
+        .await
+        .map_err(|err| parent_error(path, err))?;
+    let writer = tokio::io::BufWriter::new(file);
+
+    let (mut writer, output) = tokio::task::spawn_blocking(move || {
+        let mut bridge = SyncIoBridge::new(writer);
+        let output = write(&mut bridge)?;
+        bridge.flush()?;
+        Ok::<_, StorageError>((bridge.into_inner(), output))
+    })
+    .await??;
+    writer.shutdown().await?;
+    if options.durable {
+        writer.get_ref().sync_all().await?;
+    }
+    drop(writer);
+
+    if options.create_new {
+        tokio::fs::hard_link(temp.path(), path)
+            .await
+            .map_err(|err| exists_error(path, err))?;
+    } else {
+        tokio::fs::rename(temp.path(), path).await?;
+        temp.keep();
+    }
+    if options.durable {
+        let dir = parent_dir(path).to_path_buf();
+        tokio::task::spawn_blocking(move || sync_dir(&dir)).await??;
+    }
+
+    Ok(output)
+}
+
+/// Creates the temporary file at `temp` that will replace `path`, applying the requested
+/// permissions before any data is written to it.
+///
+/// On Unix, if `path` already exists, the temporary file takes over its owner, group and
+/// permission bits (unless `options` sets a mode), so replacing a file does not loosen them.
+fn create_temp_file(
+    temp: &Path,
+    path: &Path,
+    options: &SaveOptions,
+) -> Result<std::fs::File, io::Error> {
+    let mut open_options = std::fs::OpenOptions::new();
+    open_options.write(true).create_new(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+        let existing = std::fs::metadata(path).ok();
+        let mode = options
+            .mode
+            .or_else(|| existing.as_ref().map(existing_mode));
+        if let Some(mode) = mode {
+            open_options.mode(mode);
+        }
+
+        let file = open_options.open(temp)?;
+        if let Some(existing) = &existing {
+            preserve_owner(&file, existing);
+        }
+        if let Some(mode) = mode {
+            // The mode passed to `open` is filtered through the umask, so set it explicitly.
+            file.set_permissions(std::fs::Permissions::from_mode(mode))?;
+        }
+        Ok(file)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (path, options);
+        open_options.open(temp)
+    }
+}
+
+/// Async counterpart of [`create_temp_file`].
+#[cfg(feature = "async")]
+async fn create_temp_file_async(
+    temp: &Path,
+    path: &Path,
+    options: &SaveOptions,
+) -> Result<tokio::fs::File, io::Error> {
+    let mut open_options = tokio::fs::OpenOptions::new();
+    open_options.write(true).create_new(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let existing = tokio::fs::metadata(path).await.ok();
+        let mode = options
+            .mode
+            .or_else(|| existing.as_ref().map(existing_mode));
+        if let Some(mode) = mode {
+            open_options.mode(mode);
+        }
+
+        let file = open_options.open(temp).await?;
+        if let Some(existing) = &existing {
+            preserve_owner(&file, existing);
+        }
+        if let Some(mode) = mode {
+            // The mode passed to `open` is filtered through the umask, so set it explicitly.
+            file.set_permissions(std::fs::Permissions::from_mode(mode))
+                .await?;
+        }
+        Ok(file)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (path, options);
+        open_options.open(temp).await
+    }
+}
+
+/// Returns the permission bits of a file that is about to be replaced.
+#[cfg(unix)]
+fn existing_mode(metadata: &std::fs::Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+
+    metadata.permissions().mode() & 0o7777
+}
+
+/// Gives `file` the owner and group of the file it is about to replace, as far as the process
+/// is allowed to.
+///
+/// Only privileged processes can change the owner, but the group can also be set by the owner
+/// to any group they belong to, so that is tried on its own if the first attempt fails. Any
+/// remaining failure is ignored: the new file then keeps the owner of the process.
+#[cfg(unix)]
+fn preserve_owner(file: &impl std::os::fd::AsFd, existing: &std::fs::Metadata) {
+    use std::os::unix::fs::{MetadataExt, fchown};
+
+    if fchown(file, Some(existing.uid()), Some(existing.gid())).is_err() {
+        let _ = fchown(file, None, Some(existing.gid()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::{NamedTempFile, tempdir};
+
+    #[derive(Serialize, serde::Deserialize, Debug, PartialEq)]
+    struct TestData {
+        name: String,
+        value: i32,
+    }
+
+    /// Runs only in builds without the default features, such as `cargo test
+    /// --no-default-features`, where it checks that the sync JSON API works on its own, without
+    /// tokio, the binary formats or the compression, encryption, watch, mmap and parallel
+    /// dependencies.
+    #[cfg(not(any(
+        feature = "async",
+        feature = "compression",
+        feature = "encryption",
+        feature = "watch",
+        feature = "mmap",
+        feature = "parallel"
+    )))]
+    #[test]
+    fn minimal_build_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("minimal.json");
+        let data = TestData {
+            name: "minimal".to_string(),
+            value: 1,
+        };
+
+        save(&path, &data).unwrap();
+        assert_eq!(load::<_, TestData>(&path).unwrap(), data);
+
+        let counter = dir.path().join("counter.json");
+        let updated = update(&counter, |value: &mut u32| *value += 1).unwrap();
+        assert_eq!(updated, 1);
+        assert_eq!(Document::<u32>::new(&counter).load().unwrap(), updated);
+    }
+
+    #[test]
+    fn save_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        let data = TestData {
+            name: "test".to_string(),
+            value: 42,
+        };
+
+        let result = save(path, &data);
+        assert!(result.is_ok());
+
+        let content = std::fs::read_to_string(path).unwrap();
+        assert!(content.contains("test"));
         assert!(content.contains("42"));
     }
 
-    #[tokio::test]
-    async fn save_async_test() {
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn save_async_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_path_buf();
+
+        let data = TestData {
+            name: "async_test".to_string(),
+            value: 100,
+        };
+
+        let result = save_async(path.clone(), data).await;
+        assert!(result.is_ok());
+
+        let content = tokio::fs::read_to_string(&path).await.unwrap();
+        assert!(content.contains("async_test"));
+        assert!(content.contains("100"));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn save_async_cancelled_test() {
+        use std::{future::Future, task::Poll};
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("state.json");
+        let original: Vec<u64> = vec![1, 2, 3];
+        save(&path, &original).unwrap();
+        let replacement: Vec<u64> = (0..1_000_000).collect();
+
+        for polls in [1, 2, 5, 10, 50] {
+            let mut future = Box::pin(save_async(path.clone(), replacement.clone()));
+            let mut completed = false;
+            for _ in 0..polls {
+                let poll = std::future::poll_fn(|cx| Poll::Ready(future.as_mut().poll(cx))).await;
+                if poll.is_ready() {
+                    completed = true;
+                    break;
+                }
+                tokio::task::yield_now().await;
+            }
+            if completed {
+                continue;
+            }
+            drop(future);
+
+            let loaded: Vec<u64> = load(&path).unwrap();
+            assert!(loaded == original || loaded == replacement, "{polls}");
+            for entry in std::fs::read_dir(dir.path()).unwrap() {
+                let name = entry.unwrap().file_name().into_string().unwrap();
+                assert!(
+                    name == "state.json"
+                        || (name.starts_with(".state.json.") && name.ends_with(".tmp")),
+                    "{name}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn load_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        let original_data = TestData {
+            name: "load_test".to_string(),
+            value: 77,
+        };
+
+        save(path, &original_data).unwrap();
+
+        let loaded_data: TestData = load(path).unwrap();
+        assert_eq!(loaded_data, original_data);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn load_async_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_path_buf();
+
+        let original_data = TestData {
+            name: "async_load_test".to_string(),
+            value: 55,
+        };
+
+        save_async(path.clone(), original_data).await.unwrap();
+
+        let loaded_data: TestData = load_async(&path).await.unwrap();
+        assert_eq!(loaded_data.name, "async_load_test");
+        assert_eq!(loaded_data.value, 55);
+    }
+
+    #[test]
+    fn save_replaces_existing_file_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("data.json");
+        std::fs::write(&path, "old contents").unwrap();
+
+        let data = TestData {
+            name: "replaced".to_string(),
+            value: 1,
+        };
+        save(&path, &data).unwrap();
+
+        let loaded: TestData = load(&path).unwrap();
+        assert_eq!(loaded, data);
+
+        let entries = std::fs::read_dir(dir.path()).unwrap().count();
+        assert_eq!(entries, 1);
+    }
+
+    #[test]
+    fn save_failure_removes_temp_file_test() {
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("occupied");
+        std::fs::create_dir(&target).unwrap();
+        std::fs::write(target.join("keep"), "").unwrap();
+
+        let data = TestData {
+            name: "fail".to_string(),
+            value: 0,
+        };
+        assert!(save(&target, &data).is_err());
+
+        let entries = std::fs::read_dir(dir.path()).unwrap().count();
+        assert_eq!(entries, 1);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn save_async_failure_removes_temp_file_test() {
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("occupied");
+        std::fs::create_dir(&target).unwrap();
+        std::fs::write(target.join("keep"), "").unwrap();
+
+        let data = TestData {
+            name: "fail".to_string(),
+            value: 0,
+        };
+        assert!(save_async(target, data).await.is_err());
+
+        let entries = std::fs::read_dir(dir.path()).unwrap().count();
+        assert_eq!(entries, 1);
+    }
+
+    #[test]
+    fn load_missing_file_test() {
+        let dir = tempdir().unwrap();
+        let result: Result<TestData, _> = load(dir.path().join("missing.json"));
+
+        assert!(matches!(
+            result,
+            Err(StorageError::Io(err)) if err.kind() == io::ErrorKind::NotFound
+        ));
+    }
+
+    #[test]
+    fn load_malformed_file_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        std::fs::write(temp_file.path(), "{ not json").unwrap();
+
+        let result: Result<TestData, _> = load(temp_file.path());
+        assert!(matches!(result, Err(StorageError::Parse { .. })));
+    }
+
+    #[test]
+    fn load_empty_file_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        let err = load::<_, TestData>(path).unwrap_err();
+        assert!(matches!(&err, StorageError::Empty(empty) if empty == path));
+        assert_eq!(
+            err.to_string(),
+            format!("file is empty: {}", path.display())
+        );
+
+        let limited = load_limited::<_, TestData>(path, 1024);
+        assert!(matches!(limited, Err(StorageError::Empty(_))));
+
+        std::fs::write(path, " ").unwrap();
+        let blank = load::<_, TestData>(path);
+        assert!(matches!(blank, Err(StorageError::Parse { .. })));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn load_async_empty_file_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+
+        let result = load_async::<_, TestData>(temp_file.path()).await;
+        assert!(matches!(result, Err(StorageError::Empty(_))));
+    }
+
+    #[test]
+    fn load_parse_error_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+        std::fs::write(path, "{\n  \"name\": \"x\",\n  \"value\": x\n}").unwrap();
+
+        let err = load::<_, TestData>(path).unwrap_err();
+        let StorageError::Parse {
+            path: failed,
+            source,
+        } = &err
+        else {
+            panic!("expected a parse error, got {err:?}");
+        };
+        assert_eq!(failed, path);
+        assert_eq!((source.line(), source.column()), (3, 12));
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "failed to parse {} at line 3 column 12: expected value",
+                path.display()
+            )
+        );
+    }
+
+    #[test]
+    fn save_pretty_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        let data = TestData {
+            name: "pretty".to_string(),
+            value: 3,
+        };
+        save_pretty(path, &data).unwrap();
+
+        let content = std::fs::read_to_string(path).unwrap();
+        assert_eq!(content, "{\n  \"name\": \"pretty\",\n  \"value\": 3\n}\n");
+    }
+
+    #[test]
+    fn save_pretty_with_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        let data = TestData {
+            name: "indented".to_string(),
+            value: 4,
+        };
+        save_pretty_with(path, &data, b"    ").unwrap();
+        let content = std::fs::read_to_string(path).unwrap();
+        assert_eq!(
+            content,
+            "{\n    \"name\": \"indented\",\n    \"value\": 4\n}\n"
+        );
+
+        save_pretty_with(path, &data, b"\t").unwrap();
+        assert_eq!(load::<_, TestData>(path).unwrap(), data);
+
+        let indent = vec![b' '; 3];
+        save_pretty_with(path, &data, &indent).unwrap();
+        let content = std::fs::read_to_string(path).unwrap();
+        assert!(content.starts_with("{\n   \"name\""));
+
+        let result = save_pretty_with(path, &data, b"--");
+        assert!(
+            matches!(result, Err(StorageError::Io(err)) if err.kind() == io::ErrorKind::InvalidInput)
+        );
+        assert_eq!(std::fs::read_to_string(path).unwrap(), content);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn save_pretty_async_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_path_buf();
+
+        let data = TestData {
+            name: "pretty_async".to_string(),
+            value: 4,
+        };
+        save_pretty_async(&path, data).await.unwrap();
+
+        let content = tokio::fs::read_to_string(&path).await.unwrap();
+        assert!(content.starts_with("{\n  "));
+        assert!(content.ends_with("}\n"));
+    }
+
+    #[test]
+    fn save_with_default_options_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        save_with(path, vec![1, 2, 3], &SaveOptions::default()).unwrap();
+
+        let content = std::fs::read_to_string(path).unwrap();
+        assert_eq!(content, "[1,2,3]");
+    }
+
+    #[derive(Serialize, serde::Deserialize, Debug, PartialEq, Default)]
+    struct Counter {
+        hits: u64,
+    }
+
+    #[test]
+    fn load_or_default_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("counter.json");
+
+        let missing: Counter = load_or_default(&path).unwrap();
+        assert_eq!(missing, Counter::default());
+
+        save(&path, Counter { hits: 9 }).unwrap();
+        let existing: Counter = load_or_default(&path).unwrap();
+        assert_eq!(existing.hits, 9);
+
+        std::fs::write(&path, "garbage").unwrap();
+        let malformed: Result<Counter, _> = load_or_default(&path);
+        assert!(matches!(malformed, Err(StorageError::Parse { .. })));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn load_or_default_async_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("counter.json");
+
+        let missing: Counter = load_or_default_async(&path).await.unwrap();
+        assert_eq!(missing, Counter::default());
+
+        save_async(path.clone(), Counter { hits: 2 }).await.unwrap();
+        let existing: Counter = load_or_default_async(&path).await.unwrap();
+        assert_eq!(existing.hits, 2);
+    }
+
+    #[test]
+    fn try_load_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("counter.json");
+
+        let missing: Option<Counter> = try_load(&path).unwrap();
+        assert_eq!(missing, None);
+
+        save(&path, Counter { hits: 5 }).unwrap();
+        let existing: Option<Counter> = try_load(&path).unwrap();
+        assert_eq!(existing, Some(Counter { hits: 5 }));
+
+        std::fs::write(&path, "not json").unwrap();
+        let invalid = try_load::<_, Counter>(&path);
+        assert!(matches!(invalid, Err(StorageError::Parse { .. })));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn try_load_async_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("counter.json");
+
+        let missing: Option<Counter> = try_load_async(&path).await.unwrap();
+        assert_eq!(missing, None);
+
+        save_async(path.clone(), Counter { hits: 3 }).await.unwrap();
+        let existing: Option<Counter> = try_load_async(&path).await.unwrap();
+        assert_eq!(existing, Some(Counter { hits: 3 }));
+
+        let directory = try_load_async::<_, Counter>(dir.path()).await;
+        assert!(matches!(directory, Err(StorageError::Io(_))));
+    }
+
+    #[test]
+    fn load_first_test() {
+        let dir = tempdir().unwrap();
+        let first = dir.path().join("first.json");
+        let second = dir.path().join("second.json");
+        let third = dir.path().join("third.json");
+        let candidates = [first.as_path(), second.as_path(), third.as_path()];
+
+        let none = load_first::<_, Counter>(&candidates);
+        assert!(matches!(none, Err(StorageError::NotFound(ref tried)) if tried.len() == 3));
+        assert!(none.unwrap_err().to_string().contains("third.json"));
+
+        save(&third, Counter { hits: 3 }).unwrap();
+        save(&second, Counter { hits: 2 }).unwrap();
+        let loaded: Counter = load_first(&candidates).unwrap();
+        assert_eq!(loaded, Counter { hits: 2 });
+
+        std::fs::write(&first, "not json").unwrap();
+        let broken = load_first::<_, Counter>(&candidates);
+        assert!(matches!(broken, Err(StorageError::Parse { .. })));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn save_async_blocking_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("large.json");
+
+        let data: Vec<u64> = (0..250_000).collect();
+        let written = save_async_blocking(&path, data.clone()).await.unwrap();
+
+        assert_eq!(written as u64, std::fs::metadata(&path).unwrap().len());
+        let loaded: Vec<u64> = load(&path).unwrap();
+        assert_eq!(loaded, data);
+
+        let missing_parent = dir.path().join("missing").join("data.json");
+        assert!(save_async_blocking(missing_parent, 1).await.is_err());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn save_async_large_payload_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_path_buf();
+
+        let data: Vec<u64> = (0..250_000).collect();
+        save_async(&path, data.clone()).await.unwrap();
+
+        let loaded: Vec<u64> = load_async(&path).await.unwrap();
+        assert_eq!(loaded, data);
+    }
+
+    #[test]
+    fn save_value_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+        let records = serde_json::json!([
+            { "name": "mixed", "value": 1 },
+            { "id": 7, "tags": ["a", "b"] },
+            null,
+        ]);
+
+        let written = save_value(path, &records).unwrap();
+        assert_eq!(written as u64, std::fs::metadata(path).unwrap().len());
+        assert_eq!(load::<_, Value>(path).unwrap(), records);
+    }
+
+    #[test]
+    fn load_value_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+        std::fs::write(path, r#"{"nested":{"list":[1,"two"]}}"#).unwrap();
+
+        let value = load_value(path).unwrap();
+        assert_eq!(value["nested"]["list"][1], "two");
+
+        std::fs::write(path, "{").unwrap();
+        assert!(matches!(load_value(path), Err(StorageError::Parse { .. })));
+    }
+
+    #[test]
+    fn compact_test() {
+        #[derive(Serialize, Deserialize)]
+        struct Settings {
+            name: String,
+            #[serde(default)]
+            retries: u32,
+        }
+
         let temp_file = NamedTempFile::new().unwrap();
-        let path = temp_file.path().to_path_buf();
+        let path = temp_file.path();
+        std::fs::write(path, "{\n    \"name\" : \"api\",\n\"unknown\": true}").unwrap();
+
+        let options = SaveOptions::new().pretty(true);
+        let written = compact::<_, Settings>(path, &options).unwrap();
+        let content = std::fs::read_to_string(path).unwrap();
+        assert_eq!(content, "{\n  \"name\": \"api\",\n  \"retries\": 0\n}\n");
+        assert_eq!(written, content.len());
+
+        std::fs::write(path, "{").unwrap();
+        assert!(compact::<_, Settings>(path, &options).is_err());
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "{");
+    }
+
+    #[test]
+    fn reformat_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+        std::fs::write(
+            path,
+            " { \"b\" : [ 1 , { \"d\": 1, \"c\": 2 } ], \"a\":\"x\" } ",
+        )
+        .unwrap();
+
+        reformat(path, &SaveOptions::new()).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(path).unwrap(),
+            r#"{"a":"x","b":[1,{"c":2,"d":1}]}"#
+        );
+    }
+
+    #[test]
+    fn delete_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("delete.json");
+        save(&path, "data").unwrap();
+
+        delete(&path).unwrap();
+        assert!(!path.exists());
+
+        let result = delete(&path);
+        assert!(matches!(
+            result,
+            Err(StorageError::Io(err))
+                if err.kind() == io::ErrorKind::NotFound
+                    && err.to_string().contains("delete.json")
+        ));
+    }
+
+    #[test]
+    fn delete_if_exists_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("delete.json");
+        save(&path, "data").unwrap();
+
+        assert!(delete_if_exists(&path).unwrap());
+        assert!(!delete_if_exists(&path).unwrap());
+    }
+
+    /// A writer that rejects every write, as a full disk would.
+    struct FullDisk;
+
+    impl Write for FullDisk {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::StorageFull, "no space left"))
+        }
 
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn write_failure_is_propagated_test() {
+        let result = write_json(&mut FullDisk, &vec![1, 2, 3], &SaveOptions::default());
+        assert!(matches!(
+            result,
+            Err(StorageError::Io(err)) if err.kind() == io::ErrorKind::StorageFull
+        ));
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("data.json");
+        let result: Result<(), _> = write_atomic(&path, |_| {
+            Err(io::Error::new(io::ErrorKind::StorageFull, "no space left").into())
+        });
+        assert!(result.is_err());
+        assert!(!path.exists());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn save_iter_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        let written = save_iter(path, std::iter::empty::<TestData>()).unwrap();
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "[]");
+        assert_eq!(written, 2);
+
+        let records = (0..3).map(|value| TestData {
+            name: format!("record-{value}"),
+            value,
+        });
+        let written = save_iter(path, records.clone()).unwrap();
+        assert_eq!(written as u64, std::fs::metadata(path).unwrap().len());
+
+        let loaded: Vec<TestData> = load(path).unwrap();
+        assert_eq!(loaded, records.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn save_to_writer_test() {
         let data = TestData {
-            name: "async_test".to_string(),
-            value: 100,
+            name: "buffer".to_string(),
+            value: 3,
         };
 
-        let result = save_async(path.clone(), data).await;
-        assert!(result.is_ok());
+        let mut buffer = io::Cursor::new(Vec::new());
+        let written = save_to_writer(&mut buffer, &data).unwrap();
+        assert_eq!(written, buffer.get_ref().len());
+        assert_eq!(buffer.get_ref(), br#"{"name":"buffer","value":3}"#);
 
-        let content = tokio::fs::read_to_string(&path).await.unwrap();
-        assert!(content.contains("async_test"));
-        assert!(content.contains("100"));
+        let result = save_to_writer(&mut FullDisk, &data);
+        assert!(matches!(result, Err(StorageError::Io(_))));
     }
 
     #[test]
-    fn load_test() {
+    fn save_into_test() {
         let temp_file = NamedTempFile::new().unwrap();
         let path = temp_file.path();
+        std::fs::write(path, r#"{"name":"a much longer previous value","value":0}"#).unwrap();
 
-        let original_data = TestData {
-            name: "load_test".to_string(),
-            value: 77,
+        let mut file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(path)
+            .unwrap();
+        file.seek(SeekFrom::End(0)).unwrap();
+        let data = TestData {
+            name: "short".to_string(),
+            value: 1,
         };
+        let written = save_into(&mut file, &data).unwrap();
+        assert_eq!(written as u64, std::fs::metadata(path).unwrap().len());
+        assert_eq!(load::<_, TestData>(path).unwrap(), data);
 
-        save(path, &original_data).unwrap();
+        let mut read_only = File::open(path).unwrap();
+        assert!(matches!(
+            save_into(&mut read_only, &data),
+            Err(StorageError::Io(_))
+        ));
+    }
 
-        let loaded_data: TestData = load(path).unwrap();
-        assert_eq!(loaded_data, original_data);
+    #[test]
+    fn load_from_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let data = TestData {
+            name: "open".to_string(),
+            value: 2,
+        };
+        save(temp_file.path(), &data).unwrap();
+
+        let mut file = File::open(temp_file.path()).unwrap();
+        assert_eq!(load_from::<TestData>(&mut file).unwrap(), data);
+        assert_eq!(load_from::<TestData>(&mut file).unwrap(), data);
+    }
+
+    #[test]
+    fn load_from_reader_test() {
+        let data = TestData {
+            name: "reader".to_string(),
+            value: 8,
+        };
+        let mut buffer = Vec::new();
+        save_to_writer(&mut buffer, &data).unwrap();
+
+        let loaded: TestData = load_from_reader(buffer.as_slice()).unwrap();
+        assert_eq!(loaded, data);
+
+        let result = load_from_reader::<_, TestData>(&b"{} trailing"[..]);
+        assert!(matches!(result, Err(StorageError::Deserialize(_))));
+    }
+
+    #[test]
+    fn load_byte_order_mark_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        std::fs::write(path, b"\xEF\xBB\xBF{\"name\":\"bom\",\"value\":1}").unwrap();
+        let loaded: TestData = load(path).unwrap();
+        assert_eq!(loaded.name, "bom");
+
+        std::fs::write(path, b"\xFF\xFE{\x00}\x00").unwrap();
+        let Err(StorageError::Parse { source, .. }) = load::<_, TestData>(path) else {
+            panic!("expected a parse error");
+        };
+        assert!(source.to_string().contains("UTF-16 byte-order mark"));
     }
 
+    #[cfg(feature = "async")]
     #[tokio::test]
-    async fn load_async_test() {
+    async fn load_async_byte_order_mark_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        std::fs::write(temp_file.path(), b"\xEF\xBB\xBF[1, 2]").unwrap();
+
+        let loaded: Vec<u8> = load_async(temp_file.path()).await.unwrap();
+        assert_eq!(loaded, vec![1, 2]);
+    }
+
+    #[test]
+    fn save_if_changed_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+
+        let data = TestData {
+            name: "unchanged".to_string(),
+            value: 1,
+        };
+        assert!(save_if_changed(&path, &data).unwrap());
+        let modified = std::fs::metadata(&path).unwrap().modified().unwrap();
+
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        assert!(!save_if_changed(&path, &data).unwrap());
+        assert_eq!(
+            std::fs::metadata(&path).unwrap().modified().unwrap(),
+            modified
+        );
+
+        let changed = TestData {
+            name: "changed".to_string(),
+            value: 2,
+        };
+        assert!(save_if_changed(&path, &changed).unwrap());
+
+        let loaded: TestData = load(&path).unwrap();
+        assert_eq!(loaded, changed);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn save_with_permissions_test() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("secret.json");
+        std::fs::write(&path, "old").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        save_with_permissions(&path, "token", 0o600).unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "\"token\"");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn save_preserves_permissions_test() {
+        use std::os::unix::fs::{MetadataExt, PermissionsExt, chown};
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("secret.json");
+        save(&path, "old").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+        // Changing the owner needs privileges, so only check it where the test may do so.
+        let chowned = chown(&path, Some(4242), Some(4242)).is_ok();
+
+        save(&path, "new").unwrap();
+        let metadata = std::fs::metadata(&path).unwrap();
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+        if chowned {
+            assert_eq!((metadata.uid(), metadata.gid()), (4242, 4242));
+        }
+
+        save_with_permissions(&path, "newer", 0o640).unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o640);
+    }
+
+    #[cfg(unix)]
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn save_async_preserves_permissions_test() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("secret.json");
+        save(&path, "old").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+
+        save_async(&path, "new").await.unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[cfg(unix)]
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn save_with_async_mode_test() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("secret.json");
+
+        save_with_async(&path, "token", &SaveOptions::new().mode(0o640))
+            .await
+            .unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o640);
+    }
+
+    #[test]
+    fn save_returns_bytes_written_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        let written = save(path, vec![1, 2, 3]).unwrap();
+        assert_eq!(written, "[1,2,3]".len());
+
+        let written = save_pretty(path, vec![1]).unwrap();
+        assert_eq!(written as u64, std::fs::metadata(path).unwrap().len());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn save_async_returns_bytes_written_test() {
         let temp_file = NamedTempFile::new().unwrap();
         let path = temp_file.path().to_path_buf();
 
-        let original_data = TestData {
-            name: "async_load_test".to_string(),
-            value: 55,
+        let written = save_async(&path, "abc").await.unwrap();
+        assert_eq!(written, "\"abc\"".len());
+        assert_eq!(written as u64, std::fs::metadata(&path).unwrap().len());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn load_invalid_utf8_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_path_buf();
+        std::fs::write(&path, b"\"\xff\xfe\"").unwrap();
+
+        let result: Result<String, _> = load(&path);
+        assert!(matches!(result, Err(StorageError::Parse { .. })));
+
+        let result: Result<String, _> = load_async(&path).await;
+        assert!(matches!(result, Err(StorageError::Parse { .. })));
+    }
+
+    #[test]
+    fn copy_test() {
+        let dir = tempdir().unwrap();
+        let from = dir.path().join("from.json");
+        let to = dir.path().join("nested").join("to.json");
+        save(&from, "source").unwrap();
+
+        let copied = copy(&from, &to, false).unwrap();
+        assert_eq!(copied, std::fs::metadata(&from).unwrap().len());
+
+        let result = copy(&from, &to, false);
+        assert!(matches!(result, Err(StorageError::AlreadyExists(path)) if path == to));
+        let loaded: String = load(&to).unwrap();
+        assert_eq!(loaded, "source");
+
+        save(&from, "updated").unwrap();
+        copy(&from, &to, true).unwrap();
+        let loaded: String = load(&to).unwrap();
+        assert_eq!(loaded, "updated");
+
+        let result = copy(dir.path().join("missing.json"), &to, true);
+        assert!(matches!(
+            result,
+            Err(StorageError::Io(err))
+                if err.kind() == io::ErrorKind::NotFound
+                    && err.to_string().contains("missing.json")
+        ));
+    }
+
+    #[test]
+    fn rename_test() {
+        let dir = tempdir().unwrap();
+        let from = dir.path().join("session.json");
+        let to = dir.path().join("archive").join("session.json");
+        save(&from, 7).unwrap();
+
+        rename(&from, &to).unwrap();
+        assert!(!from.exists());
+        let loaded: u32 = load(&to).unwrap();
+        assert_eq!(loaded, 7);
+
+        let result = rename(&from, &to);
+        assert!(matches!(
+            result,
+            Err(StorageError::Io(err)) if err.kind() == io::ErrorKind::NotFound
+        ));
+    }
+
+    #[test]
+    fn save_with_create_dirs_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("data").join("users").join("42.json");
+
+        let result = save(&path, 42);
+        assert!(matches!(
+            result,
+            Err(StorageError::Io(err))
+                if err.kind() == io::ErrorKind::NotFound
+                    && err.to_string().starts_with("parent directory does not exist")
+        ));
+
+        save_with(&path, 42, &SaveOptions::new().create_dirs(true)).unwrap();
+        let loaded: u32 = load(&path).unwrap();
+        assert_eq!(loaded, 42);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn save_with_async_create_dirs_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("data").join("42.json");
+
+        assert!(save_async(&path, 42).await.is_err());
+
+        save_with_async(&path, 42, &SaveOptions::new().create_dirs(true))
+            .await
+            .unwrap();
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn save_new_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("claim.json");
+
+        let written = save_new(&path, vec![1, 2]).unwrap();
+        assert_eq!(written as u64, std::fs::metadata(&path).unwrap().len());
+
+        let result = save_new(&path, vec![3]);
+        assert!(matches!(result, Err(StorageError::AlreadyExists(existing)) if existing == path));
+        assert_eq!(load::<_, Vec<u8>>(&path).unwrap(), vec![1, 2]);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        let subdir = dir.path().join("subdir");
+        std::fs::create_dir(&subdir).unwrap();
+        assert!(matches!(
+            save_new(&subdir, 1),
+            Err(StorageError::AlreadyExists(_))
+        ));
+    }
+
+    #[test]
+    fn save_with_create_new_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("nested").join("once.json");
+        let options = SaveOptions::new()
+            .create_new(true)
+            .create_dirs(true)
+            .durable(true);
+
+        save_with(&path, "once", &options).unwrap();
+        assert_eq!(load::<_, String>(&path).unwrap(), "once");
+        assert_eq!(
+            std::fs::read_dir(path.parent().unwrap()).unwrap().count(),
+            1
+        );
+
+        // A file created by another process after the early check makes the link fail.
+        let other = dir.path().join("other.json");
+        std::fs::write(&other, "\"racing\"").unwrap();
+        let err = std::fs::hard_link(&other, &path).unwrap_err();
+        assert!(matches!(
+            exists_error(&path, err),
+            StorageError::AlreadyExists(_)
+        ));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn save_with_async_create_new_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("once.json");
+        let options = SaveOptions::new().create_new(true);
+
+        save_with_async(&path, 1, &options).await.unwrap();
+        let result = save_with_async(&path, 2, &options).await;
+        assert!(matches!(result, Err(StorageError::AlreadyExists(_))));
+        assert_eq!(load::<_, u32>(&path).unwrap(), 1);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn save_with_durable_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("wal.json");
+
+        let written = save_with(&path, vec![1, 2, 3], &SaveOptions::new().durable(true)).unwrap();
+
+        assert_eq!(written as u64, std::fs::metadata(&path).unwrap().len());
+        assert_eq!(load::<_, Vec<i32>>(&path).unwrap(), vec![1, 2, 3]);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn sync_dir_test() {
+        let dir = tempdir().unwrap();
+        sync_dir(dir.path()).unwrap();
+        sync_dir(parent_dir(Path::new("bare.json"))).unwrap();
+
+        #[cfg(unix)]
+        assert!(sync_dir(&dir.path().join("missing")).is_err());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn save_with_async_durable_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("wal.json");
+
+        let options = SaveOptions::new().pretty(true).durable(true);
+        save_with_async(&path, "entry", &options).await.unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "\"entry\"\n");
+    }
+
+    #[test]
+    fn sanitize_filename_test() {
+        let dir = tempdir().unwrap();
+        let base = dir.path();
+
+        assert_eq!(
+            sanitize_filename(base, "config.json").unwrap(),
+            base.join("config.json")
+        );
+        assert_eq!(
+            sanitize_filename(base, "./users/./42.json").unwrap(),
+            base.join("users").join("42.json")
+        );
+
+        for filename in [
+            "",
+            ".",
+            "..",
+            "../secret.json",
+            "users/../../x",
+            "/etc/passwd",
+        ] {
+            let result = sanitize_filename(base, filename);
+            assert!(
+                matches!(
+                    &result,
+                    Err(StorageError::Io(err)) if err.kind() == io::ErrorKind::InvalidInput
+                ),
+                "{filename:?} was accepted: {result:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn exists_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("present.json");
+
+        assert!(!exists(&path));
+        assert!(!exists(dir.path()));
+
+        save(&path, 1).unwrap();
+        assert!(exists(&path));
+    }
+
+    #[test]
+    fn is_empty_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("state.json");
+
+        assert!(is_empty(&path).is_err());
+
+        std::fs::write(&path, "").unwrap();
+        assert!(is_empty(&path).unwrap());
+
+        save(&path, 1).unwrap();
+        assert!(!is_empty(&path).unwrap());
+    }
+
+    #[test]
+    fn read_range_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+        std::fs::write(path, b"0123456789").unwrap();
+
+        assert_eq!(read_range(path, 0, 4).unwrap(), b"0123");
+        assert_eq!(read_range(path, 3, 4).unwrap(), b"3456");
+        assert_eq!(read_range(path, 8, 10).unwrap(), b"89");
+        assert_eq!(read_range(path, 2, 0).unwrap(), b"");
+        assert_eq!(read_range(path, 10, 1).unwrap(), b"");
+
+        let past_end = read_range(path, 11, 1).unwrap_err();
+        assert_eq!(past_end.kind(), io::ErrorKind::InvalidInput);
+        assert!(past_end.to_string().contains("offset 11"), "{past_end}");
+
+        let missing = read_range(path.with_extension("missing"), 0, 1).unwrap_err();
+        assert_eq!(missing.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn load_with_meta_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+        assert!(load_with_meta::<_, u32>(&path).is_err());
+
+        save(&path, 1).unwrap();
+        let old = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+
+        let (value, modified) = load_with_meta::<_, u32>(&path).unwrap();
+        assert_eq!(value, 1);
+        assert_eq!(modified, old);
+
+        std::fs::write(&path, "").unwrap();
+        assert!(matches!(
+            load_with_meta::<_, u32>(&path),
+            Err(StorageError::Empty(_))
+        ));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn load_async_with_meta_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+        save(&path, "fresh").unwrap();
+
+        let (value, modified) = load_async_with_meta::<_, String>(&path).await.unwrap();
+        assert_eq!(value, "fresh");
+        assert_eq!(
+            modified,
+            std::fs::metadata(&path).unwrap().modified().unwrap()
+        );
+    }
+
+    #[test]
+    fn load_validated_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        let data = TestData {
+            name: "validated".to_string(),
+            value: 70_000,
         };
+        save(path, &data).unwrap();
 
-        save_async(path.clone(), original_data).await.unwrap();
+        let in_range = |data: &TestData| {
+            if (0..=65_535).contains(&data.value) {
+                Ok(())
+            } else {
+                Err(format!("value {} out of range", data.value))
+            }
+        };
 
-        let loaded_data: TestData = load_async(&path).await.unwrap();
-        assert_eq!(loaded_data.name, "async_load_test");
-        assert_eq!(loaded_data.value, 55);
+        let result = load_validated(path, in_range);
+        assert!(matches!(
+            result,
+            Err(StorageError::Validation(reason)) if reason == "value 70000 out of range"
+        ));
+
+        save(path, TestData { value: 80, ..data }).unwrap();
+        let loaded = load_validated(path, in_range).unwrap();
+        assert_eq!(loaded.value, 80);
+    }
+
+    #[test]
+    fn load_limited_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        let data = TestData {
+            name: "limited".to_string(),
+            value: 1,
+        };
+        let written = save(path, &data).unwrap() as u64;
+
+        let loaded: TestData = load_limited(path, written).unwrap();
+        assert_eq!(loaded, data);
+
+        let result = load_limited::<_, TestData>(path, written - 1);
+        assert!(matches!(
+            result,
+            Err(StorageError::TooLarge { limit, size }) if limit == written - 1 && size == written
+        ));
+    }
+
+    #[test]
+    fn save_sorted_test() {
+        #[derive(Serialize)]
+        struct Reversed {
+            zulu: u8,
+            alpha: u8,
+        }
+
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        save_sorted(path, Reversed { zulu: 1, alpha: 2 }).unwrap();
+
+        let content = std::fs::read_to_string(path).unwrap();
+        assert_eq!(content, r#"{"alpha":2,"zulu":1}"#);
     }
 }
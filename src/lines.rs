@@ -0,0 +1,518 @@
+use std::{
+    io::{self, BufRead, Write},
+    path::Path,
+};
+
+#[cfg(feature = "compression")]
+use flate2::{Compression, bufread::MultiGzDecoder, write::GzEncoder};
+#[cfg(feature = "async")]
+use futures_util::{Stream, stream};
+use serde::{Serialize, de::DeserializeOwned};
+#[cfg(feature = "async")]
+use tokio::io::{AsyncBufReadExt, BufReader, Lines};
+
+use crate::{StorageError, UTF8_BOM};
+
+/// Synchronously appends one record to a JSON-lines file.
+///
+/// The record is serialized to a single line of JSON followed by `\n` and appended to the end of
+/// the file, which is created if it does not exist yet. The whole line is written with a single
+/// call so records from concurrent appenders are not interleaved mid-line.
+///
+/// # Arguments
+///
+/// * `path` - The JSON-lines file to append to
+/// * `data` - The record to serialize and append (must implement `Serialize`)
+///
+/// # Returns
+///
+/// * `Ok(())` - If the operation succeeds
+/// * `Err(StorageError)` - If opening or writing the file fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{append_line, load_lines};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let path = dir.path().join("events.jsonl");
+///
+/// append_line(&path, "started").unwrap();
+/// append_line(&path, "stopped").unwrap();
+///
+/// let events: Vec<String> = load_lines(&path).unwrap();
+/// assert_eq!(events, vec!["started", "stopped"]);
+/// ```
+pub fn append_line<P, T>(path: P, data: T) -> Result<(), StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    let mut line = serde_json::to_vec(&data).map_err(StorageError::from_serialize)?;
+    line.push(b'\n');
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+
+    Ok(file.write_all(&line)?)
+}
+
+/// Synchronously loads every record from a JSON-lines file.
+///
+/// Each non-blank line is deserialized independently, in file order. A UTF-8 byte-order mark at
+/// the start of the file is ignored.
+///
+/// # Arguments
+///
+/// * `path` - The JSON-lines file to read from
+///
+/// # Returns
+///
+/// * `Ok(Vec<T>)` - The deserialized records
+/// * `Err(StorageError)` - If file reading fails, or [`StorageError::InvalidLine`] with the
+///   1-based line number if a line cannot be deserialized, including when it is not valid
+///   UTF-8
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{StorageError, load_lines};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// std::fs::write(temp_file.path(), "1\n2\noops\n").unwrap();
+///
+/// let result: Result<Vec<u32>, _> = load_lines(temp_file.path());
+/// assert!(matches!(result, Err(StorageError::InvalidLine { line: 3, .. })));
+/// ```
+pub fn load_lines<P, T>(path: P) -> Result<Vec<T>, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    parse_lines(io::BufReader::new(std::fs::File::open(path)?))
+}
+
+/// Synchronously appends one record to a gzip-compressed JSON-lines file.
+///
+/// The record is serialized as by [`append_line`], compressed into a gzip member of its own,
+/// and appended to the file with a single write. A gzip file may consist of any number of
+/// members, so the file stays valid after every append and can be read with [`load_lines_gz`]
+/// or standard tools such as `zcat`. Each member carries around 20 bytes of framing, so this
+/// suits records of a few hundred bytes or more; tiny records compress better when batched.
+///
+/// # Arguments
+///
+/// * `path` - The compressed JSON-lines file to append to
+/// * `data` - The record to serialize and append (must implement `Serialize`)
+///
+/// # Returns
+///
+/// * `Ok(())` - If the operation succeeds
+/// * `Err(StorageError)` - If opening or writing the file fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{append_line_gz, load_lines_gz};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let path = dir.path().join("events.jsonl.gz");
+///
+/// append_line_gz(&path, "started").unwrap();
+/// append_line_gz(&path, "stopped").unwrap();
+///
+/// let events: Vec<String> = load_lines_gz(&path).unwrap();
+/// assert_eq!(events, vec!["started", "stopped"]);
+/// ```
+#[cfg(feature = "compression")]
+pub fn append_line_gz<P, T>(path: P, data: T) -> Result<(), StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    serde_json::to_writer(&mut encoder, &data).map_err(StorageError::from_serialize)?;
+    encoder.write_all(b"\n")?;
+    let member = encoder.finish()?;
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+
+    Ok(file.write_all(&member)?)
+}
+
+/// Synchronously loads every record from a gzip-compressed JSON-lines file.
+///
+/// The file is decompressed on the fly as its lines are read, and every gzip member is read,
+/// so files built by [`append_line_gz`] and files compressed in one go both load completely.
+/// Lines are handled as in [`load_lines`]; line numbers in [`StorageError::InvalidLine`] count
+/// decompressed lines. A member cut short, for example by a crash during an append, ends the
+/// file: the records decompressed before it are returned, and the incomplete line is dropped.
+///
+/// # Arguments
+///
+/// * `path` - The compressed JSON-lines file to read from
+///
+/// # Returns
+///
+/// * `Ok(Vec<T>)` - The deserialized records
+/// * `Err(StorageError)` - If file reading or decompression fails, or
+///   [`StorageError::InvalidLine`] with the 1-based line number if a line cannot be
+///   deserialized
+///
+/// # Example
+///
+/// ```
+/// use std::io::Write;
+/// use flate2::{Compression, write::GzEncoder};
+/// use storage_service::load_lines_gz;
+/// use tempfile::NamedTempFile;
+///
+/// let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+/// encoder.write_all(b"1\n2\n3\n").unwrap();
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// std::fs::write(temp_file.path(), encoder.finish().unwrap()).unwrap();
+///
+/// let numbers: Vec<u32> = load_lines_gz(temp_file.path()).unwrap();
+/// assert_eq!(numbers, vec![1, 2, 3]);
+/// ```
+#[cfg(feature = "compression")]
+pub fn load_lines_gz<P, T>(path: P) -> Result<Vec<T>, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    let file = io::BufReader::new(std::fs::File::open(path)?);
+
+    parse_lines(io::BufReader::new(MultiGzDecoder::new(file)))
+}
+
+/// Deserializes every non-blank line read from `reader`.
+fn parse_lines<R, T>(reader: R) -> Result<Vec<T>, StorageError>
+where
+    R: BufRead,
+    T: DeserializeOwned,
+{
+    let mut records = Vec::new();
+
+    // Lines are split as bytes so that invalid UTF-8 is reported by the parser, with its line
+    // number, rather than as a bare I/O error.
+    for (index, line) in reader.split(b'\n').enumerate() {
+        let line = match line {
+            Ok(line) => line,
+            // Only a decoder can run out of input mid-stream, when the last compressed member
+            // was cut short; it holds at most the record being appended.
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => break,
+            Err(err) => return Err(err.into()),
+        };
+        let line = match index {
+            0 => line.strip_prefix(UTF8_BOM).unwrap_or(&line),
+            _ => &line,
+        };
+        if line.trim_ascii().is_empty() {
+            continue;
+        }
+
+        let record = serde_json::from_slice(line).map_err(|source| StorageError::InvalidLine {
+            line: index + 1,
+            source,
+        })?;
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+/// Asynchronously streams the records of a JSON-lines file, parsing each line as it is read.
+///
+/// The streaming counterpart of [`load_lines`] for files that do not fit in memory: lines are
+/// read through a [`tokio::io::BufReader`] only when the consumer polls for the next record, so
+/// a slow consumer slows down reading instead of causing the file to be buffered. The file is
+/// opened on the first poll. Blank lines are skipped and a UTF-8 byte-order mark at the start
+/// of the file is ignored, as in [`load_lines`].
+///
+/// A line that cannot be deserialized yields [`StorageError::InvalidLine`] and the stream
+/// continues with the next line, so consumers can choose to skip bad records or stop. An I/O
+/// error, including failing to open the file, is yielded once and ends the stream.
+///
+/// The returned stream is not [`Unpin`]; pin it with [`std::pin::pin!`] before calling
+/// `next` on it.
+///
+/// # Arguments
+///
+/// * `path` - The JSON-lines file to read from
+///
+/// # Returns
+///
+/// A stream of the deserialized records, or of the errors encountered, in file order.
+///
+/// # Example
+///
+/// ```
+/// use futures_util::StreamExt;
+/// use storage_service::{append_line, load_lines_stream};
+/// use tempfile::tempdir;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let dir = tempdir().unwrap();
+///     let path = dir.path().join("readings.jsonl");
+///     for reading in [3, 1, 4] {
+///         append_line(&path, reading).unwrap();
+///     }
+///
+///     let mut readings = std::pin::pin!(load_lines_stream::<_, u32>(&path));
+///     let mut total = 0;
+///     while let Some(reading) = readings.next().await {
+///         total += reading.unwrap();
+///     }
+///     assert_eq!(total, 8);
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub fn load_lines_stream<P, T>(path: P) -> impl Stream<Item = Result<T, StorageError>>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    enum State {
+        Closed(std::path::PathBuf),
+        Open(Lines<BufReader<tokio::fs::File>>, usize),
+        Done,
+    }
+
+    stream::unfold(
+        State::Closed(path.as_ref().to_path_buf()),
+        |state| async move {
+            let (mut lines, mut index) = match state {
+                State::Closed(path) => match tokio::fs::File::open(&path).await {
+                    Ok(file) => (BufReader::new(file).lines(), 0),
+                    Err(err) => return Some((Err(err.into()), State::Done)),
+                },
+                State::Open(lines, index) => (lines, index),
+                State::Done => return None,
+            };
+
+            loop {
+                let line = match lines.next_line().await {
+                    Ok(Some(line)) => line,
+                    Ok(None) => return None,
+                    Err(err) => return Some((Err(err.into()), State::Done)),
+                };
+                index += 1;
+
+                let text = match index {
+                    1 => line.strip_prefix('\u{feff}').unwrap_or(&line),
+                    _ => &line,
+                };
+                if text.trim().is_empty() {
+                    continue;
+                }
+
+                let record =
+                    serde_json::from_str(text).map_err(|source| StorageError::InvalidLine {
+                        line: index,
+                        source,
+                    });
+                return Some((record, State::Open(lines, index)));
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use tempfile::tempdir;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Event {
+        kind: String,
+        at: u64,
+    }
+
+    #[test]
+    fn append_line_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("events.jsonl");
+
+        append_line(
+            &path,
+            Event {
+                kind: "open".to_string(),
+                at: 1,
+            },
+        )
+        .unwrap();
+        append_line(
+            &path,
+            Event {
+                kind: "close".to_string(),
+                at: 2,
+            },
+        )
+        .unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            content,
+            "{\"kind\":\"open\",\"at\":1}\n{\"kind\":\"close\",\"at\":2}\n"
+        );
+    }
+
+    #[test]
+    fn load_lines_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("events.jsonl");
+        std::fs::write(
+            &path,
+            "{\"kind\":\"a\",\"at\":1}\n\n{\"kind\":\"b\",\"at\":2}\n",
+        )
+        .unwrap();
+
+        let events: Vec<Event> = load_lines(&path).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].kind, "b");
+
+        std::fs::write(&path, "\u{feff}{\"kind\":\"bom\",\"at\":0}\n").unwrap();
+        let events: Vec<Event> = load_lines(&path).unwrap();
+        assert_eq!(events[0].kind, "bom");
+
+        std::fs::write(&path, "{\"kind\":\"a\",\"at\":1}\n{\"kind\":\n").unwrap();
+        let result: Result<Vec<Event>, _> = load_lines(&path);
+        assert!(matches!(
+            result,
+            Err(StorageError::InvalidLine { line: 2, .. })
+        ));
+
+        std::fs::write(
+            &path,
+            b"{\"kind\":\"a\",\"at\":1}\r\n{\"kind\":\"\xff\",\"at\":2}\n",
+        )
+        .unwrap();
+        let result: Result<Vec<Event>, _> = load_lines(&path);
+        assert!(matches!(
+            result,
+            Err(StorageError::InvalidLine { line: 2, .. })
+        ));
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn append_line_gz_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("events.jsonl.gz");
+
+        for at in 0..3 {
+            let kind = "tick".to_string();
+            append_line_gz(&path, Event { kind, at }).unwrap();
+        }
+
+        let mut content = String::new();
+        let file = io::BufReader::new(std::fs::File::open(&path).unwrap());
+        io::Read::read_to_string(&mut MultiGzDecoder::new(file), &mut content).unwrap();
+        assert_eq!(content.lines().count(), 3);
+        assert_eq!(
+            content.lines().next().unwrap(),
+            "{\"kind\":\"tick\",\"at\":0}"
+        );
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn load_lines_gz_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("events.jsonl.gz");
+
+        append_line_gz(
+            &path,
+            Event {
+                kind: "a".to_string(),
+                at: 1,
+            },
+        )
+        .unwrap();
+        append_line_gz(
+            &path,
+            Event {
+                kind: "b".to_string(),
+                at: 2,
+            },
+        )
+        .unwrap();
+        let events: Vec<Event> = load_lines_gz(&path).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].kind, "b");
+
+        append_line_gz(&path, "not an event").unwrap();
+        let result: Result<Vec<Event>, _> = load_lines_gz(&path);
+        assert!(matches!(
+            result,
+            Err(StorageError::InvalidLine { line: 3, .. })
+        ));
+
+        std::fs::write(&path, "plain text").unwrap();
+        assert!(load_lines_gz::<_, Event>(&path).is_err());
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn load_lines_gz_truncated_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("events.jsonl.gz");
+
+        let kind = "first".to_string();
+        append_line_gz(&path, Event { kind, at: 1 }).unwrap();
+        let first_len = std::fs::metadata(&path).unwrap().len() as usize;
+        let kind = "second".repeat(20);
+        append_line_gz(&path, Event { kind, at: 2 }).unwrap();
+
+        let mut bytes = std::fs::read(&path).unwrap();
+        let second_len = bytes.len() - first_len;
+        bytes.truncate(first_len + second_len / 2);
+        std::fs::write(&path, bytes).unwrap();
+
+        let events: Vec<Event> = load_lines_gz(&path).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, "first");
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn load_lines_stream_test() {
+        use futures_util::StreamExt;
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("events.jsonl");
+        std::fs::write(
+            &path,
+            "\u{feff}{\"kind\":\"a\",\"at\":1}\n\nbroken\n{\"kind\":\"b\",\"at\":2}\n",
+        )
+        .unwrap();
+
+        let results: Vec<Result<Event, _>> = load_lines_stream(&path).collect().await;
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap().kind, "a");
+        assert!(matches!(
+            results[1],
+            Err(StorageError::InvalidLine { line: 3, .. })
+        ));
+        assert_eq!(results[2].as_ref().unwrap().at, 2);
+
+        let mut missing = std::pin::pin!(load_lines_stream::<_, Event>(dir.path().join("missing")));
+        assert!(matches!(
+            missing.next().await,
+            Some(Err(StorageError::Io(_)))
+        ));
+        assert!(missing.next().await.is_none());
+    }
+}
@@ -0,0 +1,328 @@
+//! Cross-process locking around [`save`] and [`load`].
+//!
+//! Locks are taken on a sidecar `<file>.lock` next to the data file rather than on the data file
+//! itself: [`save`] replaces the data file through a rename, so a lock held on the old file would
+//! not protect the new one. The lock file is intentionally left in place after use, since
+//! deleting it would race with other processes about to lock it.
+//!
+//! # Platform caveats
+//!
+//! * On Unix the locks are advisory (`flock`): they only coordinate processes that also go
+//!   through [`save_locked`] and [`load_locked`], and do nothing against a plain [`save`] or an
+//!   unrelated program writing the file.
+//! * On Windows the locks are mandatory for the lock file, which still only affects callers
+//!   that lock it.
+//! * Locking over network filesystems such as NFS or SMB depends on the server and client
+//!   configuration and may be silently ineffective.
+
+use std::{
+    fs::{File, TryLockError},
+    io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{Format, Json, StorageError, load, load_as, save, save_as, with_suffix};
+
+/// How to behave when the lock is already held by another process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LockMode {
+    /// Wait until the lock becomes available.
+    #[default]
+    Block,
+    /// Return [`StorageError::Locked`] immediately instead of waiting.
+    FailFast,
+}
+
+/// Synchronously saves data to a JSON file while holding an exclusive lock.
+///
+/// The lock is released when the function returns, including when serialization fails or
+/// panics. See the [module documentation](self) for platform caveats.
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+/// * `mode` - What to do if another process holds the lock
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - [`StorageError::Locked`] if the lock is held and `mode` is
+///   [`LockMode::FailFast`], or any error [`save`] can return
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{LockMode, save_locked, load_locked};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let path = dir.path().join("state.json");
+///
+/// save_locked(&path, 10, LockMode::Block).unwrap();
+/// let loaded: u32 = load_locked(&path, LockMode::FailFast).unwrap();
+/// assert_eq!(loaded, 10);
+/// ```
+pub fn save_locked<P, T>(path: P, data: T, mode: LockMode) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    let path = path.as_ref();
+    let _lock = lock(path, mode, File::lock, File::try_lock)?;
+
+    save(path, data)
+}
+
+/// Synchronously loads data from a JSON file while holding a shared lock.
+///
+/// Any number of readers can hold the shared lock at once, but none while a writer holds the
+/// exclusive lock taken by [`save_locked`]. See the [module documentation](self) for platform
+/// caveats.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+/// * `mode` - What to do if another process holds the exclusive lock
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - [`StorageError::Locked`] if the lock is held and `mode` is
+///   [`LockMode::FailFast`], or any error [`load`] can return
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{LockMode, save, load_locked};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let path = dir.path().join("state.json");
+/// save(&path, "ready").unwrap();
+///
+/// let state: String = load_locked(&path, LockMode::Block).unwrap();
+/// assert_eq!(state, "ready");
+/// ```
+pub fn load_locked<P, T>(path: P, mode: LockMode) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    let path = path.as_ref();
+    let _lock = lock(path, mode, File::lock_shared, File::try_lock_shared)?;
+
+    load(path)
+}
+
+/// Synchronously applies `f` to the value stored in a JSON file and saves the result.
+///
+/// The current value is loaded, or `T::default()` if the file does not exist yet, passed to
+/// `f` for modification, and written back atomically. An exclusive lock is held for the whole
+/// read-modify-write, so concurrent calls to `update`, [`save_locked`] and [`load_locked`] for
+/// the same file never lose or observe a partial update. If loading fails the file is left
+/// untouched. See the [module documentation](self) for platform caveats.
+///
+/// # Arguments
+///
+/// * `path` - The JSON file to update
+/// * `f` - Modifies the current value in place
+///
+/// # Returns
+///
+/// * `Ok(T)` - The new value, as written to disk
+/// * `Err(StorageError)` - If locking, reading or writing the file fails, or if
+///   (de)serialization fails; an existing file that cannot be loaded is reported as by
+///   [`load`], with [`StorageError::Parse`] or [`StorageError::Empty`] naming the file
+///
+/// # Example
+///
+/// ```
+/// use serde::{Serialize, Deserialize};
+/// use storage_service::update;
+/// use tempfile::tempdir;
+///
+/// #[derive(Serialize, Deserialize, Default)]
+/// struct Counter {
+///     hits: u64,
+/// }
+///
+/// let dir = tempdir().unwrap();
+/// let path = dir.path().join("counter.json");
+///
+/// update(&path, |counter: &mut Counter| counter.hits += 1).unwrap();
+/// let counter = update(&path, |counter: &mut Counter| counter.hits += 1).unwrap();
+/// assert_eq!(counter.hits, 2);
+/// ```
+pub fn update<P, T, F>(path: P, f: F) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize + DeserializeOwned + Default,
+    F: FnOnce(&mut T),
+{
+    update_as::<Json, _, _, _>(path, f)
+}
+
+/// Like [`update`], reading and writing the file in the given [`Format`].
+pub(crate) fn update_as<Fmt, P, T, F>(path: P, f: F) -> Result<T, StorageError>
+where
+    Fmt: Format,
+    P: AsRef<Path>,
+    T: Serialize + DeserializeOwned + Default,
+    F: FnOnce(&mut T),
+{
+    let path = path.as_ref();
+    let _lock = lock(path, LockMode::Block, File::lock, File::try_lock)?;
+
+    let mut data = match load_as::<Fmt, _, _>(path) {
+        Ok(data) => data,
+        Err(StorageError::Io(err)) if err.kind() == io::ErrorKind::NotFound => T::default(),
+        Err(err) => return Err(err),
+    };
+    f(&mut data);
+    save_as::<Fmt, _, _>(path, &data)?;
+
+    Ok(data)
+}
+
+/// Returns the sidecar lock file used for `path`.
+fn lock_path(path: &Path) -> PathBuf {
+    with_suffix(path, ".lock")
+}
+
+/// Opens the lock file for `path` and locks it with `block` or `try_lock` depending on `mode`.
+///
+/// The lock is held for as long as the returned file is alive.
+fn lock(
+    path: &Path,
+    mode: LockMode,
+    block: fn(&File) -> std::io::Result<()>,
+    try_lock: fn(&File) -> Result<(), TryLockError>,
+) -> Result<File, StorageError> {
+    let lock_path = lock_path(path);
+    let file = File::options()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&lock_path)?;
+
+    match mode {
+        LockMode::Block => block(&file)?,
+        LockMode::FailFast => match try_lock(&file) {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => return Err(StorageError::Locked(lock_path)),
+            Err(TryLockError::Error(err)) => return Err(StorageError::Io(err)),
+        },
+    }
+
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn save_locked_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("state.json");
+
+        save_locked(&path, vec![1, 2], LockMode::Block).unwrap();
+        let loaded: Vec<i32> = load(&path).unwrap();
+        assert_eq!(loaded, vec![1, 2]);
+
+        let held = File::options()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(lock_path(&path))
+            .unwrap();
+        held.lock_shared().unwrap();
+
+        let result = save_locked(&path, vec![3], LockMode::FailFast);
+        assert!(matches!(result, Err(StorageError::Locked(_))));
+
+        let loaded: Vec<i32> = load(&path).unwrap();
+        assert_eq!(loaded, vec![1, 2]);
+    }
+
+    #[test]
+    fn load_locked_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("state.json");
+        save(&path, "value").unwrap();
+
+        let reader = File::options()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(lock_path(&path))
+            .unwrap();
+        reader.lock_shared().unwrap();
+
+        let shared: String = load_locked(&path, LockMode::FailFast).unwrap();
+        assert_eq!(shared, "value");
+
+        reader.unlock().unwrap();
+        reader.lock().unwrap();
+
+        let result: Result<String, _> = load_locked(&path, LockMode::FailFast);
+        assert!(matches!(result, Err(StorageError::Locked(_))));
+    }
+
+    #[test]
+    fn lock_released_after_panic_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("state.json");
+
+        struct Panics;
+
+        impl Serialize for Panics {
+            fn serialize<S: serde::Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+                panic!("serializer panicked");
+            }
+        }
+
+        let panicking_path = path.clone();
+        let result = std::panic::catch_unwind(move || {
+            let _ = save_locked(&panicking_path, Panics, LockMode::Block);
+        });
+        assert!(result.is_err());
+
+        save_locked(&path, 1, LockMode::FailFast).unwrap();
+    }
+
+    #[test]
+    fn update_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("counter.json");
+
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..25 {
+                        update(&path, |hits: &mut u32| *hits += 1).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        assert_eq!(load::<_, u32>(&path).unwrap(), 200);
+
+        std::fs::write(&path, "corrupt").unwrap();
+        let result = update(&path, |hits: &mut u32| *hits = 0);
+        assert!(matches!(result, Err(StorageError::Parse { path: failed, .. }) if failed == path));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "corrupt");
+
+        std::fs::write(&path, "").unwrap();
+        let result = update(&path, |hits: &mut u32| *hits = 0);
+        assert!(matches!(result, Err(StorageError::Empty(_))));
+    }
+}
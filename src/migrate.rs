@@ -0,0 +1,152 @@
+use std::path::Path;
+
+use serde::de::{DeserializeOwned, Error as _};
+use serde_json::Value;
+
+use crate::{StorageError, load};
+
+/// Synchronously loads data from a JSON file whose schema may be older than `T`.
+///
+/// The file is first parsed as a [`serde_json::Value`] and its top-level `version` field is
+/// read; a missing field is treated as version `0`. The document and its version are then
+/// passed to `migrate`, which is expected to upgrade older shapes to the current schema
+/// (typically by matching on the version and applying each step in turn) before the result is
+/// deserialized into `T`. The file on disk is left untouched; save the loaded value to persist
+/// the migration.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+/// * `migrate` - Upgrades the raw document from the given version to the current schema
+///
+/// # Returns
+///
+/// * `Ok(T)` - The migrated and deserialized data
+/// * `Err(StorageError)` - If file reading or parsing fails, if `version` is not a
+///   non-negative integer, if `migrate` fails, or if the migrated document does not match `T`
+///
+/// # Example
+///
+/// ```
+/// use serde::Deserialize;
+/// use serde_json::{Value, json};
+/// use storage_service::{save, load_migrated};
+/// use tempfile::NamedTempFile;
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     version: u64,
+///     hosts: Vec<String>,
+/// }
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// // Version 1 stored a single `host` string.
+/// save(temp_file.path(), json!({ "version": 1, "host": "localhost" })).unwrap();
+///
+/// let config: Config = load_migrated(temp_file.path(), |mut doc: Value, version| {
+///     if version < 2 {
+///         let host = doc["host"].take();
+///         doc["hosts"] = json!([host]);
+///         doc["version"] = json!(2);
+///     }
+///     Ok(doc)
+/// })
+/// .unwrap();
+///
+/// assert_eq!(config.version, 2);
+/// assert_eq!(config.hosts, ["localhost"]);
+/// ```
+pub fn load_migrated<P, T, F>(path: P, migrate: F) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+    F: FnOnce(Value, u64) -> Result<Value, StorageError>,
+{
+    let path = path.as_ref();
+    let document: Value = load(path)?;
+    let version = schema_version(&document).map_err(|err| err.in_file(path))?;
+    let document = migrate(document, version)?;
+
+    serde_json::from_value(document)
+        .map_err(|err| StorageError::from_deserialize(err).in_file(path))
+}
+
+/// Reads the top-level `version` field, defaulting to `0` when it is absent.
+fn schema_version(document: &Value) -> Result<u64, StorageError> {
+    match document.get("version") {
+        None => Ok(0),
+        Some(version) => version.as_u64().ok_or_else(|| {
+            StorageError::Deserialize(serde_json::Error::custom(format!(
+                "invalid schema version: {version}"
+            )))
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+    use serde_json::json;
+    use tempfile::NamedTempFile;
+
+    use super::*;
+    use crate::save;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Settings {
+        version: u64,
+        name: String,
+        retries: u32,
+    }
+
+    fn upgrade(mut doc: Value, version: u64) -> Result<Value, StorageError> {
+        if version < 1 {
+            doc["name"] = doc["title"].take();
+            doc.as_object_mut().unwrap().remove("title");
+        }
+        if version < 2 {
+            doc["retries"] = json!(3);
+        }
+        doc["version"] = json!(2);
+        Ok(doc)
+    }
+
+    #[test]
+    fn load_migrated_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        // No version field: migrated through every step.
+        save(path, json!({ "title": "legacy" })).unwrap();
+        let loaded: Settings = load_migrated(path, upgrade).unwrap();
+        assert_eq!(
+            loaded,
+            Settings {
+                version: 2,
+                name: "legacy".to_string(),
+                retries: 3,
+            }
+        );
+
+        // Already current: passed through unchanged.
+        save(
+            path,
+            json!({ "version": 2, "name": "current", "retries": 7 }),
+        )
+        .unwrap();
+        let loaded: Settings = load_migrated(path, upgrade).unwrap();
+        assert_eq!(loaded.retries, 7);
+
+        save(
+            path,
+            json!({ "version": "two", "name": "bad", "retries": 1 }),
+        )
+        .unwrap();
+        let result: Result<Settings, _> = load_migrated(path, upgrade);
+        assert!(matches!(result, Err(StorageError::Parse { path: failed, .. }) if failed == path));
+
+        save(path, json!({ "version": 2, "name": "incomplete" })).unwrap();
+        let result: Result<Settings, _> = load_migrated(path, upgrade);
+        assert!(matches!(result, Err(StorageError::Parse { path: failed, .. }) if failed == path));
+    }
+}
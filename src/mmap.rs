@@ -0,0 +1,115 @@
+use std::{io, path::Path};
+
+use memmap2::Mmap;
+use serde::de::DeserializeOwned;
+
+use crate::{StorageError, parse_file};
+
+/// Synchronously loads data from a JSON file by memory-mapping it instead of reading it.
+///
+/// The file is mapped into memory and parsed directly from the mapped pages, so its raw
+/// contents are never copied onto the heap; for large, read-mostly files this saves an
+/// allocation the size of the file and lets the operating system share the pages between
+/// processes. The mapping is released before this function returns. For small files, [`load`]
+/// is usually just as fast.
+///
+/// # Safety
+///
+/// The file must not be modified or truncated, by this or any other process, while it is being
+/// parsed. The mapped bytes are treated as an immutable slice, and changing them underneath the
+/// parser is undefined behavior; truncating the file can also crash the process with `SIGBUS`.
+/// Files written by [`save`](crate::save) are safe in this respect as long as they are only
+/// ever replaced by renaming (which leaves the mapped file intact), not written in place.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - With [`StorageError::Empty`] if the file is empty, or if opening or
+///   mapping the file fails, or if deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save, load_mmap};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save(temp_file.path(), vec![1.5, 2.5]).unwrap();
+///
+/// // SAFETY: nothing else writes to the file while it is loaded.
+/// let loaded: Vec<f64> = unsafe { load_mmap(temp_file.path()) }.unwrap();
+/// assert_eq!(loaded, vec![1.5, 2.5]);
+/// ```
+///
+/// [`load`]: crate::load
+pub unsafe fn load_mmap<P, T>(path: P) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    let path = path.as_ref();
+    let file = std::fs::File::open(path)?;
+
+    // Empty files cannot be mapped on every platform.
+    if file.metadata()?.len() == 0 {
+        return Err(StorageError::Empty(path.to_path_buf()));
+    }
+
+    // SAFETY: the caller guarantees that the file is not modified while it is mapped.
+    let mapped = unsafe { Mmap::map(&file) }.map_err(|err| {
+        io::Error::new(
+            err.kind(),
+            format!("failed to memory-map {}: {err}", path.display()),
+        )
+    })?;
+
+    parse_file(path, &mapped)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use tempfile::{NamedTempFile, tempdir};
+
+    use super::*;
+    use crate::save;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Dataset {
+        name: String,
+        values: Vec<u32>,
+    }
+
+    #[test]
+    fn load_mmap_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+        let data = Dataset {
+            name: "reference".to_string(),
+            values: (0..10_000).collect(),
+        };
+        save(path, &data).unwrap();
+
+        let loaded: Dataset = unsafe { load_mmap(path) }.unwrap();
+        assert_eq!(loaded, data);
+
+        std::fs::write(path, "{").unwrap();
+        let malformed = unsafe { load_mmap::<_, Dataset>(path) };
+        assert!(matches!(malformed, Err(StorageError::Parse { .. })));
+    }
+
+    #[test]
+    fn load_mmap_empty_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let empty = unsafe { load_mmap::<_, Dataset>(temp_file.path()) };
+        assert!(matches!(empty, Err(StorageError::Empty(_))));
+
+        let dir = tempdir().unwrap();
+        let unmappable = unsafe { load_mmap::<_, Dataset>(dir.path()) };
+        assert!(matches!(unmappable, Err(StorageError::Io(_))));
+    }
+}
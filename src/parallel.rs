@@ -0,0 +1,182 @@
+use std::path::Path;
+#[cfg(feature = "async")]
+use std::sync::Arc;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use serde::de::DeserializeOwned;
+#[cfg(feature = "async")]
+use tokio::{sync::Semaphore, task::JoinSet};
+
+use crate::StorageError;
+#[cfg(feature = "parallel")]
+use crate::load;
+#[cfg(feature = "async")]
+use crate::load_async;
+
+/// Synchronously loads many JSON files in parallel.
+///
+/// The files are read and deserialized on the global `rayon` thread pool. Each file gets its
+/// own result, so one missing or corrupt file does not affect the others, and the results are
+/// returned in the same order as `paths`.
+///
+/// # Arguments
+///
+/// * `paths` - The file paths to read from
+///
+/// # Returns
+///
+/// * `Vec<Result<T, StorageError>>` - One result per path, in input order
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save, load_many};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let paths: Vec<_> = (0..3).map(|i| dir.path().join(format!("{i}.json"))).collect();
+/// save(&paths[0], 0).unwrap();
+/// save(&paths[2], 2).unwrap();
+///
+/// let results = load_many::<_, u32>(&paths);
+/// assert_eq!(results[0].as_ref().unwrap(), &0);
+/// assert!(results[1].is_err());
+/// assert_eq!(results[2].as_ref().unwrap(), &2);
+/// ```
+#[cfg(feature = "parallel")]
+pub fn load_many<P, T>(paths: &[P]) -> Vec<Result<T, StorageError>>
+where
+    P: AsRef<Path> + Sync,
+    T: DeserializeOwned + Send,
+{
+    paths.par_iter().map(load).collect()
+}
+
+/// Asynchronously loads many JSON files concurrently.
+///
+/// At most `limit` files are read at the same time, which keeps the number of open file
+/// descriptors bounded; a `limit` of zero is treated as one. Each file gets its own result, so
+/// one missing or corrupt file does not affect the others, and the results are returned in the
+/// same order as `paths`.
+///
+/// # Arguments
+///
+/// * `paths` - The file paths to read from
+/// * `limit` - The maximum number of files to load concurrently
+///
+/// # Returns
+///
+/// * `Vec<Result<T, StorageError>>` - One result per path, in input order
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save, load_many_async};
+/// use tempfile::tempdir;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let dir = tempdir().unwrap();
+///     let paths: Vec<_> = (0..10).map(|i| dir.path().join(format!("{i}.json"))).collect();
+///     for (i, path) in paths.iter().enumerate() {
+///         save(path, i).unwrap();
+///     }
+///
+///     let results = load_many_async::<_, usize>(paths, 4).await;
+///     let loaded: Vec<usize> = results.into_iter().map(Result::unwrap).collect();
+///     assert_eq!(loaded, (0..10).collect::<Vec<_>>());
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub async fn load_many_async<I, T>(paths: I, limit: usize) -> Vec<Result<T, StorageError>>
+where
+    I: IntoIterator,
+    I::Item: AsRef<Path> + Send + 'static,
+    T: DeserializeOwned + Send + 'static,
+{
+    let semaphore = Arc::new(Semaphore::new(limit.max(1)));
+    let mut tasks = JoinSet::new();
+
+    for (index, path) in paths.into_iter().enumerate() {
+        let semaphore = Arc::clone(&semaphore);
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            (index, load_async(path).await)
+        });
+    }
+
+    let mut results: Vec<_> = std::iter::repeat_with(|| None).take(tasks.len()).collect();
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok((index, result)) => results[index] = Some(result),
+            Err(err) => std::panic::resume_unwind(err.into_panic()),
+        }
+    }
+
+    results.into_iter().flatten().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::save;
+
+    fn fixtures(dir: &Path, count: usize) -> Vec<PathBuf> {
+        let paths: Vec<_> = (0..count)
+            .map(|i| dir.join(format!("session-{i}.json")))
+            .collect();
+        for (i, path) in paths.iter().enumerate() {
+            save(path, i).unwrap();
+        }
+        std::fs::write(&paths[count / 2], "corrupt").unwrap();
+        paths
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn load_many_test() {
+        let dir = tempdir().unwrap();
+        let paths = fixtures(dir.path(), 200);
+
+        let results = load_many::<_, usize>(&paths);
+
+        assert_eq!(results.len(), paths.len());
+        for (i, result) in results.into_iter().enumerate() {
+            match result {
+                Ok(value) => assert_eq!(value, i),
+                Err(err) => {
+                    assert_eq!(i, 100);
+                    assert!(matches!(err, StorageError::Parse { .. }));
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn load_many_async_test() {
+        let dir = tempdir().unwrap();
+        let paths = fixtures(dir.path(), 200);
+
+        let results = load_many_async::<_, usize>(paths.clone(), 8).await;
+
+        assert_eq!(results.len(), paths.len());
+        for (i, result) in results.into_iter().enumerate() {
+            match result {
+                Ok(value) => assert_eq!(value, i),
+                Err(err) => {
+                    assert_eq!(i, 100);
+                    assert!(matches!(err, StorageError::Parse { .. }));
+                }
+            }
+        }
+
+        let empty = load_many_async::<Vec<PathBuf>, usize>(Vec::new(), 0).await;
+        assert!(empty.is_empty());
+    }
+}
@@ -0,0 +1,240 @@
+use std::{io, path::Path};
+
+use json_patch::Patch;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{StorageError, load, save};
+
+/// Synchronously applies a JSON Merge Patch (RFC 7386) to a stored JSON file.
+///
+/// The file is read as a [`serde_json::Value`] and `patch` is serialized and merged into it:
+/// objects are merged recursively, `null` values remove the corresponding key, and any other
+/// value (including arrays) replaces what was there. Fields present in the file but not in
+/// `patch` are kept, which makes this suitable for partial updates of layered configuration.
+/// A missing file is treated as an empty object. The result is written back atomically.
+///
+/// Note that `Option` fields serialized as `null` therefore delete keys; mark them with
+/// `#[serde(skip_serializing_if = "Option::is_none")]` to leave them untouched instead.
+///
+/// # Arguments
+///
+/// * `path` - The JSON file to update
+/// * `patch` - The partial data to merge in (must implement `Serialize`)
+///
+/// # Returns
+///
+/// * `Ok(Value)` - The merged document as written to disk
+/// * `Err(StorageError)` - If reading or writing the file fails, or if (de)serialization fails
+///
+/// # Example
+///
+/// ```
+/// use serde_json::json;
+/// use storage_service::{save, merge, load};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save(temp_file.path(), json!({ "theme": "light", "font": { "size": 12, "family": "mono" } }))
+///     .unwrap();
+///
+/// merge(temp_file.path(), json!({ "theme": "dark", "font": { "size": 14 } })).unwrap();
+///
+/// let merged: serde_json::Value = load(temp_file.path()).unwrap();
+/// assert_eq!(merged, json!({ "theme": "dark", "font": { "size": 14, "family": "mono" } }));
+/// ```
+pub fn merge<P, T>(path: P, patch: T) -> Result<Value, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    let path = path.as_ref();
+
+    let mut document = match load(path) {
+        Ok(document) => document,
+        Err(StorageError::Io(err)) if err.kind() == io::ErrorKind::NotFound => Value::Null,
+        Err(err) => return Err(err),
+    };
+    let patch = serde_json::to_value(patch).map_err(StorageError::from_serialize)?;
+
+    merge_patch(&mut document, patch);
+    save(path, &document)?;
+
+    Ok(document)
+}
+
+/// Synchronously applies a JSON Patch (RFC 6902) to a stored JSON file.
+///
+/// The file is read as a [`serde_json::Value`], the operations of `patch` are applied in order,
+/// and the result is written back atomically. Unlike [`merge`], a patch addresses individual
+/// locations by JSON Pointer and can add, remove, replace, move, copy and test values, which
+/// makes it suitable for declarative edits and for undo stacks built from inverse patches.
+///
+/// The patch is all or nothing: if any operation fails, the file is left unchanged.
+///
+/// # Arguments
+///
+/// * `path` - The JSON file to update
+/// * `patch` - The operations to apply, as defined by the `json-patch` crate
+///
+/// # Returns
+///
+/// * `Ok(Value)` - The patched document as written to disk
+/// * `Err(StorageError)` - With [`StorageError::Patch`] if an operation cannot be applied, or
+///   if reading or writing the file fails, or if the file is not valid JSON
+///
+/// # Example
+///
+/// ```
+/// use json_patch::Patch;
+/// use serde_json::json;
+/// use storage_service::{save, apply_patch};
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save(temp_file.path(), json!({ "settings": { "theme": "light" } })).unwrap();
+///
+/// let patch: Patch = serde_json::from_value(json!([
+///     { "op": "replace", "path": "/settings/theme", "value": "dark" }
+/// ]))
+/// .unwrap();
+///
+/// let patched = apply_patch(temp_file.path(), &patch).unwrap();
+/// assert_eq!(patched, json!({ "settings": { "theme": "dark" } }));
+/// ```
+pub fn apply_patch<P>(path: P, patch: &Patch) -> Result<Value, StorageError>
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+
+    let mut document = load(path)?;
+    json_patch::patch(&mut document, patch).map_err(StorageError::Patch)?;
+    save(path, &document)?;
+
+    Ok(document)
+}
+
+/// Merges `patch` into `target` following RFC 7386.
+fn merge_patch(target: &mut Value, patch: Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch;
+        return;
+    };
+
+    if !target.is_object() {
+        *target = Value::Object(Default::default());
+    }
+
+    if let Value::Object(target) = target {
+        for (key, value) in patch {
+            if value.is_null() {
+                target.remove(&key);
+            } else {
+                merge_patch(target.entry(key).or_insert(Value::Null), value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    #[test]
+    fn merge_patch_test() {
+        // Examples from RFC 7386, appendix A.
+        let cases = [
+            (json!({"a": "b"}), json!({"a": "c"}), json!({"a": "c"})),
+            (
+                json!({"a": "b"}),
+                json!({"b": "c"}),
+                json!({"a": "b", "b": "c"}),
+            ),
+            (json!({"a": "b"}), json!({"a": null}), json!({})),
+            (
+                json!({"a": [{"b": "c"}]}),
+                json!({"a": [1]}),
+                json!({"a": [1]}),
+            ),
+            (json!(["a", "b"]), json!({"a": "b"}), json!({"a": "b"})),
+            (
+                json!({"e": null}),
+                json!({"a": 1}),
+                json!({"e": null, "a": 1}),
+            ),
+            (
+                json!({}),
+                json!({"a": {"bb": {"ccc": null}}}),
+                json!({"a": {"bb": {}}}),
+            ),
+        ];
+
+        for (mut target, patch, expected) in cases {
+            merge_patch(&mut target, patch);
+            assert_eq!(target, expected);
+        }
+    }
+
+    #[test]
+    fn merge_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+
+        #[derive(Serialize)]
+        struct ThemePatch {
+            theme: &'static str,
+        }
+
+        let merged = merge(&path, ThemePatch { theme: "dark" }).unwrap();
+        assert_eq!(merged, json!({"theme": "dark"}));
+
+        save(&path, json!({"theme": "light", "unmodeled": [1, 2]})).unwrap();
+        merge(&path, ThemePatch { theme: "dark" }).unwrap();
+
+        let stored: Value = load(&path).unwrap();
+        assert_eq!(stored, json!({"theme": "dark", "unmodeled": [1, 2]}));
+    }
+
+    #[test]
+    fn apply_patch_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        save(
+            &path,
+            json!({"settings": {"theme": "light"}, "tags": ["a"]}),
+        )
+        .unwrap();
+
+        let patch: Patch = serde_json::from_value(json!([
+            {"op": "replace", "path": "/settings/theme", "value": "dark"},
+            {"op": "add", "path": "/tags/-", "value": "b"},
+            {"op": "move", "from": "/settings", "path": "/display"},
+        ]))
+        .unwrap();
+        let patched = apply_patch(&path, &patch).unwrap();
+
+        let expected = json!({"display": {"theme": "dark"}, "tags": ["a", "b"]});
+        assert_eq!(patched, expected);
+        assert_eq!(load::<_, Value>(&path).unwrap(), expected);
+    }
+
+    #[test]
+    fn apply_patch_invalid_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        save(&path, json!({"theme": "light"})).unwrap();
+
+        let patch: Patch = serde_json::from_value(json!([
+            {"op": "replace", "path": "/theme", "value": "dark"},
+            {"op": "remove", "path": "/missing"},
+        ]))
+        .unwrap();
+        let result = apply_patch(&path, &patch);
+
+        assert!(matches!(result, Err(StorageError::Patch(err)) if err.operation == 1));
+        assert_eq!(load::<_, Value>(&path).unwrap(), json!({"theme": "light"}));
+    }
+}
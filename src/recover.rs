@@ -0,0 +1,216 @@
+use std::path::Path;
+
+use serde::de::{DeserializeOwned, Error as _};
+
+use crate::{StorageError, UTF8_BOM};
+
+/// The elements salvaged from a JSON array file by [`load_best_effort`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recovered<T> {
+    /// The complete elements, in file order.
+    pub items: Vec<T>,
+    /// Whether the array ended early, that is, whether anything after `items` was lost.
+    pub truncated: bool,
+}
+
+impl<T> Recovered<T> {
+    /// Returns the number of recovered elements.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns whether no element could be recovered.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+/// Synchronously loads the complete elements of a JSON array file, tolerating a truncated end.
+///
+/// A process that crashes while writing an array file leaves it without its closing bracket,
+/// often in the middle of an element, and [`load`](crate::load) then rejects the whole file.
+/// This function instead parses the elements one by one and stops at the point where the file
+/// ends, returning everything before it. Trailing NUL bytes, which some filesystems leave
+/// behind after a crash, are treated as the end of the file. A number that is not followed by
+/// `,` or `]` is dropped as well, since it may have lost digits.
+///
+/// Only a premature end is forgiven: a file that is not an array, an element that is malformed
+/// before the end of the file, or an element that does not match `T` is still an error.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(Recovered<T>)` - The complete elements and whether the array was truncated
+/// * `Err(StorageError)` - If file reading fails, or if the file is corrupt in any other way
+///
+/// # Example
+///
+/// ```
+/// use storage_service::load_best_effort;
+/// use tempfile::NamedTempFile;
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// std::fs::write(temp_file.path(), r#"[{"id":1},{"id":2},{"id""#).unwrap();
+///
+/// let recovered = load_best_effort::<_, serde_json::Value>(temp_file.path()).unwrap();
+/// assert_eq!(recovered.len(), 2);
+/// assert!(recovered.truncated);
+/// ```
+pub fn load_best_effort<P, T>(path: P) -> Result<Recovered<T>, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    let path = path.as_ref();
+    let json_data = std::fs::read(path)?;
+
+    parse_best_effort(&json_data).map_err(|err| StorageError::Deserialize(err).in_file(path))
+}
+
+fn parse_best_effort<T: DeserializeOwned>(
+    json_data: &[u8],
+) -> Result<Recovered<T>, serde_json::Error> {
+    let json_data = json_data.strip_prefix(UTF8_BOM).unwrap_or(json_data);
+    let end = json_data
+        .iter()
+        .rposition(|&byte| byte != 0)
+        .map_or(0, |index| index + 1);
+    let json_data = &json_data[..end];
+
+    let mut items = Vec::new();
+    let truncated = |items| {
+        Ok(Recovered {
+            items,
+            truncated: true,
+        })
+    };
+
+    let mut rest = skip_whitespace(json_data);
+    match rest.split_first() {
+        None => return truncated(items),
+        Some((b'[', tail)) => rest = skip_whitespace(tail),
+        Some(_) => return Err(serde_json::Error::custom("expected a JSON array")),
+    }
+    if let Some((b']', tail)) = rest.split_first() {
+        return finish(items, tail);
+    }
+
+    loop {
+        let mut stream = serde_json::Deserializer::from_slice(rest).into_iter::<T>();
+        match stream.next() {
+            None => return truncated(items),
+            Some(Err(err)) if err.is_eof() => return truncated(items),
+            Some(Err(err)) => return Err(err),
+            Some(Ok(item)) => items.push(item),
+        }
+        let (element, tail) = rest.split_at(stream.byte_offset());
+        rest = skip_whitespace(tail);
+
+        match rest.split_first() {
+            None => {
+                // A number is the only kind of value that still parses when cut short.
+                if element.last().is_some_and(u8::is_ascii_digit) {
+                    items.pop();
+                }
+                return truncated(items);
+            }
+            Some((b',', tail)) => rest = skip_whitespace(tail),
+            Some((b']', tail)) => return finish(items, tail),
+            Some(_) => {
+                return Err(serde_json::Error::custom(
+                    "expected `,` or `]` after an array element",
+                ));
+            }
+        }
+    }
+}
+
+/// Completes a fully closed array, rejecting anything but whitespace after it.
+fn finish<T>(items: Vec<T>, rest: &[u8]) -> Result<Recovered<T>, serde_json::Error> {
+    if !skip_whitespace(rest).is_empty() {
+        return Err(serde_json::Error::custom(
+            "trailing characters after the array",
+        ));
+    }
+
+    Ok(Recovered {
+        items,
+        truncated: false,
+    })
+}
+
+fn skip_whitespace(bytes: &[u8]) -> &[u8] {
+    let start = bytes
+        .iter()
+        .position(|byte| !byte.is_ascii_whitespace())
+        .unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use tempfile::NamedTempFile;
+
+    use super::*;
+    use crate::save;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+    struct Event {
+        id: u32,
+    }
+
+    #[test]
+    fn load_best_effort_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        let events: Vec<Event> = (1..=3).map(|id| Event { id }).collect();
+        save(path, &events).unwrap();
+        let complete = load_best_effort::<_, Event>(path).unwrap();
+        assert_eq!(complete.items, events);
+        assert!(!complete.truncated);
+
+        let full = std::fs::read(path).unwrap();
+        for cut in 0..full.len() - 1 {
+            let mut partial = full[..cut].to_vec();
+            partial.extend_from_slice(&[0; 8]);
+            std::fs::write(path, &partial).unwrap();
+
+            let recovered = load_best_effort::<_, Event>(path).unwrap();
+            assert!(recovered.truncated, "cut at {cut}");
+            assert_eq!(recovered.items, events[..recovered.len()]);
+        }
+
+        std::fs::write(path, "[10, 20, 30").unwrap();
+        let numbers = load_best_effort::<_, u32>(path).unwrap();
+        assert_eq!(numbers.items, vec![10, 20]);
+    }
+
+    #[test]
+    fn load_best_effort_corrupt_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        for corrupt in [
+            r#"{"id":1}"#,
+            r#"[{"id":1} {"id":2}]"#,
+            r#"[{"id":1},{"id":"two"},{"id":3"#,
+            r#"[{"id":1}] trailing"#,
+        ] {
+            std::fs::write(path, corrupt).unwrap();
+            let result = load_best_effort::<_, Event>(path);
+            assert!(
+                matches!(result, Err(StorageError::Parse { path: failed, .. }) if failed == path),
+                "{corrupt}"
+            );
+        }
+
+        std::fs::write(path, "[]").unwrap();
+        let empty = load_best_effort::<_, Event>(path).unwrap();
+        assert!(empty.is_empty() && !empty.truncated);
+    }
+}
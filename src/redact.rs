@@ -0,0 +1,136 @@
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{StorageError, save};
+
+/// The value that redacted fields are replaced with.
+const REDACTED: &str = "***";
+
+/// Synchronously saves data to a JSON file with the given fields replaced by `"***"`.
+///
+/// The data is serialized to a [`serde_json::Value`] first and only that copy is modified, so
+/// `data` itself is left untouched. Each entry of `redact` is a field name or a dotted path to a
+/// nested field, such as `"password"` or `"database.credentials.token"`; when the path passes
+/// through an array, the field is redacted in every element. Paths that do not match anything
+/// are ignored.
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+/// * `redact` - The field names or dotted paths to scrub
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - If file creation or writing fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use serde::Serialize;
+/// use serde_json::{Value, json};
+/// use storage_service::{save_redacted, load};
+/// use tempfile::NamedTempFile;
+///
+/// #[derive(Serialize)]
+/// struct Connection {
+///     host: String,
+///     password: String,
+/// }
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// let connection = Connection { host: "db".to_string(), password: "hunter2".to_string() };
+///
+/// save_redacted(temp_file.path(), &connection, &["password"]).unwrap();
+///
+/// let saved: Value = load(temp_file.path()).unwrap();
+/// assert_eq!(saved, json!({ "host": "db", "password": "***" }));
+/// assert_eq!(connection.password, "hunter2");
+/// ```
+pub fn save_redacted<P, T>(path: P, data: T, redact: &[&str]) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    let mut document = serde_json::to_value(data).map_err(StorageError::from_serialize)?;
+
+    for field in redact {
+        let keys: Vec<&str> = field.split('.').collect();
+        redact_path(&mut document, &keys);
+    }
+
+    save(path, &document)
+}
+
+/// Replaces the field at `keys` inside `value`, descending into every element of arrays.
+fn redact_path(value: &mut Value, keys: &[&str]) {
+    let Some((key, rest)) = keys.split_first() else {
+        return;
+    };
+
+    match value {
+        Value::Object(map) => match map.get_mut(*key) {
+            Some(field) if rest.is_empty() => *field = Value::from(REDACTED),
+            Some(field) => redact_path(field, rest),
+            None => {}
+        },
+        Value::Array(items) => {
+            for item in items {
+                redact_path(item, keys);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tempfile::NamedTempFile;
+
+    use super::*;
+    use crate::load;
+
+    #[test]
+    fn save_redacted_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        let data = json!({
+            "token": "abc",
+            "database": { "user": "app", "password": "secret" },
+            "users": [
+                { "name": "alice", "api_key": "k1" },
+                { "name": "bob", "api_key": "k2" },
+                "not an object"
+            ],
+            "count": 2
+        });
+        let redact = [
+            "token",
+            "database.password",
+            "users.api_key",
+            "missing.field",
+        ];
+        save_redacted(path, &data, &redact).unwrap();
+
+        let saved: Value = load(path).unwrap();
+        assert_eq!(
+            saved,
+            json!({
+                "token": "***",
+                "database": { "user": "app", "password": "***" },
+                "users": [
+                    { "name": "alice", "api_key": "***" },
+                    { "name": "bob", "api_key": "***" },
+                    "not an object"
+                ],
+                "count": 2
+            })
+        );
+        assert_eq!(data["token"], "abc");
+    }
+}
@@ -0,0 +1,222 @@
+use std::{io, path::Path};
+
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+
+use crate::{StorageError, load, write_atomic};
+
+/// Synchronously saves the same data to several replica files.
+///
+/// The data is serialized once, and the resulting bytes are written atomically to each path in
+/// turn, as by [`save`](crate::save). Every replica is attempted even if an earlier one fails,
+/// so a failed disk does not stop the others from being updated, and the outcome is reported per path.
+/// Read the data back with [`load_replicated`].
+///
+/// # Arguments
+///
+/// * `paths` - The replica file paths, typically on different disks
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+///
+/// # Returns
+///
+/// * `Ok(Vec<Result<usize, StorageError>>)` - For each path, in order, the number of bytes
+///   written or the error that prevented writing that replica
+/// * `Err(StorageError)` - If serialization fails, in which case no replica is written
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{load_replicated, save_replicated};
+/// use tempfile::tempdir;
+///
+/// let primary = tempdir().unwrap();
+/// let mirror = tempdir().unwrap();
+/// let paths = [
+///     primary.path().join("state.json"),
+///     mirror.path().join("state.json"),
+///     mirror.path().join("missing").join("state.json"),
+/// ];
+///
+/// let results = save_replicated(&paths, &vec![1, 2, 3]).unwrap();
+/// assert!(results[0].is_ok() && results[1].is_ok());
+/// assert!(results[2].is_err());
+///
+/// let loaded: Vec<u32> = load_replicated(&paths[..2], true).unwrap();
+/// assert_eq!(loaded, vec![1, 2, 3]);
+/// ```
+pub fn save_replicated<P, T>(
+    paths: &[P],
+    data: &T,
+) -> Result<Vec<Result<usize, StorageError>>, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize + ?Sized,
+{
+    let bytes = serde_json::to_vec(data).map_err(StorageError::from_serialize)?;
+
+    let results = paths
+        .iter()
+        .map(|path| {
+            write_atomic(path.as_ref(), |writer| {
+                writer.write_all(&bytes)?;
+                Ok(bytes.len())
+            })
+        })
+        .collect();
+
+    Ok(results)
+}
+
+/// Synchronously loads data from the first readable of several replica files.
+///
+/// The replicas are tried in order, and any that is missing or cannot be read or parsed is
+/// skipped in favour of the next, so one damaged replica does not make the data unavailable.
+///
+/// With `verify`, every replica is read and compared with the first readable one, as JSON
+/// values so that formatting differences do not count. If any differs or cannot be read, the
+/// load fails with [`StorageError::ReplicaMismatch`] listing those replicas, so divergence is
+/// noticed and can be repaired, for example by saving the correct data again with
+/// [`save_replicated`].
+///
+/// # Arguments
+///
+/// * `paths` - The replica file paths, in order of preference
+/// * `verify` - Whether to check that all replicas hold the same data
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data of the first readable replica
+/// * `Err(StorageError)` - [`StorageError::NotFound`] listing every path if none exist,
+///   [`StorageError::ReplicaMismatch`] if `verify` is set and the replicas disagree, or the
+///   first error other than a missing file if no replica could be loaded
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{StorageError, load_replicated, save};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let primary = dir.path().join("primary.json");
+/// let mirror = dir.path().join("mirror.json");
+/// std::fs::write(&primary, "{ corrupted").unwrap();
+/// save(&mirror, "good").unwrap();
+///
+/// let loaded: String = load_replicated(&[&primary, &mirror], false).unwrap();
+/// assert_eq!(loaded, "good");
+///
+/// let checked = load_replicated::<_, String>(&[&primary, &mirror], true);
+/// assert!(matches!(checked, Err(StorageError::ReplicaMismatch(paths)) if paths == [primary]));
+/// ```
+pub fn load_replicated<P, T>(paths: &[P], verify: bool) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    if !verify {
+        return first_readable(paths, |path| load(path)).map(|(_, data)| data);
+    }
+
+    let (index, value) = first_readable(paths, |path| load::<_, Value>(path))?;
+    let mismatched: Vec<_> = paths
+        .iter()
+        .enumerate()
+        .filter(|&(other, _)| other != index)
+        .map(|(_, path)| path.as_ref())
+        .filter(|path| !load::<_, Value>(path).is_ok_and(|other| other == value))
+        .map(Path::to_path_buf)
+        .collect();
+    if !mismatched.is_empty() {
+        return Err(StorageError::ReplicaMismatch(mismatched));
+    }
+
+    let path = paths[index].as_ref();
+    serde_json::from_value(value).map_err(|err| StorageError::from_deserialize(err).in_file(path))
+}
+
+/// Returns the index and result of the first replica that `load` succeeds for.
+fn first_readable<P, T, F>(paths: &[P], load: F) -> Result<(usize, T), StorageError>
+where
+    P: AsRef<Path>,
+    F: Fn(&Path) -> Result<T, StorageError>,
+{
+    let mut first_error = None;
+    for (index, path) in paths.iter().enumerate() {
+        match load(path.as_ref()) {
+            Ok(data) => return Ok((index, data)),
+            Err(StorageError::Io(err)) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => {
+                first_error.get_or_insert(err);
+            }
+        }
+    }
+
+    Err(first_error.unwrap_or_else(|| {
+        let tried = paths.iter().map(|path| path.as_ref().to_path_buf());
+        StorageError::NotFound(tried.collect())
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::save;
+
+    #[test]
+    fn save_replicated_test() {
+        let dir = tempdir().unwrap();
+        let paths = [
+            dir.path().join("a.json"),
+            dir.path().join("missing").join("b.json"),
+            dir.path().join("c.json"),
+        ];
+
+        let results = save_replicated(&paths, "state").unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(*results[0].as_ref().unwrap(), 7);
+        assert!(matches!(results[1], Err(StorageError::Io(_))));
+        assert_eq!(*results[2].as_ref().unwrap(), 7);
+        assert_eq!(
+            std::fs::read(&paths[0]).unwrap(),
+            std::fs::read(&paths[2]).unwrap()
+        );
+
+        assert!(save_replicated::<&Path, _>(&[], &1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn load_replicated_test() {
+        let dir = tempdir().unwrap();
+        let first = dir.path().join("first.json");
+        let second = dir.path().join("second.json");
+        let paths = [&first, &second];
+
+        let missing = load_replicated::<_, u32>(&paths, false);
+        assert!(matches!(missing, Err(StorageError::NotFound(tried)) if tried.len() == 2));
+
+        save(&second, 2).unwrap();
+        assert_eq!(load_replicated::<_, u32>(&paths, false).unwrap(), 2);
+        let incomplete = load_replicated::<_, u32>(&paths, true);
+        assert!(
+            matches!(incomplete, Err(StorageError::ReplicaMismatch(bad)) if bad == [first.clone()])
+        );
+
+        std::fs::write(&first, "{").unwrap();
+        assert_eq!(load_replicated::<_, u32>(&paths, false).unwrap(), 2);
+        std::fs::write(&second, "").unwrap();
+        let broken = load_replicated::<_, u32>(&paths, false);
+        assert!(matches!(broken, Err(StorageError::Parse { .. })));
+
+        save_replicated(&paths, &3).unwrap();
+        std::fs::write(&second, " 3 \n").unwrap();
+        assert_eq!(load_replicated::<_, u32>(&paths, true).unwrap(), 3);
+
+        save(&second, 4).unwrap();
+        let diverged = load_replicated::<_, u32>(&paths, true);
+        assert!(
+            matches!(diverged, Err(StorageError::ReplicaMismatch(bad)) if bad == [second.clone()])
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use std::{io, path::Path, sync::Arc, time::Duration};
+
+use serde::Serialize;
+
+use crate::{SaveOptions, StorageError, write_atomic_async, write_json};
+
+/// How often and how patiently [`save_async_retry`] retries transient failures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryConfig {
+    /// The total number of attempts, including the first one. Values below 1 are treated as 1.
+    pub max_attempts: u32,
+    /// The delay before the first retry; each further retry waits twice as long as the previous.
+    pub base_delay: Duration,
+}
+
+impl RetryConfig {
+    /// Creates a retry configuration.
+    pub fn new(max_attempts: u32, base_delay: Duration) -> Self {
+        Self {
+            max_attempts,
+            base_delay,
+        }
+    }
+
+    /// Returns the delay to wait after the given failed attempt (1-based).
+    fn delay(&self, attempt: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+    }
+}
+
+impl Default for RetryConfig {
+    /// Three attempts, starting with a 50 ms delay.
+    fn default() -> Self {
+        Self::new(3, Duration::from_millis(50))
+    }
+}
+
+/// Asynchronously saves data to a JSON file, retrying transient I/O failures with exponential
+/// backoff.
+///
+/// The data is serialized once up front; only the write is retried. Only the
+/// [`io::ErrorKind`]s `Interrupted`, `WouldBlock` and `TimedOut` are considered transient, as
+/// seen on networked filesystems. Serialization errors and every other I/O error, such as
+/// `PermissionDenied`, are returned immediately.
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`, `Send`, and `'static`)
+/// * `config` - The number of attempts and the backoff delay
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if one of the attempts succeeds
+/// * `Err(StorageError)` - The error of the last attempt if all attempts fail, or the first
+///   non-transient error
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use storage_service::{RetryConfig, save_async_retry};
+/// use tempfile::NamedTempFile;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let temp_file = NamedTempFile::new().unwrap();
+///     let config = RetryConfig::new(5, Duration::from_millis(10));
+///
+///     save_async_retry(temp_file.path(), vec![1, 2, 3], config).await.unwrap();
+/// }
+/// ```
+pub async fn save_async_retry<P, T>(
+    path: P,
+    data: T,
+    config: RetryConfig,
+) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize + Send + 'static,
+{
+    let path = path.as_ref();
+    let json_data: Arc<[u8]> = tokio::task::spawn_blocking(move || {
+        let mut json_data = Vec::new();
+        write_json(&mut json_data, &data, &SaveOptions::default())?;
+        Ok::<_, StorageError>(json_data)
+    })
+    .await??
+    .into();
+
+    let mut attempt = 1;
+    loop {
+        let bytes = Arc::clone(&json_data);
+        let result = write_atomic_async(path, move |writer| {
+            writer.write_all(&bytes)?;
+            Ok(bytes.len())
+        })
+        .await;
+
+        match result {
+            Err(StorageError::Io(err)) if is_transient(&err) && attempt < config.max_attempts => {
+                tokio::time::sleep(config.delay(attempt)).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Returns whether `err` is worth retrying.
+fn is_transient(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn retry_config_delay_test() {
+        let config = RetryConfig::new(4, Duration::from_millis(10));
+
+        assert_eq!(config.delay(1), Duration::from_millis(10));
+        assert_eq!(config.delay(2), Duration::from_millis(20));
+        assert_eq!(config.delay(3), Duration::from_millis(40));
+    }
+
+    #[test]
+    fn is_transient_test() {
+        assert!(is_transient(&io::ErrorKind::Interrupted.into()));
+        assert!(is_transient(&io::ErrorKind::WouldBlock.into()));
+        assert!(is_transient(&io::ErrorKind::TimedOut.into()));
+        assert!(!is_transient(&io::ErrorKind::PermissionDenied.into()));
+        assert!(!is_transient(&io::ErrorKind::NotFound.into()));
+    }
+
+    #[tokio::test]
+    async fn save_async_retry_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("data.json");
+
+        save_async_retry(&path, vec![4, 5], RetryConfig::default())
+            .await
+            .unwrap();
+        let loaded: Vec<i32> = crate::load(&path).unwrap();
+        assert_eq!(loaded, vec![4, 5]);
+
+        let started = std::time::Instant::now();
+        let missing_dir = dir.path().join("missing").join("data.json");
+        let config = RetryConfig::new(5, Duration::from_secs(1));
+        let result = save_async_retry(&missing_dir, 1, config).await;
+
+        assert!(matches!(
+            result,
+            Err(StorageError::Io(err)) if err.kind() == io::ErrorKind::NotFound
+        ));
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+}
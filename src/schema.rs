@@ -0,0 +1,145 @@
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{StorageError, load};
+
+/// Synchronously loads data from a JSON file after validating it against a JSON Schema.
+///
+/// The file is first loaded as a [`serde_json::Value`] and checked with the `jsonschema`
+/// crate; only if it conforms is it deserialized into `T`. Every violation is collected, not
+/// just the first, each naming the location in the document as a JSON Pointer, which makes the
+/// errors far more helpful than serde's for hand-edited files. The schema draft is detected
+/// from its `$schema` keyword. Remote `$ref`s are not resolved.
+///
+/// Available with the `schema` feature.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+/// * `schema` - The JSON Schema the file must conform to
+///
+/// # Returns
+///
+/// * `Ok(T)` - The validated and deserialized data
+/// * `Err(StorageError)` - [`StorageError::SchemaViolation`] listing every violation if the
+///   file does not conform, [`StorageError::Validation`] if `schema` itself is invalid, or if
+///   file reading or deserialization fails
+///
+/// # Example
+///
+/// ```
+/// use serde_json::json;
+/// use storage_service::{StorageError, load_schema_validated, save};
+/// use tempfile::NamedTempFile;
+///
+/// let schema = json!({
+///     "type": "object",
+///     "properties": { "port": { "type": "integer", "maximum": 65535 } },
+///     "required": ["port"]
+/// });
+///
+/// let temp_file = NamedTempFile::new().unwrap();
+/// save(temp_file.path(), json!({ "port": 70000 })).unwrap();
+///
+/// let result = load_schema_validated::<_, serde_json::Value>(temp_file.path(), &schema);
+/// let Err(StorageError::SchemaViolation(violations)) = result else { panic!() };
+/// assert_eq!(violations, ["/port: 70000 is greater than the maximum of 65535"]);
+/// ```
+pub fn load_schema_validated<P, T>(path: P, schema: &Value) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    let path = path.as_ref();
+    let validator = jsonschema::validator_for(schema)
+        .map_err(|err| StorageError::Validation(format!("invalid JSON schema: {err}")))?;
+
+    let document: Value = load(path)?;
+    let violations: Vec<String> = validator
+        .iter_errors(&document)
+        .map(|err| format!("{}: {err}", pointer(&err.instance_path().to_string())))
+        .collect();
+    if !violations.is_empty() {
+        return Err(StorageError::SchemaViolation(violations));
+    }
+
+    serde_json::from_value(document)
+        .map_err(|err| StorageError::from_deserialize(err).in_file(path))
+}
+
+/// Names the whole document `/` rather than with the empty pointer.
+fn pointer(location: &str) -> &str {
+    if location.is_empty() { "/" } else { location }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+    use serde_json::json;
+    use tempfile::NamedTempFile;
+
+    use super::*;
+    use crate::save;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Server {
+        host: String,
+        port: u16,
+    }
+
+    fn schema() -> Value {
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": {
+                "host": { "type": "string", "minLength": 1 },
+                "port": { "type": "integer", "minimum": 1, "maximum": 65535 }
+            },
+            "required": ["host", "port"]
+        })
+    }
+
+    #[test]
+    fn load_schema_validated_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path();
+
+        save(path, json!({ "host": "localhost", "port": 8080 })).unwrap();
+        let server: Server = load_schema_validated(path, &schema()).unwrap();
+        assert_eq!(server.port, 8080);
+
+        save(path, json!({ "host": "", "port": 0 })).unwrap();
+        let Err(StorageError::SchemaViolation(mut violations)) =
+            load_schema_validated::<_, Server>(path, &schema())
+        else {
+            panic!("expected a schema violation");
+        };
+        violations.sort();
+        assert_eq!(violations.len(), 2);
+        assert!(violations[0].starts_with("/host: "), "{violations:?}");
+        assert!(violations[1].starts_with("/port: "), "{violations:?}");
+
+        save(path, json!([])).unwrap();
+        let Err(StorageError::SchemaViolation(violations)) =
+            load_schema_validated::<_, Server>(path, &schema())
+        else {
+            panic!("expected a schema violation");
+        };
+        assert!(violations[0].starts_with("/: "), "{violations:?}");
+    }
+
+    #[test]
+    fn load_schema_validated_invalid_schema_test() {
+        let temp_file = NamedTempFile::new().unwrap();
+        save(temp_file.path(), json!({})).unwrap();
+
+        let result =
+            load_schema_validated::<_, Value>(temp_file.path(), &json!({ "type": "nonsense" }));
+        assert!(matches!(result, Err(StorageError::Validation(_))));
+
+        let mismatched = load_schema_validated::<_, Server>(temp_file.path(), &json!({}));
+        assert!(matches!(mismatched, Err(StorageError::Parse { .. })));
+    }
+}
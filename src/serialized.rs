@@ -0,0 +1,208 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, LazyLock, Mutex, MutexGuard, Weak},
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+
+use crate::{StorageError, load_async, save_async};
+
+/// The in-process locks of the paths currently being accessed through this module.
+///
+/// Entries hold weak references, so a lock lives only as long as some task holds or waits for
+/// it, and dead entries are swept whenever a lock is released.
+static REGISTRY: LazyLock<Mutex<HashMap<PathBuf, Weak<AsyncMutex<()>>>>> =
+    LazyLock::new(Default::default);
+
+/// Asynchronously saves data to a JSON file, serialized with other accesses to the same path.
+///
+/// Concurrent calls to this function and [`load_async_serialized`] for the same path within
+/// this process run one at a time, in the order they started waiting, while different paths
+/// still proceed in parallel. Paths are made absolute but not canonicalized, so the same file
+/// reached through a symbolic link or `..` component is treated as a different path. Calls to
+/// the plain [`save_async`] are not coordinated; use [`save_locked`](crate::save_locked) to
+/// guard against other processes.
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds
+/// * `Err(StorageError)` - Any error [`save_async`] can return
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{load_async_serialized, save_async_serialized};
+/// use tempfile::NamedTempFile;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let temp_file = NamedTempFile::new().unwrap();
+///     let path = temp_file.path();
+///
+///     let (first, second) = tokio::join!(
+///         save_async_serialized(path, "first"),
+///         save_async_serialized(path, "second"),
+///     );
+///     assert!(first.is_ok() && second.is_ok());
+///
+///     let loaded: String = load_async_serialized(path).await.unwrap();
+///     assert!(loaded == "first" || loaded == "second");
+/// }
+/// ```
+pub async fn save_async_serialized<P, T>(path: P, data: T) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize + Send + 'static,
+{
+    let path = path.as_ref();
+    let _guard = PathGuard::acquire(path).await?;
+
+    save_async(path, data).await
+}
+
+/// Asynchronously loads data from a JSON file, serialized with other accesses to the same path.
+///
+/// See [`save_async_serialized`] for how calls are coordinated.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - Any error [`load_async`] can return
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save, load_async_serialized};
+/// use tempfile::NamedTempFile;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let temp_file = NamedTempFile::new().unwrap();
+///     save(temp_file.path(), 7).unwrap();
+///
+///     let loaded: i32 = load_async_serialized(temp_file.path()).await.unwrap();
+///     assert_eq!(loaded, 7);
+/// }
+/// ```
+pub async fn load_async_serialized<P, T>(path: P) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned + Send + 'static,
+{
+    let path = path.as_ref();
+    let _guard = PathGuard::acquire(path).await?;
+
+    load_async(path).await
+}
+
+/// Holds the in-process lock of a path, removing it from the registry once unused.
+struct PathGuard {
+    guard: Option<OwnedMutexGuard<()>>,
+}
+
+impl PathGuard {
+    async fn acquire(path: &Path) -> Result<Self, StorageError> {
+        let key = std::path::absolute(path)?;
+
+        let lock = {
+            let mut registry = registry();
+            match registry.get(&key).and_then(Weak::upgrade) {
+                Some(lock) => lock,
+                None => {
+                    let lock = Arc::new(AsyncMutex::new(()));
+                    registry.insert(key, Arc::downgrade(&lock));
+                    lock
+                }
+            }
+        };
+
+        Ok(Self {
+            guard: Some(lock.lock_owned().await),
+        })
+    }
+}
+
+impl Drop for PathGuard {
+    fn drop(&mut self) {
+        // Release the lock before sweeping, so an uncontended entry is seen as dead.
+        drop(self.guard.take());
+        registry().retain(|_, lock| lock.strong_count() > 0);
+    }
+}
+
+/// Locks the registry, recovering from poisoning since the map is always left consistent.
+fn registry() -> MutexGuard<'static, HashMap<PathBuf, Weak<AsyncMutex<()>>>> {
+    REGISTRY
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn is_registered(path: &Path) -> bool {
+        registry().contains_key(&std::path::absolute(path).unwrap())
+    }
+
+    #[tokio::test]
+    async fn save_async_serialized_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("shared.json");
+
+        let first = PathGuard::acquire(&path).await.unwrap();
+        assert!(is_registered(&path));
+
+        // A save to the same path waits for the guard, a save elsewhere does not.
+        let waiting = tokio::spawn(save_async_serialized(path.clone(), 1));
+        save_async_serialized(dir.path().join("other.json"), 2)
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!waiting.is_finished());
+        assert!(!path.exists());
+
+        drop(first);
+        waiting.await.unwrap().unwrap();
+        assert_eq!(crate::load::<_, i32>(&path).unwrap(), 1);
+
+        let mut saves = tokio::task::JoinSet::new();
+        for i in 0..20 {
+            saves.spawn(save_async_serialized(path.clone(), i));
+        }
+        while let Some(result) = saves.join_next().await {
+            result.unwrap().unwrap();
+        }
+        assert!(!is_registered(&path));
+        assert!(!is_registered(&dir.path().join("other.json")));
+    }
+
+    #[tokio::test]
+    async fn load_async_serialized_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("shared.json");
+        crate::save(&path, "value").unwrap();
+
+        let loaded: String = load_async_serialized(&path).await.unwrap();
+        assert_eq!(loaded, "value");
+
+        let missing = load_async_serialized::<_, String>(dir.path().join("missing.json")).await;
+        assert!(matches!(missing, Err(StorageError::Io(_))));
+        assert!(!is_registered(&path));
+    }
+}
@@ -0,0 +1,161 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{HashAlgo, SaveOptions, StorageError, load, save_with};
+
+/// A set of JSON files spread over nested subdirectories by a hash of their keys.
+///
+/// Each key is hashed with SHA-256, and the file is stored under its hex digest, with the
+/// first `depth` pairs of hex digits as directory levels: with a depth of 2, the key whose
+/// digest is `abcd12...` is stored at `base/ab/cd/abcd12....json`. Every level has at most 256
+/// entries, so even millions of files never end up in one huge directory that is slow to list
+/// and stat. The scheme depends only on the key and the depth, so a key always maps to the
+/// same file; keys may be any string, since they never become part of a path themselves.
+/// Shard directories are created as needed, and files are written atomically as by
+/// [`save`](crate::save).
+///
+/// Because files are named by digest, the keys cannot be listed back from the directory; use
+/// [`Store`](crate::Store) when that is needed.
+///
+/// # Example
+///
+/// ```
+/// use storage_service::ShardedStore;
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let store = ShardedStore::new(dir.path(), 2);
+///
+/// store.save("user:42", &"Ada").unwrap();
+/// let name: String = store.load("user:42").unwrap();
+/// assert_eq!(name, "Ada");
+///
+/// let path = store.path("user:42");
+/// assert_eq!(path.parent().unwrap().parent().unwrap().parent().unwrap(), dir.path());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ShardedStore {
+    base: PathBuf,
+    depth: usize,
+}
+
+impl ShardedStore {
+    /// The largest supported depth, one level per byte of the SHA-256 digest.
+    pub const MAX_DEPTH: usize = 32;
+
+    /// Creates a store rooted at `base` with `depth` levels of shard directories.
+    ///
+    /// A depth of 0 stores every file directly in `base`. Each level multiplies the number of
+    /// directories by 256, so 1 or 2 levels are enough for most workloads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `depth` is greater than [`MAX_DEPTH`](Self::MAX_DEPTH).
+    pub fn new<P: AsRef<Path>>(base: P, depth: usize) -> Self {
+        assert!(
+            depth <= Self::MAX_DEPTH,
+            "shard depth {depth} exceeds the maximum of {}",
+            Self::MAX_DEPTH
+        );
+
+        Self {
+            base: base.as_ref().to_path_buf(),
+            depth,
+        }
+    }
+
+    /// Returns the base directory of the store.
+    pub fn base(&self) -> &Path {
+        &self.base
+    }
+
+    /// Returns the number of shard directory levels.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the file path of `key`.
+    pub fn path(&self, key: &str) -> PathBuf {
+        let digest = HashAlgo::Sha256.digest(key.as_bytes());
+
+        let mut path = self.base.clone();
+        for level in 0..self.depth {
+            path.push(&digest[level * 2..level * 2 + 2]);
+        }
+        path.push(format!("{digest}.json"));
+
+        path
+    }
+
+    /// Saves `data` as JSON under `key`, returning the number of bytes written.
+    pub fn save<T>(&self, key: &str, data: &T) -> Result<usize, StorageError>
+    where
+        T: Serialize + ?Sized,
+    {
+        save_with(self.path(key), data, &SaveOptions::new().create_dirs(true))
+    }
+
+    /// Loads and deserializes the JSON file stored under `key`.
+    pub fn load<T>(&self, key: &str) -> Result<T, StorageError>
+    where
+        T: DeserializeOwned,
+    {
+        load(self.path(key))
+    }
+
+    /// Deletes the file stored under `key`.
+    ///
+    /// Shard directories are left in place, even when they become empty.
+    pub fn delete(&self, key: &str) -> Result<(), StorageError> {
+        crate::delete(self.path(key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn sharded_store_test() {
+        let dir = tempdir().unwrap();
+        let store = ShardedStore::new(dir.path().join("objects"), 2);
+
+        for id in 0..50 {
+            store.save(&format!("user:{id}"), &id).unwrap();
+        }
+        assert_eq!(store.load::<u32>("user:7").unwrap(), 7);
+        assert_eq!(store.load::<u32>("../../etc/passwd").ok(), None);
+
+        let digest = HashAlgo::Sha256.digest(b"user:7");
+        let expected = dir
+            .path()
+            .join("objects")
+            .join(&digest[0..2])
+            .join(&digest[2..4])
+            .join(format!("{digest}.json"));
+        assert_eq!(store.path("user:7"), expected);
+        assert!(expected.is_file());
+
+        store.delete("user:7").unwrap();
+        assert!(!expected.exists());
+        assert!(store.delete("user:7").is_err());
+    }
+
+    #[test]
+    fn sharded_store_depth_test() {
+        let dir = tempdir().unwrap();
+
+        let flat = ShardedStore::new(dir.path(), 0);
+        assert_eq!(flat.path("key").parent().unwrap(), dir.path());
+
+        let deepest = ShardedStore::new(dir.path(), ShardedStore::MAX_DEPTH);
+        deepest.save("key", &true).unwrap();
+        assert!(deepest.load::<bool>("key").unwrap());
+
+        let too_deep = std::panic::catch_unwind(|| ShardedStore::new("base", 33));
+        assert!(too_deep.is_err());
+    }
+}
@@ -0,0 +1,127 @@
+use std::{
+    io::{self, Write},
+    path::Path,
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{StorageError, load, load_from_reader, save, save_to_writer};
+
+/// Returns whether `path` is the conventional `-` placeholder for standard input or output.
+fn is_stdio(path: &Path) -> bool {
+    path.as_os_str() == "-"
+}
+
+/// Synchronously saves data as JSON to a file, or to standard output if the path is `-`.
+///
+/// This follows the command-line convention of treating `-` as standard output, so a tool can
+/// take an output path argument and still be used in a pipeline. Any other path is passed to
+/// [`save`] unchanged. Standard output is written directly, without the temporary file and
+/// rename, followed by a newline, and is flushed before returning. A file that really is named
+/// `-` can still be written with [`save`], or through this function as `./-`.
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved, or `-` for standard output
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes of JSON written, if the operation succeeds
+/// * `Err(StorageError)` - If writing fails or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::save_or_stdout;
+///
+/// // Prints `{"ok":true}`.
+/// save_or_stdout("-", serde_json::json!({ "ok": true })).unwrap();
+/// ```
+pub fn save_or_stdout<P, T>(path: P, data: T) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize,
+{
+    let path = path.as_ref();
+    if !is_stdio(path) {
+        return save(path, data);
+    }
+
+    let mut stdout = io::stdout().lock();
+    let written = save_to_writer(&mut stdout, &data)?;
+    stdout.write_all(b"\n")?;
+    stdout.flush()?;
+
+    Ok(written)
+}
+
+/// Synchronously loads data from a JSON file, or from standard input if the path is `-`.
+///
+/// The counterpart of [`save_or_stdout`]: standard input is read to its end and parsed as a
+/// single JSON value, and any other path is passed to [`load`] unchanged.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from, or `-` for standard input
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - If reading fails or if deserialization fails
+///
+/// # Example
+///
+/// ```no_run
+/// use storage_service::{load_or_stdin, save_or_stdout};
+///
+/// // `cat data.json | mytool - -` copies the value through.
+/// let args: Vec<String> = std::env::args().collect();
+/// let value: serde_json::Value = load_or_stdin(&args[1]).unwrap();
+/// save_or_stdout(&args[2], value).unwrap();
+/// ```
+pub fn load_or_stdin<P, T>(path: P) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+{
+    let path = path.as_ref();
+    if !is_stdio(path) {
+        return load(path);
+    }
+
+    load_from_reader(io::stdin().lock())
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn is_stdio_test() {
+        assert!(is_stdio(Path::new("-")));
+        assert!(!is_stdio(Path::new("./-")));
+        assert!(!is_stdio(Path::new("data.json")));
+    }
+
+    #[test]
+    fn save_or_stdout_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("data.json");
+
+        save_or_stdout(&path, vec![1, 2]).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "[1,2]");
+    }
+
+    #[test]
+    fn load_or_stdin_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("data.json");
+        save(&path, "from file").unwrap();
+
+        let loaded: String = load_or_stdin(&path).unwrap();
+        assert_eq!(loaded, "from file");
+    }
+}
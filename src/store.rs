@@ -0,0 +1,166 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{SaveOptions, StorageError, load, sanitize_filename, save_with};
+
+/// A set of JSON files rooted at a base directory, addressed by relative keys.
+///
+/// Keys are relative file names such as `settings.json` or `users/42.json`; they are joined
+/// onto the base directory with [`sanitize_filename`], so a key containing `..` or an absolute
+/// path is rejected instead of reaching outside it. Saving a key creates the intermediate
+/// directories it needs, and files are written atomically as by [`save`](crate::save).
+///
+/// # Example
+///
+/// ```
+/// use storage_service::Store;
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let store = Store::new(dir.path());
+///
+/// store.save("users/42.json", &"Ada").unwrap();
+/// let name: String = store.load("users/42.json").unwrap();
+/// assert_eq!(name, "Ada");
+///
+/// assert!(store.save("../outside.json", &"nope").is_err());
+/// assert_eq!(store.list().unwrap(), vec!["users/42.json"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Store {
+    base: PathBuf,
+}
+
+impl Store {
+    /// Creates a store rooted at `base`, which is created on the first save if needed.
+    pub fn new<P: AsRef<Path>>(base: P) -> Self {
+        Self {
+            base: base.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Returns the base directory of the store.
+    pub fn base(&self) -> &Path {
+        &self.base
+    }
+
+    /// Returns the file path of `key`, or an error if the key would escape the base directory.
+    pub fn path(&self, key: &str) -> Result<PathBuf, StorageError> {
+        sanitize_filename(&self.base, key)
+    }
+
+    /// Saves `data` as JSON under `key`, returning the number of bytes written.
+    pub fn save<T>(&self, key: &str, data: &T) -> Result<usize, StorageError>
+    where
+        T: Serialize + ?Sized,
+    {
+        save_with(self.path(key)?, data, &SaveOptions::new().create_dirs(true))
+    }
+
+    /// Loads and deserializes the JSON file stored under `key`.
+    pub fn load<T>(&self, key: &str) -> Result<T, StorageError>
+    where
+        T: DeserializeOwned,
+    {
+        load(self.path(key)?)
+    }
+
+    /// Deletes the file stored under `key`.
+    pub fn delete(&self, key: &str) -> Result<(), StorageError> {
+        crate::delete(self.path(key)?)
+    }
+
+    /// Lists the keys in the store, sorted, with `/` separating subdirectories.
+    ///
+    /// Subdirectories are searched recursively. Hidden entries, whose names start with `.`,
+    /// are skipped; these include the temporary files of saves in progress. Names that are not
+    /// valid UTF-8 cannot be keys and are skipped as well. A base directory that does not exist
+    /// yet holds no keys.
+    pub fn list(&self) -> Result<Vec<String>, StorageError> {
+        let mut keys = Vec::new();
+        match collect_keys(&self.base, "", &mut keys) {
+            Err(err) if err.kind() == io::ErrorKind::NotFound && !self.base.exists() => {}
+            result => result?,
+        }
+        keys.sort();
+
+        Ok(keys)
+    }
+}
+
+/// Appends the keys below `dir`, each prefixed with `prefix`, to `keys`.
+fn collect_keys(dir: &Path, prefix: &str, keys: &mut Vec<String>) -> Result<(), io::Error> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        if name.starts_with('.') {
+            continue;
+        }
+
+        let key = format!("{prefix}{name}");
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_keys(&entry.path(), &format!("{key}/"), keys)?;
+        } else if entry.path().is_file() {
+            keys.push(key);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn store_test() {
+        let dir = tempdir().unwrap();
+        let store = Store::new(dir.path().join("data"));
+        assert!(store.list().unwrap().is_empty());
+
+        store.save("settings.json", &true).unwrap();
+        store.save("users/1.json", "Ada").unwrap();
+        store.save("./users/2.json", "Grace").unwrap();
+        assert!(dir.path().join("data/users/2.json").exists());
+        assert_eq!(store.load::<String>("users/1.json").unwrap(), "Ada");
+
+        std::fs::write(dir.path().join("data/.hidden.tmp"), "").unwrap();
+        assert_eq!(
+            store.list().unwrap(),
+            vec!["settings.json", "users/1.json", "users/2.json"]
+        );
+
+        store.delete("users/1.json").unwrap();
+        assert_eq!(store.list().unwrap(), vec!["settings.json", "users/2.json"]);
+    }
+
+    #[test]
+    fn store_traversal_test() {
+        let dir = tempdir().unwrap();
+        let store = Store::new(dir.path().join("data"));
+
+        for key in [
+            "../escape.json",
+            "/etc/passwd",
+            "users/../../escape.json",
+            "",
+        ] {
+            assert!(
+                matches!(store.save(key, &1), Err(StorageError::Io(err)) if err.kind() == io::ErrorKind::InvalidInput),
+                "{key}"
+            );
+            assert!(store.load::<u32>(key).is_err(), "{key}");
+            assert!(store.delete(key).is_err(), "{key}");
+        }
+        assert!(!dir.path().join("escape.json").exists());
+    }
+}
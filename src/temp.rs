@@ -0,0 +1,123 @@
+use std::{
+    io::{self, Write},
+    path::Path,
+};
+
+use serde::Serialize;
+use tempfile::TempPath;
+
+use crate::{SaveOptions, StorageError, copy, write_json};
+
+/// A JSON file in the system temporary directory that is deleted when dropped.
+///
+/// Created by [`save_temp`]. The file is closed once written, so its [`path`](Self::path) can be
+/// handed to another process, and it is removed when the guard goes out of scope unless it is
+/// kept with [`persist`](Self::persist).
+#[derive(Debug)]
+pub struct TempDocument {
+    path: TempPath,
+}
+
+impl TempDocument {
+    /// Returns the location of the temporary file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Moves the file to `path` instead of deleting it, replacing any existing file there.
+    ///
+    /// The file is renamed when possible and copied when `path` is on a different filesystem
+    /// from the temporary directory. A renamed file keeps the owner-only permissions it was
+    /// created with. If this fails, the temporary file is still removed.
+    pub fn persist<P: AsRef<Path>>(self, path: P) -> Result<(), StorageError> {
+        let path = path.as_ref();
+
+        match self.path.persist(path) {
+            Ok(()) => Ok(()),
+            Err(err) if err.error.kind() == io::ErrorKind::CrossesDevices => {
+                copy(&err.path, path, true)?;
+                Ok(())
+            }
+            Err(err) => Err(StorageError::Io(err.error)),
+        }
+    }
+}
+
+/// Synchronously saves data to a new temporary JSON file that is deleted when no longer needed.
+///
+/// The file is created with a unique `.json` name in the system temporary directory, only
+/// readable by the current user on Unix. It lives as long as the returned guard.
+///
+/// # Arguments
+///
+/// * `data` - The data to serialize and save (must implement `Serialize`)
+///
+/// # Returns
+///
+/// * `Ok(TempDocument)` - A guard owning the temporary file
+/// * `Err(StorageError)` - If file creation or writing fails, or if serialization fails
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{save_temp, load};
+///
+/// let temp = save_temp(&vec!["--verbose", "--dry-run"]).unwrap();
+/// let path = temp.path().to_path_buf();
+///
+/// let args: Vec<String> = load(&path).unwrap();
+/// assert_eq!(args, ["--verbose", "--dry-run"]);
+///
+/// drop(temp);
+/// assert!(!path.exists());
+/// ```
+pub fn save_temp<T>(data: &T) -> Result<TempDocument, StorageError>
+where
+    T: Serialize + ?Sized,
+{
+    let file = tempfile::Builder::new()
+        .prefix(".storage-")
+        .suffix(".json")
+        .tempfile()?;
+
+    let (file, path) = file.into_parts();
+    let mut writer = io::BufWriter::new(file);
+    write_json(&mut writer, data, &SaveOptions::default())?;
+    writer.flush()?;
+
+    Ok(TempDocument { path })
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::load;
+
+    #[test]
+    fn save_temp_test() {
+        let temp = save_temp(&[1, 2, 3]).unwrap();
+        let path = temp.path().to_path_buf();
+
+        assert_eq!(path.extension().unwrap(), "json");
+        assert_eq!(load::<_, Vec<i32>>(&path).unwrap(), vec![1, 2, 3]);
+
+        drop(temp);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn temp_document_persist_test() {
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("kept.json");
+        std::fs::write(&target, "old").unwrap();
+
+        let temp = save_temp("kept").unwrap();
+        let temp_path = temp.path().to_path_buf();
+        temp.persist(&target).unwrap();
+
+        assert!(!temp_path.exists());
+        assert_eq!(load::<_, String>(&target).unwrap(), "kept");
+    }
+}
@@ -0,0 +1,159 @@
+use std::{path::Path, time::Duration};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{StorageError, load_async, save_async};
+
+/// Asynchronously loads data from a JSON file, giving up after `timeout`.
+///
+/// This bounds the latency of [`load_async`] when the underlying storage hangs, for example on
+/// a stale network mount.
+///
+/// # Arguments
+///
+/// * `path` - The file path to read from
+/// * `timeout` - The maximum time to wait for the load to finish
+///
+/// # Returns
+///
+/// * `Ok(T)` - The deserialized data if successful
+/// * `Err(StorageError)` - [`StorageError::Timeout`] if the load took too long, or any error
+///   [`load_async`] can return
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use storage_service::{save, load_async_timeout};
+/// use tempfile::NamedTempFile;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let temp_file = NamedTempFile::new().unwrap();
+///     save(temp_file.path(), "fast").unwrap();
+///
+///     let loaded: String = load_async_timeout(temp_file.path(), Duration::from_secs(5))
+///         .await
+///         .unwrap();
+///     assert_eq!(loaded, "fast");
+/// }
+/// ```
+pub async fn load_async_timeout<P, T>(path: P, timeout: Duration) -> Result<T, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned + Send + 'static,
+{
+    tokio::time::timeout(timeout, load_async(path))
+        .await
+        .map_err(|_| StorageError::Timeout(timeout))?
+}
+
+/// Asynchronously saves data to a JSON file, giving up after `timeout`.
+///
+/// If the timeout elapses, the in-progress write is abandoned and its temporary file removed;
+/// the destination keeps its previous contents.
+///
+/// # Arguments
+///
+/// * `path` - The file path where data will be saved
+/// * `data` - The data to serialize and save (must implement `Serialize`, `Send`, and `'static`)
+/// * `timeout` - The maximum time to wait for the save to finish
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of bytes written, if the operation succeeds in time
+/// * `Err(StorageError)` - [`StorageError::Timeout`] if the save took too long, or any error
+///   [`save_async`] can return
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use storage_service::save_async_timeout;
+/// use tempfile::NamedTempFile;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let temp_file = NamedTempFile::new().unwrap();
+///
+///     save_async_timeout(temp_file.path(), 1, Duration::from_secs(5)).await.unwrap();
+/// }
+/// ```
+pub async fn save_async_timeout<P, T>(
+    path: P,
+    data: T,
+    timeout: Duration,
+) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    T: Serialize + Send + 'static,
+{
+    tokio::time::timeout(timeout, save_async(path, data))
+        .await
+        .map_err(|_| StorageError::Timeout(timeout))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{load, save};
+    use tempfile::tempdir;
+
+    /// Serializes successfully, but only after blocking for a while.
+    struct Slow;
+
+    impl Serialize for Slow {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            std::thread::sleep(Duration::from_millis(300));
+            serializer.serialize_str("slow")
+        }
+    }
+
+    #[tokio::test]
+    async fn load_async_timeout_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("data.json");
+        save(&path, 5).unwrap();
+
+        let loaded: u32 = load_async_timeout(&path, Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(loaded, 5);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn load_async_timeout_elapsed_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("hung.json");
+
+        // Opening a FIFO for reading blocks until a writer shows up, like a hung mount.
+        let status = std::process::Command::new("mkfifo")
+            .arg(&path)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let result: Result<u32, _> = load_async_timeout(&path, Duration::from_millis(50)).await;
+        assert!(matches!(result, Err(StorageError::Timeout(_))));
+
+        // Unblock the abandoned reader so the runtime can shut down.
+        let _ = std::fs::write(&path, "5");
+    }
+
+    #[tokio::test]
+    async fn save_async_timeout_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("data.json");
+        save(&path, "previous").unwrap();
+
+        let result = save_async_timeout(&path, Slow, Duration::from_millis(50)).await;
+        assert!(matches!(result, Err(StorageError::Timeout(_))));
+
+        tokio::time::sleep(Duration::from_millis(400)).await;
+
+        let loaded: String = load(&path).unwrap();
+        assert_eq!(loaded, "previous");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
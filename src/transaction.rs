@@ -0,0 +1,253 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+
+use crate::{
+    SaveOptions, StorageError, parent_dir, sync_dir, try_load, write_atomic_with, write_json,
+};
+
+/// A batch of saves that is applied all-or-nothing, using a write-ahead log.
+///
+/// Writes are [`stage`](Transaction::stage)d in memory and only touch the disk on
+/// [`commit`](Transaction::commit), which first records every pending write in the log file,
+/// then applies them one by one, and finally removes the log. If the process crashes while the
+/// writes are being applied, the log is still there on the next run, and
+/// [`Transaction::recover`] replays it to finish the transaction. If it crashes before the log
+/// is complete, none of the writes happen. Call [`Transaction::recover`] on startup, before
+/// committing new transactions with the same log.
+///
+/// This makes a batch of saves atomic as a whole, but it is not a database: concurrent
+/// transactions on overlapping files are not isolated from each other.
+///
+/// # Example
+///
+/// ```
+/// use storage_service::{Transaction, load};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let wal = dir.path().join("state.wal");
+///
+/// // Finish any transaction interrupted by a crash in a previous run.
+/// Transaction::recover(&wal).unwrap();
+///
+/// let mut transaction = Transaction::new(&wal);
+/// transaction.stage(dir.path().join("from.json"), &90).unwrap();
+/// transaction.stage(dir.path().join("to.json"), &110).unwrap();
+/// transaction.commit().unwrap();
+///
+/// let balance: u32 = load(dir.path().join("to.json")).unwrap();
+/// assert_eq!(balance, 110);
+/// assert!(!wal.exists());
+/// ```
+#[derive(Debug)]
+pub struct Transaction {
+    wal_path: PathBuf,
+    writes: Vec<LoggedWrite>,
+}
+
+/// A single pending write, as recorded in the log.
+#[derive(Debug, Serialize, Deserialize)]
+struct LoggedWrite {
+    path: PathBuf,
+    data: Box<RawValue>,
+}
+
+impl Transaction {
+    /// Creates an empty transaction that logs to `wal_path`.
+    pub fn new<P: AsRef<Path>>(wal_path: P) -> Self {
+        Self {
+            wal_path: wal_path.as_ref().to_path_buf(),
+            writes: Vec::new(),
+        }
+    }
+
+    /// Serializes `data` to be saved to `path` as JSON when the transaction is committed.
+    ///
+    /// Nothing is written yet. Staging the same path twice keeps both writes, so the later one
+    /// wins. A relative `path` is resolved against the current directory now, so that
+    /// [`Transaction::recover`] writes to the same file even if run from elsewhere.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file path where data will be saved
+    /// * `data` - The data to serialize and save (must implement `Serialize`)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the data was serialized and staged
+    /// * `Err(StorageError)` - If serialization fails, or if the current directory is needed to
+    ///   resolve `path` and cannot be determined
+    pub fn stage<P, T>(&mut self, path: P, data: &T) -> Result<(), StorageError>
+    where
+        P: AsRef<Path>,
+        T: Serialize + ?Sized,
+    {
+        let data = serde_json::value::to_raw_value(data).map_err(StorageError::from_serialize)?;
+        self.writes.push(LoggedWrite {
+            path: std::path::absolute(path)?,
+            data,
+        });
+
+        Ok(())
+    }
+
+    /// Returns the number of staged writes.
+    pub fn len(&self) -> usize {
+        self.writes.len()
+    }
+
+    /// Returns whether no write has been staged.
+    pub fn is_empty(&self) -> bool {
+        self.writes.is_empty()
+    }
+
+    /// Applies the staged writes, logging them first so that they survive a crash.
+    ///
+    /// The log is written and synced to disk atomically, then each file is saved atomically and
+    /// synced, and the log is removed, with its directory synced, once all of them are in
+    /// place. An empty transaction does nothing. Dropping a transaction without committing it
+    /// discards its writes.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If every staged write was applied
+    /// * `Err(StorageError)` - With an [`io::ErrorKind::AlreadyExists`] error if a previous
+    ///   transaction's log has not been recovered yet, or if writing the log or any file fails;
+    ///   once the log is written, [`Transaction::recover`] completes the remaining writes
+    pub fn commit(self) -> Result<(), StorageError> {
+        if self.writes.is_empty() {
+            return Ok(());
+        }
+
+        // Creating the log exclusively, rather than checking for it first, keeps two concurrent
+        // commits from both claiming it.
+        write_atomic_with(&self.wal_path, &durable().create_new(true), |writer| {
+            write_json(writer, &self.writes, &SaveOptions::default())
+        })
+        .map_err(|err| match err {
+            StorageError::AlreadyExists(path) => StorageError::Io(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("unrecovered write-ahead log: {}", path.display()),
+            )),
+            err => err,
+        })?;
+
+        apply(&self.wal_path, &self.writes).map(drop)
+    }
+
+    /// Completes a transaction that was interrupted after its log was written.
+    ///
+    /// If the log at `wal_path` exists, every write recorded in it is applied again, which is
+    /// harmless for writes that had already been applied, and the log is removed. Without a
+    /// log, there is nothing to do.
+    ///
+    /// # Arguments
+    ///
+    /// * `wal_path` - The log file passed to [`Transaction::new`]
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The number of writes replayed, zero if there was no log
+    /// * `Err(StorageError)` - If the log cannot be read, or if writing any file fails
+    pub fn recover<P: AsRef<Path>>(wal_path: P) -> Result<usize, StorageError> {
+        let wal_path = wal_path.as_ref();
+        let Some(writes) = try_load::<_, Vec<LoggedWrite>>(wal_path)? else {
+            return Ok(0);
+        };
+
+        apply(wal_path, &writes)
+    }
+}
+
+/// Saves every logged write, then removes the log.
+fn apply(wal_path: &Path, writes: &[LoggedWrite]) -> Result<usize, StorageError> {
+    for write in writes {
+        write_atomic_with(&write.path, &durable(), |writer| {
+            writer.write_all(write.data.get().as_bytes())?;
+            Ok(())
+        })?;
+    }
+
+    // The removal must be durable too, or the log could reappear after a power loss and be
+    // replayed over newer writes.
+    std::fs::remove_file(wal_path)?;
+    sync_dir(parent_dir(wal_path))?;
+
+    Ok(writes.len())
+}
+
+fn durable() -> SaveOptions {
+    SaveOptions::new().durable(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::{load, save};
+
+    #[test]
+    fn commit_test() {
+        let dir = tempdir().unwrap();
+        let wal = dir.path().join("state.wal");
+        let (a, b) = (dir.path().join("a.json"), dir.path().join("b.json"));
+        save(&a, "old").unwrap();
+
+        let mut transaction = Transaction::new(&wal);
+        transaction.stage(&a, "new").unwrap();
+        transaction.stage(&b, &json!({ "count": 1 })).unwrap();
+        transaction.stage(&b, &json!({ "count": 2 })).unwrap();
+        assert_eq!(transaction.len(), 3);
+        assert_eq!(load::<_, String>(&a).unwrap(), "old");
+
+        transaction.commit().unwrap();
+        assert_eq!(load::<_, String>(&a).unwrap(), "new");
+        assert_eq!(
+            load::<_, serde_json::Value>(&b).unwrap(),
+            json!({ "count": 2 })
+        );
+        assert!(!wal.exists());
+
+        std::fs::write(&wal, "[]").unwrap();
+        let mut blocked = Transaction::new(&wal);
+        blocked.stage(&a, "newer").unwrap();
+        assert!(matches!(
+            blocked.commit(),
+            Err(StorageError::Io(err)) if err.kind() == io::ErrorKind::AlreadyExists
+        ));
+        assert_eq!(load::<_, String>(&a).unwrap(), "new");
+        assert_eq!(std::fs::read_to_string(&wal).unwrap(), "[]");
+
+        let mut relative = Transaction::new(&wal);
+        relative.stage("relative.json", &1).unwrap();
+        let expected = std::env::current_dir().unwrap().join("relative.json");
+        assert_eq!(relative.writes[0].path, expected);
+    }
+
+    #[test]
+    fn recover_test() {
+        let dir = tempdir().unwrap();
+        let wal = dir.path().join("state.wal");
+        let (a, b) = (dir.path().join("a.json"), dir.path().join("b.json"));
+        assert_eq!(Transaction::recover(&wal).unwrap(), 0);
+
+        // Simulate a crash after the log was written and the first file was applied.
+        let mut transaction = Transaction::new(&wal);
+        transaction.stage(&a, &1).unwrap();
+        transaction.stage(&b, &[2, 3]).unwrap();
+        save(&wal, &transaction.writes).unwrap();
+        save(&a, 1).unwrap();
+
+        assert_eq!(Transaction::recover(&wal).unwrap(), 2);
+        assert_eq!(load::<_, u32>(&a).unwrap(), 1);
+        assert_eq!(load::<_, Vec<u32>>(&b).unwrap(), vec![2, 3]);
+        assert!(!wal.exists());
+    }
+}
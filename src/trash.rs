@@ -0,0 +1,250 @@
+use std::{
+    ffi::{OsStr, OsString},
+    io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{Clock, StorageError, SystemClock, parent_dir};
+
+/// The name of the crate-managed trash directory created next to trashed files.
+const TRASH_DIR: &str = ".trash";
+
+/// Moves a stored file to the trash instead of deleting it permanently.
+///
+/// With the `trash` feature (enabled by default), the file is moved to the platform trash or
+/// recycle bin, where users can restore it with their usual tools. If there is no system trash,
+/// as on headless servers and in many containers, or without the feature, the file is instead
+/// moved into a `.trash` directory next to it, named after the time of deletion so that
+/// repeated deletions of the same name are all kept. Use [`purge_trash`] to reap old entries
+/// from that directory.
+///
+/// # Arguments
+///
+/// * `path` - The file to move to the trash
+///
+/// # Returns
+///
+/// * `Ok(())` - If the file was moved to the trash
+/// * `Err(StorageError)` - With an [`io::ErrorKind::NotFound`] error if the file does not exist,
+///   or if moving it fails
+///
+/// # Example
+///
+/// ```no_run
+/// use storage_service::{save, trash};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let path = dir.path().join("draft.json");
+/// save(&path, "unfinished").unwrap();
+///
+/// trash(&path).unwrap();
+/// assert!(!path.exists());
+/// ```
+pub fn trash<P>(path: P) -> Result<(), StorageError>
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    std::fs::symlink_metadata(path)?;
+
+    #[cfg(feature = "trash")]
+    if ::trash::delete(path).is_ok() {
+        return Ok(());
+    }
+
+    move_to_local_trash(path, SystemTime::now())?;
+
+    Ok(())
+}
+
+/// Permanently deletes entries older than `older_than` from the `.trash` directory of `dir`.
+///
+/// Only the crate-managed directory used by [`trash`] as a fallback is affected; files in the
+/// system trash are left to the operating system. The age of an entry is the time it was
+/// trashed, not its modification time. Entries not created by [`trash`] are left untouched.
+///
+/// # Arguments
+///
+/// * `dir` - The directory whose `.trash` subdirectory is purged
+/// * `older_than` - The minimum age of the entries to delete
+///
+/// # Returns
+///
+/// * `Ok(usize)` - The number of entries deleted, zero if there is no `.trash` directory
+/// * `Err(StorageError)` - If reading the directory or deleting an entry fails
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use storage_service::purge_trash;
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let purged = purge_trash(dir.path(), Duration::from_secs(30 * 24 * 60 * 60)).unwrap();
+/// assert_eq!(purged, 0);
+/// ```
+pub fn purge_trash<P>(dir: P, older_than: Duration) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+{
+    purge_trash_with_clock(dir, older_than, &SystemClock)
+}
+
+/// Like [`purge_trash`], measuring the age of entries against the time of `clock`.
+pub fn purge_trash_with_clock<P, C>(
+    dir: P,
+    older_than: Duration,
+    clock: &C,
+) -> Result<usize, StorageError>
+where
+    P: AsRef<Path>,
+    C: Clock + ?Sized,
+{
+    let trash_dir = dir.as_ref().join(TRASH_DIR);
+    let cutoff = clock.now().checked_sub(older_than).unwrap_or(UNIX_EPOCH);
+
+    let entries = match std::fs::read_dir(&trash_dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut purged = 0;
+    for entry in entries {
+        let entry = entry?;
+        let Some(trashed_at) = trashed_at(&entry.file_name()) else {
+            continue;
+        };
+        if trashed_at >= cutoff {
+            continue;
+        }
+
+        if entry.file_type()?.is_dir() {
+            std::fs::remove_dir_all(entry.path())?;
+        } else {
+            std::fs::remove_file(entry.path())?;
+        }
+        purged += 1;
+    }
+
+    Ok(purged)
+}
+
+/// Moves `path` into the `.trash` directory next to it as `<millis>-<name>`, returning the new
+/// path.
+fn move_to_local_trash(path: &Path, now: SystemTime) -> Result<PathBuf, io::Error> {
+    let name = path.file_name().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("cannot trash {}", path.display()),
+        )
+    })?;
+    let trash_dir = parent_dir(path).join(TRASH_DIR);
+    std::fs::create_dir_all(&trash_dir)?;
+
+    let mut millis = now
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    loop {
+        let mut entry_name = OsString::from(format!("{millis}-"));
+        entry_name.push(name);
+        let target = trash_dir.join(entry_name);
+
+        // Bumping the timestamp keeps every deletion of the same name within one millisecond.
+        if target.symlink_metadata().is_ok() {
+            millis += 1;
+            continue;
+        }
+        std::fs::rename(path, &target)?;
+        return Ok(target);
+    }
+}
+
+/// Parses the deletion time from a `.trash` entry name created by [`move_to_local_trash`].
+fn trashed_at(entry_name: &OsStr) -> Option<SystemTime> {
+    let (millis, _) = entry_name.to_str()?.split_once('-')?;
+    let millis: u64 = millis.parse().ok()?;
+
+    UNIX_EPOCH.checked_add(Duration::from_millis(millis))
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::{load, save};
+
+    #[test]
+    fn trash_test() {
+        let dir = tempdir().unwrap();
+        let missing = trash(dir.path().join("missing.json"));
+        assert!(matches!(
+            missing,
+            Err(StorageError::Io(err)) if err.kind() == io::ErrorKind::NotFound
+        ));
+    }
+
+    #[test]
+    fn move_to_local_trash_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("note.json");
+        let now = UNIX_EPOCH + Duration::from_millis(1_000);
+
+        save(&path, "first").unwrap();
+        let first = move_to_local_trash(&path, now).unwrap();
+        save(&path, "second").unwrap();
+        let second = move_to_local_trash(&path, now).unwrap();
+
+        assert!(!path.exists());
+        assert_eq!(first, dir.path().join(".trash").join("1000-note.json"));
+        assert_eq!(second, dir.path().join(".trash").join("1001-note.json"));
+        assert_eq!(load::<_, String>(&first).unwrap(), "first");
+        assert_eq!(load::<_, String>(&second).unwrap(), "second");
+    }
+
+    #[test]
+    fn purge_trash_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("note.json");
+        let day = Duration::from_secs(24 * 60 * 60);
+
+        save(&path, "old").unwrap();
+        move_to_local_trash(&path, SystemTime::now() - 10 * day).unwrap();
+        save(&path, "recent").unwrap();
+        let recent = move_to_local_trash(&path, SystemTime::now()).unwrap();
+        std::fs::write(dir.path().join(".trash").join("README"), "").unwrap();
+
+        assert_eq!(purge_trash(dir.path(), 7 * day).unwrap(), 1);
+        assert!(recent.exists());
+        assert!(dir.path().join(".trash").join("README").exists());
+
+        assert_eq!(purge_trash(dir.path().join("nested"), day).unwrap(), 0);
+    }
+
+    #[test]
+    fn purge_trash_with_clock_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("note.json");
+        let day = Duration::from_secs(24 * 60 * 60);
+        let clock = crate::ManualClock::new(UNIX_EPOCH + 100 * day);
+
+        save(&path, "old").unwrap();
+        move_to_local_trash(&path, clock.now()).unwrap();
+
+        clock.advance(day);
+        assert_eq!(
+            purge_trash_with_clock(dir.path(), 7 * day, &clock).unwrap(),
+            0
+        );
+        clock.advance(7 * day);
+        assert_eq!(
+            purge_trash_with_clock(dir.path(), 7 * day, &clock).unwrap(),
+            1
+        );
+    }
+}
@@ -0,0 +1,258 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::de::DeserializeOwned;
+
+use crate::{StorageError, load, parent_dir};
+
+/// How long the file must stay quiet before a burst of events triggers a reload.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// A handle to a running [`watch`]; dropping it stops watching.
+///
+/// The background thread exits once the handle is dropped and any in-flight callback has
+/// returned.
+pub struct StorageWatcher {
+    path: PathBuf,
+    _watcher: RecommendedWatcher,
+}
+
+impl StorageWatcher {
+    /// Returns the path of the watched file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Watches a JSON file and reloads it whenever it changes on disk.
+///
+/// The parent directory is watched rather than the file itself, so the watch survives the
+/// write-temp-then-rename pattern used by most editors (and by [`save`](crate::save)) as well
+/// as the file being deleted and recreated. Events are debounced: a burst of changes results
+/// in a single reload once the file has been quiet for a short while. Each reload is
+/// deserialized into `T` and passed to `on_change` on a background thread; reload failures,
+/// including the file being removed, are passed as `Err` so the caller can keep its previous
+/// value, as are errors reported by the watcher itself.
+///
+/// # Arguments
+///
+/// * `path` - The JSON file to watch
+/// * `on_change` - Called with the freshly loaded data after every change
+///
+/// # Returns
+///
+/// * `Ok(StorageWatcher)` - A handle that keeps the watch alive until dropped
+/// * `Err(StorageError)` - If the parent directory does not exist or cannot be watched
+///
+/// # Example
+///
+/// ```
+/// use std::{sync::mpsc, time::Duration};
+/// use storage_service::{save, watch};
+/// use tempfile::tempdir;
+///
+/// let dir = tempdir().unwrap();
+/// let path = dir.path().join("config.json");
+/// save(&path, 1).unwrap();
+///
+/// let (tx, rx) = mpsc::channel();
+/// let _watcher = watch(&path, move |result: Result<u32, _>| {
+///     let _ = tx.send(result);
+/// })
+/// .unwrap();
+///
+/// save(&path, 2).unwrap();
+/// let reloaded = rx.recv_timeout(Duration::from_secs(5)).unwrap().unwrap();
+/// assert_eq!(reloaded, 2);
+/// ```
+pub fn watch<P, T, F>(path: P, mut on_change: F) -> Result<StorageWatcher, StorageError>
+where
+    P: AsRef<Path>,
+    T: DeserializeOwned,
+    F: FnMut(Result<T, StorageError>) + Send + 'static,
+{
+    let path = path.as_ref().to_path_buf();
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("not a file path: {}", path.display()),
+            )
+        })?
+        .to_owned();
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).map_err(watch_error)?;
+    watcher
+        .watch(parent_dir(&path), RecursiveMode::NonRecursive)
+        .map_err(watch_error)?;
+
+    let is_relevant = move |event: &Event| {
+        !matches!(event.kind, EventKind::Access(_))
+            && event
+                .paths
+                .iter()
+                .any(|changed| changed.file_name() == Some(&file_name))
+    };
+
+    let target = path.clone();
+    thread::spawn(move || run(&rx, is_relevant, &target, &mut on_change));
+
+    Ok(StorageWatcher {
+        path,
+        _watcher: watcher,
+    })
+}
+
+/// Reloads `target` after every debounced burst of relevant events from `rx`, until the
+/// watcher is dropped.
+///
+/// Only relevant events restart the quiet period, so activity on other files in the directory
+/// cannot postpone a reload indefinitely. Watcher errors are passed to `on_change` as they
+/// arrive, including in the middle of a burst.
+fn run<T, F>(
+    rx: &Receiver<notify::Result<Event>>,
+    is_relevant: impl Fn(&Event) -> bool,
+    target: &Path,
+    on_change: &mut F,
+) where
+    T: DeserializeOwned,
+    F: FnMut(Result<T, StorageError>),
+{
+    while let Ok(event) = rx.recv() {
+        match event {
+            Ok(event) if !is_relevant(&event) => continue,
+            Ok(_) => {}
+            Err(err) => {
+                on_change(Err(watch_error(err)));
+                continue;
+            }
+        }
+
+        let mut deadline = Instant::now() + DEBOUNCE;
+        loop {
+            match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(Ok(event)) if is_relevant(&event) => deadline = Instant::now() + DEBOUNCE,
+                Ok(Ok(_)) => {}
+                Ok(Err(err)) => on_change(Err(watch_error(err))),
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+
+        on_change(load(target));
+    }
+}
+
+/// Converts a watcher error into a `StorageError`, keeping I/O errors intact.
+fn watch_error(err: notify::Error) -> StorageError {
+    match err.kind {
+        notify::ErrorKind::Io(err) => StorageError::Io(err),
+        notify::ErrorKind::PathNotFound => {
+            io::Error::new(io::ErrorKind::NotFound, "watched directory not found").into()
+        }
+        _ => io::Error::other(err).into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::save;
+
+    const WAIT: Duration = Duration::from_secs(5);
+
+    #[test]
+    fn watch_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("watched.json");
+        save(&path, 0).unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        let watcher = watch(&path, move |result: Result<u32, _>| {
+            let _ = tx.send(result);
+        })
+        .unwrap();
+        assert_eq!(watcher.path(), path);
+
+        // Unrelated files in the same directory are ignored.
+        save(dir.path().join("other.json"), 99).unwrap();
+
+        // Atomic saves replace the file by renaming; the watch must survive several of them.
+        save(&path, 1).unwrap();
+        assert_eq!(rx.recv_timeout(WAIT).unwrap().unwrap(), 1);
+        save(&path, 2).unwrap();
+        save(&path, 3).unwrap();
+        let last = rx.iter().map(Result::unwrap).find(|&value| value == 3);
+        assert_eq!(last, Some(3));
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            rx.recv_timeout(WAIT).unwrap(),
+            Err(StorageError::Io(err)) if err.kind() == io::ErrorKind::NotFound
+        ));
+
+        drop(watcher);
+        save(&path, 4).unwrap();
+        assert!(rx.recv_timeout(DEBOUNCE * 5).is_err());
+    }
+
+    #[test]
+    fn run_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("watched.json");
+        save(&path, 7).unwrap();
+
+        let relevant = || Ok(Event::default().add_path(path.clone()));
+        let other = || Ok(Event::default().add_path(dir.path().join("other.json")));
+        let (tx, rx) = mpsc::channel();
+        for event in [
+            other(),
+            Err(notify::Error::generic("idle")),
+            relevant(),
+            other(),
+            Err(notify::Error::generic("burst")),
+        ] {
+            tx.send(event).unwrap();
+        }
+
+        // Keep the watcher side open past the quiet period, then stop the loop.
+        thread::spawn(move || {
+            thread::sleep(DEBOUNCE * 3);
+            drop(tx);
+        });
+
+        let is_relevant = |event: &Event| event.paths.contains(&path);
+        let mut results = Vec::new();
+        run(&rx, is_relevant, &path, &mut |result: Result<u32, _>| {
+            results.push(result.map_err(|err| err.to_string()));
+        });
+
+        assert_eq!(results.len(), 3);
+        assert!(matches!(&results[0], Err(err) if err.contains("idle")));
+        assert!(matches!(&results[1], Err(err) if err.contains("burst")));
+        assert_eq!(results[2], Ok(7));
+    }
+
+    #[test]
+    fn watch_missing_directory_test() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("missing").join("watched.json");
+
+        let result = watch(&path, |_: Result<u32, _>| {});
+
+        assert!(matches!(
+            result,
+            Err(StorageError::Io(err)) if err.kind() == io::ErrorKind::NotFound
+        ));
+    }
+}